@@ -1,26 +1,62 @@
 use bevy::{
-    app::{App, Plugin, Update},
-    prelude::{in_state, EventReader, IntoSystemConfigs, OnEnter, Query, With},
+    app::{App, Plugin, Startup, Update},
+    asset::{Assets, Handle},
+    log::error,
+    math::Vec3,
+    prelude::{
+        in_state, Bundle, Commands, Component, EventReader, Image, IntoSystemConfigs, NonSend,
+        OnEnter, Query, ResMut, Transform, Visibility, With,
+    },
+    sprite::{Anchor, Sprite, SpriteBundle},
     window::{CursorGrabMode, CursorIcon, PrimaryWindow, Window},
 };
-use pixlib_parser::runner::CursorEvent;
+use pixlib_parser::runner::{classes::Mouse, CnvContent, CursorEvent};
 
-use super::events_plugin::PixlibCursorEvent;
-use crate::AppState;
+use super::{
+    events_plugin::PixlibCursorEvent, graphics_plugin::LoadedGraphicsIdentifier,
+    scripts_plugin::ScriptRunner,
+};
+use crate::{
+    util::{animation_data_to_handle, image_data_to_handle},
+    AppState,
+};
+
+const CURSOR_Z: f32 = 1000f32;
 
 #[derive(Debug, Default)]
 pub struct CursorPlugin;
 
 impl Plugin for CursorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            update_cursor.run_if(in_state(AppState::SceneViewer)),
-        )
-        .add_systems(OnEnter(AppState::SceneChooser), reset_cursor);
+        app.add_systems(Startup, create_cursor_graphics)
+            .add_systems(
+                Update,
+                (update_cursor, draw_cursor).run_if(in_state(AppState::SceneViewer)),
+            )
+            .add_systems(OnEnter(AppState::SceneChooser), reset_cursor);
     }
 }
 
+#[derive(Component, Debug, Default)]
+pub struct CursorGraphicsMarker;
+
+#[derive(Bundle, Default)]
+pub struct CursorGraphicsBundle {
+    pub marker: CursorGraphicsMarker,
+    pub identifier: LoadedGraphicsIdentifier,
+    pub sprite: SpriteBundle,
+}
+
+fn create_cursor_graphics(mut commands: Commands) {
+    commands.spawn(CursorGraphicsBundle {
+        sprite: SpriteBundle {
+            visibility: Visibility::Hidden,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+}
+
 fn update_cursor(
     mut reader: EventReader<PixlibCursorEvent>,
     mut windows: Query<&mut Window, With<PrimaryWindow>>,
@@ -36,11 +72,99 @@ fn update_cursor(
             CursorEvent::CursorShown => window.cursor.visible = true,
             CursorEvent::CursorSetToPointer => window.cursor.icon = CursorIcon::Pointer,
             CursorEvent::CursorSetToDefault => window.cursor.icon = CursorIcon::Default,
+            CursorEvent::CursorSetToImage(name) => window.cursor.icon = get_cursor_icon(name),
         };
     }
 }
 
-fn reset_cursor(mut windows: Query<&mut Window, With<PrimaryWindow>>) {
+/// Draws the graphics object set with `MOUSE^SET` at the cursor position,
+/// hiding the system cursor while it is shown.
+fn draw_cursor(
+    mut textures: ResMut<Assets<Image>>,
+    mut query: Query<
+        (
+            &mut LoadedGraphicsIdentifier,
+            &mut Sprite,
+            &mut Transform,
+            &mut Handle<Image>,
+            &mut Visibility,
+        ),
+        With<CursorGraphicsMarker>,
+    >,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    runner: NonSend<ScriptRunner>,
+) {
+    let Ok((mut ident, mut sprite, mut transform, mut handle, mut visibility)) =
+        query.get_single_mut()
+    else {
+        return;
+    };
+    *visibility = Visibility::Hidden;
+    let Some(cursor_image) = Mouse::get_cursor_image().ok().flatten() else {
+        return;
+    };
+    let is_mouse_visible = Mouse::is_visible().unwrap_or_default();
+    let object = runner
+        .get_object(&cursor_image)
+        .filter(|_| is_mouse_visible);
+    let hash = match object.as_ref().map(|o| &o.content) {
+        Some(CnvContent::Image(image)) => image
+            .get_image_to_show()
+            .inspect_err(|e| error!("Error getting cursor image: {:?}", e))
+            .ok()
+            .flatten()
+            .map(|(image_definition, image_data)| {
+                if !ident.0.is_some_and(|h| h == image_data.hash) {
+                    *handle = image_data_to_handle(&mut textures, &image_definition, &image_data);
+                }
+                image_data.hash
+            }),
+        Some(CnvContent::Animation(animation)) => animation
+            .get_frame_to_show()
+            .inspect_err(|e| error!("Error getting cursor frame: {:?}", e))
+            .ok()
+            .flatten()
+            .map(|(rect, sprite_data)| {
+                if !ident.0.is_some_and(|h| h == sprite_data.hash) {
+                    *handle = animation_data_to_handle(&mut textures, rect, &sprite_data);
+                }
+                sprite_data.hash
+            }),
+        _ => None,
+    };
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.cursor.visible = is_mouse_visible && hash.is_none();
+    }
+    let Some(hash) = hash else {
+        return;
+    };
+    ident.0 = Some(hash);
+    let (x, y) = Mouse::get_position().unwrap_or_default();
+    *visibility = Visibility::Visible;
+    sprite.flip_x = false;
+    sprite.flip_y = false;
+    sprite.anchor = Anchor::TopLeft;
+    *transform =
+        Transform::from_xyz(x as f32, y as f32, CURSOR_Z).with_scale(Vec3::new(1f32, -1f32, 1f32));
+}
+
+fn get_cursor_icon(name: &str) -> CursorIcon {
+    match name.to_uppercase().as_ref() {
+        "HAND" | "POINTER" => CursorIcon::Pointer,
+        "WAIT" => CursorIcon::Wait,
+        "MOVE" => CursorIcon::Move,
+        "TEXT" => CursorIcon::Text,
+        _ => CursorIcon::Default,
+    }
+}
+
+fn reset_cursor(
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut cursor_graphics: Query<&mut Visibility, With<CursorGraphicsMarker>>,
+) {
+    for mut visibility in cursor_graphics.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
     let mut window = windows.single_mut();
     window.cursor.grab_mode = CursorGrabMode::None;
     window.cursor.visible = true;
@@ -8,6 +8,7 @@ use bevy::{
         IntoSystemConfigs, NonSend, OnExit, Query, ResMut, SpatialBundle, Transform, Visibility,
     },
     sprite::{Anchor, Sprite, SpriteBundle},
+    window::WindowResized,
 };
 
 use pixlib_parser::runner::{classes::GeneralGraphics, CnvContent, ScenePath, ScriptEvent};
@@ -27,6 +28,7 @@ pub struct GraphicsPlugin;
 impl Plugin for GraphicsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, create_pool)
+            .add_systems(Update, update_window_size)
             .add_systems(
                 Update,
                 (update_background, update_images, update_animations)
@@ -85,6 +87,12 @@ pub fn create_pool(mut commands: Commands) {
     info!("Created a pool of {} graphics objects", POOL_SIZE);
 }
 
+fn update_window_size(mut reader: EventReader<WindowResized>, runner: NonSend<ScriptRunner>) {
+    if let Some(evt) = reader.read().last() {
+        runner.set_window_size(evt.width as usize, evt.height as usize);
+    }
+}
+
 fn run_if_any_script_loaded(mut reader: EventReader<PixlibScriptEvent>) -> bool {
     let mut any_script_loaded = false;
     for evt in reader.read() {
@@ -244,6 +252,7 @@ pub fn update_images(
     )>,
     runner: NonSend<ScriptRunner>,
 ) {
+    let hidden_by_groups = runner.get_objects_hidden_by_groups();
     for (marker, mut ident, mut sprite, mut transform, mut handle, mut visibility) in
         query.iter_mut()
     {
@@ -270,7 +279,11 @@ pub fn update_images(
             *visibility = Visibility::Hidden;
             continue;
         };
-        *visibility = if image.is_visible().unwrap() {
+        *visibility = if runner
+            .is_effectively_visible(&object, &hidden_by_groups)
+            .inspect_err(|e| error!("Error checking visibility: {:?}", e))
+            .unwrap_or_default()
+        {
             Visibility::Visible
         } else {
             Visibility::Hidden
@@ -311,6 +324,7 @@ pub fn update_animations(
     )>,
     runner: NonSend<ScriptRunner>,
 ) {
+    let hidden_by_groups = runner.get_objects_hidden_by_groups();
     for (marker, mut ident, mut sprite, mut transform, mut handle, mut visibility) in
         query.iter_mut()
     {
@@ -343,7 +357,11 @@ pub fn update_animations(
             *visibility = Visibility::Hidden;
             continue;
         };
-        *visibility = if animation.is_visible().unwrap() {
+        *visibility = if runner
+            .is_effectively_visible(&object, &hidden_by_groups)
+            .inspect_err(|e| error!("Error checking visibility: {:?}", e))
+            .unwrap_or_default()
+        {
             Visibility::Visible
         } else {
             Visibility::Hidden
@@ -100,6 +100,11 @@ pub fn queue_keyboard_input(keys: Res<ButtonInput<KeyCode>>, runner: NonSend<Scr
             key_code: from_bevy_key_code(key),
         });
     }
+    for key in keys.get_just_released() {
+        in_events.push_back(KeyboardEvent::KeyReleased {
+            key_code: from_bevy_key_code(key),
+        });
+    }
 }
 
 fn from_bevy_key_code(key_code: &KeyCode) -> KeyboardKey {
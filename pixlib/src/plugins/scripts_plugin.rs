@@ -67,11 +67,6 @@ fn reload_scene_script(script_runner: NonSend<ScriptRunner>, chosen_scene: Res<C
     if !chosen_scene.is_changed() {
         return;
     }
-    script_runner
-        .scripts
-        .borrow_mut()
-        .remove_scene_script()
-        .unwrap();
     let scene_name = chosen_scene.list[chosen_scene.index].name.clone();
     script_runner.change_scene(&scene_name).unwrap();
 }
@@ -225,7 +220,12 @@ fn reload_main_script(
     };
     //#endregion
 
-    let scene_list = episode.get_scene_list();
+    let scene_list = script_runner
+        .list_episodes_and_scenes()
+        .into_iter()
+        .find(|e| e.name == episode_name)
+        .map(|e| e.scenes)
+        .unwrap_or_default();
     if scene_list.is_empty() {
         panic!(
             "Invalid episode object {} - no scenes defined",
@@ -20,7 +20,9 @@ use bevy_kira_audio::{
     prelude::StaticSoundData, Audio, AudioControl, AudioInstance, AudioSource, AudioTween,
     PlaybackState,
 };
-use pixlib_parser::runner::{CnvContent, MultimediaEvents, ScriptEvent, SoundEvent, SoundSource};
+use pixlib_parser::runner::{
+    CnvContent, MultimediaEvents, ScriptEvent, SoundBackend, SoundEvent, SoundSource,
+};
 
 use crate::AppState;
 
@@ -31,6 +33,7 @@ use super::{
 
 const POOL_SIZE: usize = 1111;
 const EASING: AudioTween = AudioTween::linear(Duration::ZERO);
+const BASE_VOLUME: f64 = 0.2;
 
 #[derive(Debug, Default)]
 pub struct SoundsPlugin;
@@ -107,9 +110,13 @@ pub struct SoundsBundle {
     pub marker: SoundsMarker,
     pub identifier: LoadedSoundsIdentifier,
     handle: SoundsInstanceHandle,
+    sample_rate: SoundsSampleRate,
     previous_state: SoundsState,
 }
 
+#[derive(Component, Debug, Clone, Copy, Default)]
+struct SoundsSampleRate(Option<u32>);
+
 #[derive(Component, Debug, Clone, Default)]
 struct SoundsInstanceHandle(Option<Handle<AudioInstance>>);
 
@@ -271,6 +278,7 @@ fn assign_pool(
             **next = Some(SoundSource::Sound {
                 script_path: script.path.clone(),
                 object_name: object.name.clone(),
+                instance: 0,
             });
             sound_counter += 1;
         }
@@ -352,9 +360,10 @@ fn update_sounds(
     audio: Res<Audio>,
     pool_query: Query<&SoundsPoolMarker>,
     mut query: Query<(
-        &SoundsMarker,
+        &mut SoundsMarker,
         &mut LoadedSoundsIdentifier,
         &mut SoundsInstanceHandle,
+        &mut SoundsSampleRate,
         &mut SoundsState,
     )>,
     mut audio_sources: ResMut<Assets<AudioSource>>,
@@ -367,12 +376,15 @@ fn update_sounds(
     for evt in reader.read() {
         let evt_source = match &evt.event {
             SoundEvent::SoundLoaded { source, .. } => source,
+            SoundEvent::SoundParametersChanged { source, .. } => source,
             SoundEvent::SoundStarted(source) => source,
             SoundEvent::SoundPaused(source) => source,
             SoundEvent::SoundResumed(source) => source,
             SoundEvent::SoundStopped(source) => source,
         };
-        if reloaded_sources.contains(evt_source)
+        if reloaded_sources
+            .iter()
+            .any(|s: &SoundSource| s.is_same_object(evt_source))
             && !matches!(&evt.event, SoundEvent::SoundLoaded { .. })
         {
             continue;
@@ -380,30 +392,34 @@ fn update_sounds(
         evt.mark_as_processed();
         // info!("Read sound event: {}", evt.event);
         let mut any_marker_matched = false;
-        for (marker, mut ident, mut handle, mut state) in query.iter_mut() {
+        for (mut marker, mut ident, mut handle, mut sample_rate, mut state) in query.iter_mut() {
             let Some(snd_source) = &**marker else {
                 continue;
             };
-            if evt_source != snd_source {
+            if !evt_source.is_same_object(snd_source) {
                 continue;
             }
+            if matches!(&evt.event, SoundEvent::SoundStarted(_)) {
+                // finished events report the most recently started playback
+                **marker = Some(evt_source.clone());
+            }
             any_marker_matched = true;
             // info!("Matched the sounds pool element");
             match &evt.event {
                 SoundEvent::SoundLoaded { sound_data, .. } => {
                     if !ident.is_some_and(|h| h == sound_data.hash) {
-                        let source = audio_sources.add(AudioSource {
-                            sound: StaticSoundData::from_cursor(
-                                Cursor::new(sound_data.data.as_ref().clone()),
-                                Default::default(),
-                            )
-                            .unwrap(),
-                        });
+                        let sound = StaticSoundData::from_cursor(
+                            Cursor::new(sound_data.data.as_ref().clone()),
+                            Default::default(),
+                        )
+                        .unwrap();
+                        sample_rate.0 = Some(sound.sample_rate);
+                        let source = audio_sources.add(AudioSource { sound });
                         let new_handle: Handle<AudioInstance> = audio
                             .play(source)
                             .looped()
                             .paused()
-                            .with_volume(0.2)
+                            .with_volume(BASE_VOLUME)
                             .handle();
                         if let Some(handle) = handle.replace(new_handle) {
                             if let Some(mut instance) = audio_instances.remove(&handle) {
@@ -423,27 +439,11 @@ fn update_sounds(
                         // warn!("Cannot retrieve audio instance for sound {:?}", snd_source);
                         break;
                     };
-                    match &evt.event {
-                        SoundEvent::SoundStarted(_) => {
-                            instance.resume(EASING);
-                            // info!("Started sound {:?}", snd_source);
-                        }
-                        SoundEvent::SoundPaused(_) => {
-                            instance.pause(EASING);
-                            // info!("Paused sound {:?}", snd_source);
-                        }
-                        SoundEvent::SoundResumed(_) => {
-                            instance.resume(EASING);
-                            // info!("Resumed sound {:?}", snd_source);
-                        }
-                        SoundEvent::SoundStopped(_) => {
-                            instance.pause(EASING);
-                            instance.seek_to(0.0);
-                            state.position = Some(0.0);
-                            // info!("Stopped sound {:?}", snd_source);
-                        }
-                        _ => unreachable!(),
-                    };
+                    evt.event.apply_to(&mut KiraSoundInstance {
+                        instance,
+                        sample_rate: sample_rate.0,
+                        state: &mut state,
+                    });
                 }
             };
         }
@@ -452,3 +452,50 @@ fn update_sounds(
         }
     }
 }
+
+/// Sound backend driving a single pooled audio instance.
+/// Events are matched with the instance by the pool beforehand.
+struct KiraSoundInstance<'a> {
+    instance: &'a mut AudioInstance,
+    sample_rate: Option<u32>,
+    state: &'a mut SoundsState,
+}
+
+impl SoundBackend for KiraSoundInstance<'_> {
+    fn play(&mut self, _source: &SoundSource) {
+        self.instance.resume(EASING);
+    }
+
+    fn stop(&mut self, _source: &SoundSource) {
+        self.instance.pause(EASING);
+        self.instance.seek_to(0.0);
+        self.state.position = Some(0.0);
+    }
+
+    fn pause(&mut self, _source: &SoundSource) {
+        self.instance.pause(EASING);
+    }
+
+    fn resume(&mut self, _source: &SoundSource) {
+        self.instance.resume(EASING);
+    }
+
+    fn set_volume(&mut self, _source: &SoundSource, volume: f32) {
+        self.instance
+            .set_volume(BASE_VOLUME * volume as f64, EASING);
+    }
+
+    fn set_pan(&mut self, _source: &SoundSource, pan: f32) {
+        self.instance.set_panning((pan as f64 + 1.0) / 2.0, EASING);
+    }
+
+    fn set_frequency(&mut self, _source: &SoundSource, frequency: Option<usize>) {
+        let playback_rate = match (frequency, self.sample_rate) {
+            (Some(frequency), Some(sample_rate)) if sample_rate > 0 => {
+                frequency as f64 / sample_rate as f64
+            }
+            _ => 1.0,
+        };
+        self.instance.set_playback_rate(playback_rate, EASING);
+    }
+}
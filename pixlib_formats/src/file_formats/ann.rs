@@ -12,6 +12,7 @@ use nom::{
 };
 
 use super::{ColorFormat, CompressionType, DecodedStr, ImageData};
+use crate::Rect;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AnnHeader {
@@ -97,6 +98,7 @@ pub struct SequenceHeader {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoopingSettings {
     LoopingAfter(usize),
     NoLooping,
@@ -443,3 +445,155 @@ pub fn parse_ann(data: &[u8]) -> AnnFile {
         sprites,
     }
 }
+
+/// Overview of an animation file, meant for tools displaying its properties.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnnSummary {
+    pub sprite_count: usize,
+    pub sequence_count: usize,
+    pub frame_count: usize,
+    pub frames_per_second: u32,
+    /// Smallest rect containing every frame's sprite at its final position, if any.
+    pub bounds: Option<Rect>,
+}
+
+impl AnnFile<'_> {
+    /// Frames of every sequence in order, paired with the sprites they show.
+    fn frame_sprites(&self) -> impl Iterator<Item = (&FrameHeader, &Sprite<'_>)> {
+        self.sequences.iter().flat_map(|sequence| {
+            sequence
+                .frames
+                .iter()
+                .zip(sequence.header.frame_to_sprite_mapping.iter())
+                .filter_map(|(frame, sprite_idx)| {
+                    Some((frame, self.sprites.get(usize::from(*sprite_idx))?))
+                })
+        })
+    }
+
+    fn frame_rect(frame: &FrameHeader, sprite: &SpriteHeader) -> Rect {
+        Rect::from(
+            (
+                isize::from(frame.x_position_px) + isize::from(sprite.x_position_px),
+                isize::from(frame.y_position_px) + isize::from(sprite.y_position_px),
+            ),
+            (sprite.width_px.into(), sprite.height_px.into()),
+        )
+    }
+
+    pub fn summary(&self) -> AnnSummary {
+        let frame_rects = self
+            .frame_sprites()
+            .map(|(frame, sprite)| Self::frame_rect(frame, &sprite.header));
+        let bounds = frame_rects.reduce(|bounds, rect| Rect {
+            top_left_x: bounds.top_left_x.min(rect.top_left_x),
+            top_left_y: bounds.top_left_y.min(rect.top_left_y),
+            bottom_right_x: bounds.bottom_right_x.max(rect.bottom_right_x),
+            bottom_right_y: bounds.bottom_right_y.max(rect.bottom_right_y),
+        });
+        AnnSummary {
+            sprite_count: self.sprites.len(),
+            sequence_count: self.sequences.len(),
+            frame_count: self.sequences.iter().map(|s| s.frames.len()).sum(),
+            frames_per_second: self.header.frames_per_second,
+            bounds,
+        }
+    }
+
+    /// Decodes every frame of every sequence (in order) to its final rect and RGBA8888 pixels.
+    pub fn to_rgba_frames(&self) -> Vec<(Rect, Vec<u8>)> {
+        self.frame_sprites()
+            .map(|(frame, sprite)| {
+                let pixels = sprite
+                    .image_data
+                    .to_rgba8888(self.header.color_format, sprite.header.compression_type);
+                (
+                    Self::frame_rect(frame, &sprite.header),
+                    pixels.as_ref().clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_ann_summary {
+    use super::*;
+    use crate::test_utils::{self, TestFrame, TestSequence, TestSprite};
+
+    /// Sprite size followed by its offset.
+    type SpriteSpec = ((u16, u16), (i16, i16));
+
+    /// Builds a red animation with a single sequence, each frame showing the sprite of the same index.
+    fn build_ann(frame_offsets: &[(i16, i16)], sprites: &[SpriteSpec]) -> Vec<u8> {
+        let frame_names = (0..frame_offsets.len())
+            .map(|i| format!("FRAME{}", i))
+            .collect::<Vec<_>>();
+        let frames = frame_offsets
+            .iter()
+            .zip(&frame_names)
+            .enumerate()
+            .map(|(i, (&offset_px, name))| TestFrame {
+                name,
+                sprite_idx: i as u16,
+                offset_px,
+                sfx: None,
+            })
+            .collect::<Vec<_>>();
+        let sprites = sprites
+            .iter()
+            .map(|&(size, offset_px)| TestSprite::opaque(size, offset_px, 0xf800))
+            .collect::<Vec<_>>();
+        test_utils::build_ann(
+            12,
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &frames,
+            }],
+            &sprites,
+        )
+    }
+
+    #[test]
+    fn summary_should_aggregate_counts_and_bounds() {
+        let data = build_ann(&[(0, 0), (10, -5)], &[((4, 3), (-2, 1)), ((6, 2), (0, 0))]);
+
+        let summary = parse_ann(&data).summary();
+
+        assert_eq!(
+            summary,
+            AnnSummary {
+                sprite_count: 2,
+                sequence_count: 1,
+                frame_count: 2,
+                frames_per_second: 12,
+                bounds: Some(Rect::from((-2, -5), (18, 9))),
+            }
+        );
+    }
+
+    #[test]
+    fn summary_of_empty_animation_should_have_no_bounds() {
+        let data = build_ann(&[], &[]);
+
+        let summary = parse_ann(&data).summary();
+
+        assert_eq!(summary.frame_count, 0);
+        assert_eq!(summary.bounds, None);
+    }
+
+    #[test]
+    fn frames_should_decode_to_rgba_at_final_positions() {
+        let data = build_ann(&[(0, 0), (10, -5)], &[((4, 3), (-2, 1)), ((6, 2), (0, 0))]);
+
+        let frames = parse_ann(&data).to_rgba_frames();
+
+        assert_eq!(frames.len(), 2);
+        let (rect, pixels) = &frames[0];
+        assert_eq!(*rect, Rect::from((-2, 1), (4, 3)));
+        assert_eq!(pixels.len(), 4 * 3 * 4);
+        assert_eq!(pixels[4 * 5..4 * 6], [255, 0, 0, 255]);
+        assert_eq!(frames[1].0, Rect::from((10, -5), (6, 2)));
+    }
+}
@@ -13,6 +13,81 @@ lazy_static! {
     pub static ref STRING_ENCODING: Coding = Coding::new(1250).unwrap();
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+pub enum FileFormat {
+    Ann,
+    Arr,
+    Font,
+    Img,
+    Seq,
+    Wav,
+}
+
+/// Guesses the format of a file from its contents.
+///
+/// Fonts share the ANN container and cannot be told apart from animations,
+/// so they are reported as [`FileFormat::Ann`]. ARR files have no magic number,
+/// so they are only recognized by a plausible first element type.
+pub fn detect_format(data: &[u8]) -> Option<FileFormat> {
+    if data.starts_with(b"PIK\0") {
+        return Some(FileFormat::Img);
+    }
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WAVE") {
+        return Some(FileFormat::Wav);
+    }
+    if ann::header(data).is_ok() {
+        return Some(FileFormat::Ann);
+    }
+    if is_seq(data) {
+        return Some(FileFormat::Seq);
+    }
+    if let Ok((rest, header)) = arr::header(data) {
+        let is_arr = if header.size == 0 {
+            rest.is_empty()
+        } else {
+            arr::element(rest).is_ok()
+        };
+        if is_arr {
+            return Some(FileFormat::Arr);
+        }
+    }
+    None
+}
+
+fn is_seq(data: &[u8]) -> bool {
+    let Some(name_line) = data.split(|c| *c == b'\n').next() else {
+        return false;
+    };
+    if is_cipher_header(name_line) {
+        // encrypted scripts cannot be inspected without decoding them
+        return true;
+    }
+    let Some(name) = name_line.strip_prefix(b"NAME=") else {
+        return false;
+    };
+    let name = name.trim_ascii();
+    !name.is_empty()
+        && data
+            .windows(name.len() + 1)
+            .any(|w| w[..name.len()] == *name && w[name.len()] == b':')
+}
+
+/// Checks for the `{<C:N>}` / `{<D:N>}` line preceding encrypted scripts.
+fn is_cipher_header(line: &[u8]) -> bool {
+    let Some(inner) = line
+        .trim_ascii_end()
+        .strip_prefix(b"{<")
+        .and_then(|l| l.strip_suffix(b">}"))
+    else {
+        return false;
+    };
+    matches!(
+        inner,
+        [b'C' | b'D', b':', digits @ ..]
+            if (1..=10).contains(&digits.len()) && digits.iter().all(u8::is_ascii_digit)
+    )
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ImageData<'a> {
     pub color: &'a [u8],
@@ -156,3 +231,81 @@ impl AsRef<str> for DecodedStr {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{build_ann, TestSequence};
+
+    fn build_empty_ann(sequence_name: &str) -> Vec<u8> {
+        let sequence = TestSequence {
+            name: sequence_name,
+            looping_after: 0,
+            frames: &[],
+        };
+        build_ann(16, &[sequence], &[])
+    }
+
+    #[test]
+    fn should_detect_img() {
+        assert_eq!(
+            detect_format(b"PIK\0\x01\0\0\0\x01\0\0\0"),
+            Some(FileFormat::Img)
+        );
+    }
+
+    #[test]
+    fn should_detect_ann() {
+        assert_eq!(
+            detect_format(&build_empty_ann("WALK")),
+            Some(FileFormat::Ann)
+        );
+    }
+
+    #[test]
+    fn should_detect_ann_with_single_character_sequence_name() {
+        assert_eq!(detect_format(&build_empty_ann("A")), Some(FileFormat::Ann));
+    }
+
+    #[test]
+    fn should_detect_wav() {
+        assert_eq!(
+            detect_format(b"RIFF\x24\0\0\0WAVEfmt "),
+            Some(FileFormat::Wav)
+        );
+    }
+
+    #[test]
+    fn should_detect_seq() {
+        assert_eq!(
+            detect_format(b"NAME=TEST\nTEST:TYPE=SEQUENCE\n"),
+            Some(FileFormat::Seq)
+        );
+    }
+
+    #[test]
+    fn should_detect_encrypted_seq() {
+        assert_eq!(
+            detect_format(b"{<C:6>}\r\n\x8a\x12\xf0"),
+            Some(FileFormat::Seq)
+        );
+        assert_eq!(
+            detect_format(b"{<D:120>}\n\x8a\x12\xf0"),
+            Some(FileFormat::Seq)
+        );
+        assert_eq!(detect_format(b"{<X:6>}\n\x8a\x12\xf0"), None);
+    }
+
+    #[test]
+    fn should_detect_arr() {
+        let data = arr::serialize_arr(&[arr::ElementData::Integer(42)]).unwrap();
+        assert_eq!(detect_format(&data), Some(FileFormat::Arr));
+        assert_eq!(detect_format(&[0, 0, 0, 0]), Some(FileFormat::Arr));
+    }
+
+    #[test]
+    fn should_not_detect_unknown_data() {
+        assert_eq!(detect_format(b"hello, world"), None);
+        assert_eq!(detect_format(&[]), None);
+    }
+}
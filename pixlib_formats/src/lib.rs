@@ -1,10 +1,15 @@
 pub mod compression_algorithms;
 pub mod file_formats;
+#[cfg(any(test, feature = "test_utils"))]
+pub mod test_utils;
+
+pub use file_formats::{detect_format, FileFormat};
 
 #[allow(clippy::assertions_on_constants)]
 const _: () = assert!(usize::BITS >= u32::BITS);
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rect {
     pub top_left_x: isize,
     pub top_left_y: isize,
@@ -29,9 +34,7 @@ impl Rect {
             bottom_right_x: self.bottom_right_x.min(other.bottom_right_x),
             bottom_right_y: self.bottom_right_y.min(other.bottom_right_y),
         };
-        if intersection.bottom_right_x <= intersection.top_left_x
-            || intersection.bottom_right_y <= intersection.top_left_y
-        {
+        if intersection.is_degenerate() {
             None
         } else {
             Some(intersection)
@@ -43,12 +46,21 @@ impl Rect {
             && y.clamp(self.top_left_y, self.bottom_right_y) == y
     }
 
+    /// Returns `true` for rects with no area, including inverted ones.
+    pub fn is_degenerate(&self) -> bool {
+        self.bottom_right_x <= self.top_left_x || self.bottom_right_y <= self.top_left_y
+    }
+
     pub fn get_width(&self) -> usize {
-        (self.bottom_right_x - self.top_left_x) as usize
+        self.bottom_right_x
+            .checked_sub(self.top_left_x)
+            .map_or(0, |w| w.max(0) as usize)
     }
 
     pub fn get_height(&self) -> usize {
-        (self.bottom_right_y - self.top_left_y) as usize
+        self.bottom_right_y
+            .checked_sub(self.top_left_y)
+            .map_or(0, |h| h.max(0) as usize)
     }
 
     pub fn get_center(&self) -> (isize, isize) {
@@ -69,3 +81,26 @@ impl From<(isize, isize, isize, isize)> for Rect {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverted_rect_should_have_zero_size() {
+        let rect: Rect = (10, 20, -5, 0).into();
+
+        assert!(rect.is_degenerate());
+        assert_eq!(rect.get_width(), 0);
+        assert_eq!(rect.get_height(), 0);
+    }
+
+    #[test]
+    fn regular_rect_should_have_its_size() {
+        let rect = Rect::from((-5, 0), (15, 20));
+
+        assert!(!rect.is_degenerate());
+        assert_eq!(rect.get_width(), 15);
+        assert_eq!(rect.get_height(), 20);
+    }
+}
@@ -0,0 +1,114 @@
+//! Builders of minimal, uncompressed files for use in tests.
+
+pub struct TestFrame<'a> {
+    pub name: &'a str,
+    pub sprite_idx: u16,
+    pub offset_px: (i16, i16),
+    pub sfx: Option<&'a str>,
+}
+
+pub struct TestSequence<'a> {
+    pub name: &'a str,
+    /// Frame count after which the sequence wraps around, 0 for no looping.
+    pub looping_after: u32,
+    pub frames: &'a [TestFrame<'a>],
+}
+
+pub struct TestSprite {
+    pub size: (u16, u16),
+    pub offset_px: (i16, i16),
+    /// RGB565 color shared by every pixel.
+    pub color: u16,
+    /// Per-pixel alpha, row by row.
+    pub alpha: Vec<u8>,
+}
+
+impl TestSprite {
+    /// Creates a sprite filled with a single opaque color.
+    pub fn opaque(size: (u16, u16), offset_px: (i16, i16), color: u16) -> Self {
+        Self {
+            size,
+            offset_px,
+            color,
+            alpha: vec![255; size.0 as usize * size.1 as usize],
+        }
+    }
+}
+
+fn push_str(buffer: &mut Vec<u8>, string: &str) {
+    buffer.extend((string.len() as u32 + 1).to_le_bytes());
+    buffer.extend(string.as_bytes());
+    buffer.push(0);
+}
+
+fn push_fixed_str(buffer: &mut Vec<u8>, string: &str, length: usize) {
+    let mut bytes = string.as_bytes().to_owned();
+    bytes.resize(length, 0);
+    buffer.extend(bytes);
+}
+
+/// Builds an RGB565 ANN file out of the given sequences and sprites.
+pub fn build_ann(
+    frames_per_second: u32,
+    sequences: &[TestSequence],
+    sprites: &[TestSprite],
+) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend(b"NVM\0");
+    buffer.extend((sprites.len() as u16).to_le_bytes());
+    buffer.extend(16u16.to_le_bytes());
+    buffer.extend((sequences.len() as u16).to_le_bytes());
+    push_fixed_str(&mut buffer, "", 13);
+    buffer.extend(frames_per_second.to_le_bytes());
+    buffer.extend(0u32.to_le_bytes());
+    buffer.push(255);
+    buffer.extend([0u8; 12]);
+    push_str(&mut buffer, "TEST");
+    buffer.extend(0u32.to_le_bytes());
+    for sequence in sequences {
+        push_fixed_str(&mut buffer, sequence.name, 32);
+        buffer.extend((sequence.frames.len() as u16).to_le_bytes());
+        buffer.extend([0u8; 6]);
+        buffer.extend(sequence.looping_after.to_le_bytes());
+        buffer.extend([0u8; 10]);
+        buffer.push(255);
+        buffer.extend([0u8; 12]);
+        for frame in sequence.frames {
+            buffer.extend(frame.sprite_idx.to_le_bytes());
+        }
+        for frame in sequence.frames {
+            buffer.extend([0u8; 8]);
+            buffer.extend(frame.offset_px.0.to_le_bytes());
+            buffer.extend(frame.offset_px.1.to_le_bytes());
+            buffer.extend(0u32.to_le_bytes());
+            buffer.extend((frame.sfx.is_some() as u32).to_le_bytes());
+            buffer.extend(0u32.to_le_bytes());
+            buffer.push(255);
+            buffer.push(0);
+            buffer.extend(0u32.to_le_bytes());
+            push_str(&mut buffer, frame.name);
+            if let Some(sfx) = frame.sfx {
+                push_str(&mut buffer, sfx);
+            }
+        }
+    }
+    for sprite in sprites {
+        let (width, height) = sprite.size;
+        let pixel_count = width as u32 * height as u32;
+        buffer.extend(width.to_le_bytes());
+        buffer.extend(height.to_le_bytes());
+        buffer.extend(sprite.offset_px.0.to_le_bytes());
+        buffer.extend(sprite.offset_px.1.to_le_bytes());
+        buffer.extend(0u16.to_le_bytes());
+        buffer.extend((pixel_count * 2).to_le_bytes());
+        buffer.extend([0u8; 14]);
+        buffer.extend(pixel_count.to_le_bytes());
+        push_fixed_str(&mut buffer, "SPRITE", 20);
+    }
+    for sprite in sprites {
+        let pixel_count = sprite.size.0 as usize * sprite.size.1 as usize;
+        buffer.extend(sprite.color.to_le_bytes().repeat(pixel_count));
+        buffer.extend(&sprite.alpha);
+    }
+    buffer
+}
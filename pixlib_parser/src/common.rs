@@ -163,6 +163,18 @@ impl<I: Issue> IssueManager<I> {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IssueLogger;
+
+impl<I: Issue> IssueHandler<I> for IssueLogger {
+    fn handle(&mut self, issue: I) {
+        match issue.kind() {
+            IssueKind::Warning => warn!("{}", issue),
+            _ => error!("{}", issue),
+        }
+    }
+}
+
 pub type Spanned<Tok, Loc, Error> = Result<(Loc, Tok, Loc), Error>;
 
 pub trait DroppableRefMut {
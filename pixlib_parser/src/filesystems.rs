@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     io::{Cursor, Read},
     path::PathBuf,
     sync::Arc,
@@ -8,7 +9,7 @@ use cdfs::{DirectoryEntry, ISOError, ISO9660};
 use log::{error, info, trace};
 use zip::{result::ZipError, ZipArchive};
 
-use crate::runner::{FileSystem, Path};
+use crate::runner::{FileSystem, Path, ResolveStrategy};
 
 #[derive(Debug)]
 pub struct DummyFileSystem;
@@ -25,6 +26,7 @@ impl FileSystem for DummyFileSystem {
 
 pub struct CompressedPatch {
     handle: ZipArchive<Cursor<Vec<u8>>>,
+    resolve_strategy: ResolveStrategy,
 }
 
 impl std::fmt::Debug for CompressedPatch {
@@ -38,9 +40,8 @@ impl std::fmt::Debug for CompressedPatch {
 impl FileSystem for CompressedPatch {
     fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
         let sought_name = self
-            .handle
-            .file_names()
-            .find(|n| n.eq_ignore_ascii_case(filename))
+            .resolve_strategy
+            .resolve(filename, self.handle.file_names())
             .map(|s| s.to_owned());
         let Some(sought_name) = sought_name else {
             return Err(std::io::Error::from(std::io::ErrorKind::NotFound));
@@ -73,12 +74,21 @@ impl CompressedPatch {
     pub fn new(data: Vec<u8>) -> Result<Self, ZipError> {
         Ok(Self {
             handle: ZipArchive::new(Cursor::new(data))?,
+            resolve_strategy: ResolveStrategy::default(),
         })
     }
+
+    pub fn with_resolve_strategy(self, resolve_strategy: ResolveStrategy) -> Self {
+        Self {
+            resolve_strategy,
+            ..self
+        }
+    }
 }
 
 pub struct InsertedDisk {
     handle: ISO9660<Cursor<Vec<u8>>>,
+    resolve_strategy: ResolveStrategy,
 }
 
 impl std::fmt::Debug for InsertedDisk {
@@ -92,17 +102,18 @@ impl std::fmt::Debug for InsertedDisk {
 impl FileSystem for InsertedDisk {
     fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
         let handle = &self.handle;
-        if let Ok(Some(DirectoryEntry::File(file))) =
-            handle.open(&filename.replace('\\', "/").to_ascii_lowercase())
-        {
-            let mut wrapped_buffer = Arc::new(Vec::new());
-            let buffer = Arc::get_mut(&mut wrapped_buffer).unwrap();
-            let bytes_read = file.read().read_to_end(buffer).unwrap();
-            info!("Read file {:?} ({} bytes)", filename, bytes_read);
-            Ok(wrapped_buffer)
-        } else {
-            Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+        for candidate in self.resolve_strategy.candidates(filename) {
+            if let Ok(Some(DirectoryEntry::File(file))) =
+                handle.open(&candidate.replace('\\', "/").to_ascii_lowercase())
+            {
+                let mut wrapped_buffer = Arc::new(Vec::new());
+                let buffer = Arc::get_mut(&mut wrapped_buffer).unwrap();
+                let bytes_read = file.read().read_to_end(buffer).unwrap();
+                info!("Read file {:?} ({} bytes)", candidate, bytes_read);
+                return Ok(wrapped_buffer);
+            }
         }
+        Err(std::io::Error::from(std::io::ErrorKind::NotFound))
     }
 
     fn write_file(&mut self, _filename: &str, _data: &[u8]) -> std::io::Result<()> {
@@ -114,14 +125,79 @@ impl InsertedDisk {
     pub fn new(data: Vec<u8>) -> Result<Self, ISOError> {
         Ok(Self {
             handle: ISO9660::new(Cursor::new(data))?,
+            resolve_strategy: ResolveStrategy::default(),
         })
     }
+
+    pub fn with_resolve_strategy(self, resolve_strategy: ResolveStrategy) -> Self {
+        Self {
+            resolve_strategy,
+            ..self
+        }
+    }
+}
+
+/// In-memory file system serving preloaded files synchronously.
+///
+/// Meant for targets where blocking IO is not an option (e.g. wasm),
+/// with the contents supplied upfront from a dropped or fetched archive.
+#[derive(Debug, Default)]
+pub struct BundleFileSystem {
+    files: HashMap<String, Arc<Vec<u8>>>,
+    resolve_strategy: ResolveStrategy,
+}
+
+impl FileSystem for BundleFileSystem {
+    fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
+        self.resolve_strategy
+            .resolve(filename, self.files.keys().map(|k| k.as_ref()))
+            .and_then(|k| self.files.get(k))
+            .cloned()
+            .ok_or(std::io::Error::from(std::io::ErrorKind::NotFound))
+    }
+
+    fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()> {
+        let key = self
+            .resolve_strategy
+            .resolve(filename, self.files.keys().map(|k| k.as_ref()))
+            .map(|k| k.to_owned())
+            .unwrap_or_else(|| self.resolve_strategy.map_directories(filename));
+        self.files.insert(key, Arc::new(data.to_owned()));
+        Ok(())
+    }
+}
+
+impl BundleFileSystem {
+    pub fn new(files: impl IntoIterator<Item = (String, Vec<u8>)>) -> Self {
+        Self {
+            files: files
+                .into_iter()
+                .map(|(name, data)| (name, Arc::new(data)))
+                .collect(),
+            resolve_strategy: ResolveStrategy::default(),
+        }
+    }
+
+    pub fn with_resolve_strategy(self, resolve_strategy: ResolveStrategy) -> Self {
+        Self {
+            resolve_strategy,
+            ..self
+        }
+    }
+
+    pub fn insert(&mut self, filename: &str, data: Vec<u8>) {
+        self.files.insert(filename.to_owned(), Arc::new(data));
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
 #[derive(Debug)]
 pub struct GameDirectory {
     base_path: Path,
+    write_path: Option<Path>,
+    resolve_strategy: ResolveStrategy,
+    #[cfg(feature = "hot_reload")]
+    read_files: HashMap<String, (PathBuf, Option<std::time::SystemTime>)>,
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -129,14 +205,36 @@ impl GameDirectory {
     pub fn new(base_path: &str) -> std::io::Result<Self> {
         let res = GameDirectory {
             base_path: Path::from(base_path),
+            write_path: None,
+            resolve_strategy: ResolveStrategy::default(),
+            #[cfg(feature = "hot_reload")]
+            read_files: HashMap::new(),
         };
-        Self::get_matching_path(&res.base_path)?;
+        res.get_matching_path(&res.base_path)?;
         Ok(res)
     }
 
+    pub fn with_resolve_strategy(self, resolve_strategy: ResolveStrategy) -> Self {
+        Self {
+            resolve_strategy,
+            ..self
+        }
+    }
+
+    /// Redirects all writes to a separate directory, leaving the game directory untouched.
+    ///
+    /// Files present in the write directory take precedence over the game files when reading.
+    pub fn with_write_path(self, write_path: &str) -> std::io::Result<Self> {
+        std::fs::create_dir_all(write_path)?;
+        Ok(Self {
+            write_path: Some(Path::from(write_path)),
+            ..self
+        })
+    }
+
     #[cfg(not(target_os = "windows"))]
-    fn get_matching_path(path: &str) -> std::io::Result<PathBuf> {
-        let path = Path::from(path);
+    fn get_matching_path(&self, path: &str) -> std::io::Result<PathBuf> {
+        let path = Path::from(&self.resolve_strategy.map_directories(path));
         let mut built_path = String::from(if path.starts_with('/') { "/" } else { "." });
         let segment_count = path.split('/').count();
         for (i, segment) in path
@@ -145,14 +243,18 @@ impl GameDirectory {
             .skip_while(|(_, s)| s.is_empty())
         {
             trace!("Matching path segment {segment}, currently built path is {built_path}");
-            let Some(continuation) = std::fs::read_dir(&built_path)?
-                .find(|r| {
-                    r.as_ref().is_ok_and(|e| {
-                        e.file_name().eq_ignore_ascii_case(segment)
-                            && (i == segment_count - 1 || e.file_type().is_ok_and(|t| t.is_dir()))
-                    })
-                })
-                .transpose()?
+            let mut entry_names = Vec::new();
+            for entry in std::fs::read_dir(&built_path)? {
+                let entry = entry?;
+                if i == segment_count - 1 || entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    if let Ok(name) = entry.file_name().into_string() {
+                        entry_names.push(name);
+                    }
+                }
+            }
+            let Some(continuation) = self
+                .resolve_strategy
+                .resolve(segment, entry_names.iter().map(|n| n.as_str()))
             else {
                 return Err(std::io::Error::from(std::io::ErrorKind::NotFound));
             };
@@ -161,26 +263,40 @@ impl GameDirectory {
             } else if built_path != "/" {
                 built_path.push('/');
             }
-            built_path.push_str(continuation.file_name().to_str().unwrap());
+            built_path.push_str(continuation);
         }
         Ok(PathBuf::from(built_path))
     }
 
     #[cfg(target_os = "windows")]
-    fn get_matching_path(path: &str) -> std::io::Result<PathBuf> {
-        if std::fs::exists(path)? {
-            Ok(PathBuf::from(path))
-        } else {
-            Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+    fn get_matching_path(&self, path: &str) -> std::io::Result<PathBuf> {
+        for candidate in self.resolve_strategy.candidates(path) {
+            if std::fs::exists(&candidate)? {
+                return Ok(PathBuf::from(candidate));
+            }
         }
+        Err(std::io::Error::from(std::io::ErrorKind::NotFound))
     }
 }
 
 #[cfg(not(target_family = "wasm"))]
 impl FileSystem for GameDirectory {
     fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
-        let matched_path = Self::get_matching_path(&self.base_path.with_appended(filename))?;
-        let mut file = std::fs::File::open(matched_path)?;
+        let overridden_path = self
+            .write_path
+            .as_ref()
+            .and_then(|p| self.get_matching_path(&p.with_appended(filename)).ok());
+        let matched_path = match overridden_path {
+            Some(path) => path,
+            None => self.get_matching_path(&self.base_path.with_appended(filename))?,
+        };
+        let mut file = std::fs::File::open(&matched_path)?;
+        #[cfg(feature = "hot_reload")]
+        {
+            let modified = file.metadata().and_then(|m| m.modified()).ok();
+            self.read_files
+                .insert(filename.to_owned(), (matched_path, modified));
+        }
         let mut wrapped_vec = Arc::new(Vec::new());
         let vec = Arc::get_mut(&mut wrapped_vec).unwrap();
         file.read_to_end(vec)?;
@@ -189,15 +305,19 @@ impl FileSystem for GameDirectory {
 
     fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()> {
         trace!("Writing to {} data: {:?}", filename, data);
-        let total_path = self.base_path.with_appended(filename);
-        if let Ok(writing_path) = Self::get_matching_path(&total_path) {
+        let total_path = self
+            .write_path
+            .as_ref()
+            .unwrap_or(&self.base_path)
+            .with_appended(filename);
+        if let Ok(writing_path) = self.get_matching_path(&total_path) {
             trace!("Matched path: {:?}", writing_path);
             return std::fs::write(writing_path, data);
         }
         let (rest_index, mut max_matching_path) = total_path
             .rmatch_indices('/')
             .filter_map(
-                |(i, _)| match Self::get_matching_path(&total_path[..(i + 1)]) {
+                |(i, _)| match self.get_matching_path(&total_path[..(i + 1)]) {
                     Ok(path) => Some((i + 1, path)),
                     Err(_) => None,
                 },
@@ -211,4 +331,102 @@ impl FileSystem for GameDirectory {
         }
         std::fs::write(max_matching_path, data)
     }
+
+    #[cfg(feature = "hot_reload")]
+    fn poll_changed_files(&mut self) -> Vec<String> {
+        let mut changed_files = Vec::new();
+        for (filename, (path, last_modified)) in self.read_files.iter_mut() {
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed_files.push(filename.clone());
+            }
+        }
+        changed_files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{GamePaths, ScenePath};
+    use std::sync::RwLock;
+    use test_case::test_case;
+
+    #[test_case("exact path", "SCENE", "TEST.CNV")]
+    #[test_case("lowercase", "scene", "test.cnv")]
+    #[test_case("without extension", "Scene", "Test")]
+    fn bundle_should_serve_scene_assets(_description: &str, dir_path: &str, file_path: &str) {
+        let filesystem: Arc<RwLock<dyn FileSystem>> =
+            Arc::new(RwLock::new(BundleFileSystem::new([
+                ("DANE/SCENE/TEST.CNV".to_owned(), b"OBJECT=TEST".to_vec()),
+                ("DANE/SCENE/OTHER.CNV".to_owned(), b"OBJECT=OTHER".to_vec()),
+            ])));
+
+        let actual = filesystem
+            .write()
+            .unwrap()
+            .read_scene_asset(
+                Arc::new(GamePaths::default()),
+                &ScenePath::new(dir_path, file_path),
+            )
+            .unwrap();
+
+        assert_eq!(*actual, b"OBJECT=TEST");
+    }
+
+    #[test]
+    fn bundle_should_overwrite_files_case_insensitively() {
+        let mut filesystem = BundleFileSystem::new([("DANE/SAVE.INI".to_owned(), b"OLD".to_vec())]);
+
+        filesystem.write_file("dane/save.ini", b"NEW").unwrap();
+
+        assert_eq!(*filesystem.read_file("DANE/SAVE.INI").unwrap(), b"NEW");
+        assert_eq!(filesystem.files.len(), 1);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test_case("lowercase", "helper.img")]
+    #[test_case("uppercase", "HELPER.IMG")]
+    #[test_case("mixed case", "Helper.Img")]
+    #[test_case("lowercase without extension", "helper")]
+    #[test_case("uppercase without extension", "HELPER")]
+    fn game_directory_should_resolve_references_to_the_same_file(
+        _description: &str,
+        filename: &str,
+    ) {
+        let test_dir_path =
+            PathBuf::from_iter([env!("CARGO_MANIFEST_DIR"), "src/tests/unit_assets"]);
+        let mut filesystem = GameDirectory::new(test_dir_path.to_str().unwrap()).unwrap();
+        let expected = std::fs::read(test_dir_path.join("helper.img")).unwrap();
+
+        let actual = filesystem.read_file(filename).unwrap();
+
+        assert_eq!(*actual, expected);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    #[test]
+    fn game_directory_should_read_back_files_from_write_path() {
+        let test_dir_path =
+            PathBuf::from_iter([env!("CARGO_MANIFEST_DIR"), "src/tests/unit_assets"]);
+        let write_dir_path =
+            std::env::temp_dir().join(format!("pixlib_write_path_test_{}", std::process::id()));
+        let mut filesystem = GameDirectory::new(test_dir_path.to_str().unwrap())
+            .unwrap()
+            .with_write_path(write_dir_path.to_str().unwrap())
+            .unwrap();
+
+        filesystem.write_file("DANE/SAVE.INI", b"[SAVE]").unwrap();
+        filesystem.write_file("helper.img", b"OVERRIDE").unwrap();
+        let save = filesystem.read_file("dane/save.ini").unwrap();
+        let helper = filesystem.read_file("HELPER.IMG").unwrap();
+        let original = std::fs::read(test_dir_path.join("helper.img")).unwrap();
+        std::fs::remove_dir_all(&write_dir_path).unwrap();
+
+        assert_eq!(*save, b"[SAVE]");
+        assert_eq!(*helper, b"OVERRIDE");
+        assert_ne!(original, b"OVERRIDE");
+        assert!(!test_dir_path.join("DANE").exists());
+    }
 }
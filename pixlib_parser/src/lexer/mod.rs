@@ -70,6 +70,28 @@ pub struct LexerState {
     pub brace_level: usize,
     pub bracket_level: usize,
     pub parenthesis_level: usize,
+    /// Set right after `$`, so that the parameter name ends at the first non-alphanumeric.
+    pub expecting_parameter_name: bool,
+    /// Set right after a parameter, an invocation or a bracketed operation,
+    /// which may be followed by an unbracketed operator.
+    pub after_operand: bool,
+    /// Parenthesis level of an unbracketed operation (like `$1 + 2`) being lexed, if any.
+    pub inline_operation_level: Option<usize>,
+}
+
+impl LexerState {
+    fn is_in_operation(&self) -> bool {
+        self.bracket_level > 0 || self.inline_operation_level.is_some()
+    }
+
+    fn end_inline_operation(&mut self) {
+        if self
+            .inline_operation_level
+            .is_some_and(|level| level >= self.parenthesis_level)
+        {
+            self.inline_operation_level = None;
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -111,6 +133,18 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
             .next_if(|result| result.as_ref().is_ok_and(|(_, c, _)| c.is_whitespace()))
             .is_some()
         {}
+        let after_operand = std::mem::take(&mut self.state.after_operand);
+        if after_operand
+            && self.state.parenthesis_level > 0
+            && self.state.bracket_level == 0
+            && self.state.inline_operation_level.is_none()
+            && self
+                .input
+                .peek()
+                .is_some_and(|result| result.as_ref().is_ok_and(|(_, c, _)| is_operator(*c)))
+        {
+            self.state.inline_operation_level = Some(self.state.parenthesis_level);
+        }
         match self.input.next() {
             Some(Ok((pos, '@', next_pos))) => {
                 Some(Ok((pos, CnvToken::At, self.next_position.assign(next_pos))))
@@ -125,16 +159,50 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                 CnvToken::Pipe,
                 self.next_position.assign(next_pos),
             ))),
-            Some(Ok((pos, ',', next_pos))) => Some(Ok((
-                pos,
-                CnvToken::Comma,
-                self.next_position.assign(next_pos),
-            ))),
-            Some(Ok((pos, '$', next_pos))) => Some(Ok((
-                pos,
-                CnvToken::Dollar,
-                self.next_position.assign(next_pos),
-            ))),
+            Some(Ok((pos, ',', next_pos))) => {
+                self.state.end_inline_operation();
+                Some(Ok((
+                    pos,
+                    CnvToken::Comma,
+                    self.next_position.assign(next_pos),
+                )))
+            }
+            Some(Ok((pos, '$', next_pos))) => {
+                self.state.expecting_parameter_name = true;
+                Some(Ok((
+                    pos,
+                    CnvToken::Dollar,
+                    self.next_position.assign(next_pos),
+                )))
+            }
+            Some(Ok((pos, character @ ('<' | '>' | '=' | '!'), next_pos)))
+                if self.state.is_in_operation() =>
+            {
+                self.next_position = next_pos;
+                let second = self
+                    .input
+                    .next_if(|result| {
+                        result
+                            .as_ref()
+                            .is_ok_and(|(_, c, _)| *c == '=' || (character == '<' && *c == '>'))
+                    })
+                    .map(|triple| {
+                        let (_, c, next_pos) = triple.unwrap();
+                        self.next_position = next_pos;
+                        c
+                    });
+                let token = match (character, second) {
+                    ('<', None) => CnvToken::Less,
+                    ('<', Some('=')) => CnvToken::LessEqual,
+                    ('<', Some(_)) | ('!', Some(_)) => CnvToken::NotEqual,
+                    ('>', None) => CnvToken::Greater,
+                    ('>', Some(_)) => CnvToken::GreaterEqual,
+                    ('=', Some(_)) => CnvToken::Equal,
+                    ('=', None) => CnvToken::Unexpected('='),
+                    _ => CnvToken::Bang,
+                };
+                Some(Ok((pos, token, self.next_position)))
+            }
             Some(Ok((pos, '!', next_pos))) => Some(Ok((
                 pos,
                 CnvToken::Bang,
@@ -154,7 +222,9 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                 )))
             }
             Some(Ok((pos, ')', next_pos))) => {
+                self.state.end_inline_operation();
                 self.state.parenthesis_level = self.state.parenthesis_level.saturating_sub(1);
+                self.state.after_operand = true;
                 Some(Ok((
                     pos,
                     CnvToken::RightParenthesis,
@@ -171,6 +241,7 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
             }
             Some(Ok((pos, ']', next_pos))) => {
                 self.state.bracket_level = self.state.bracket_level.saturating_sub(1);
+                self.state.after_operand = true;
                 Some(Ok((
                     pos,
                     CnvToken::RightBracket,
@@ -198,7 +269,7 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                 CnvToken::Plus,
                 self.next_position.assign(next_pos),
             ))),
-            Some(Ok((pos, '-', next_pos))) if self.state.bracket_level > 0 => Some(Ok((
+            Some(Ok((pos, '-', next_pos))) if self.state.is_in_operation() => Some(Ok((
                 pos,
                 CnvToken::Minus,
                 self.next_position.assign(next_pos),
@@ -215,6 +286,11 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
             ))),
             Some(Ok((pos, character, next_pos))) => {
                 self.next_position = next_pos;
+                let is_parameter_name = std::mem::take(&mut self.state.expecting_parameter_name);
+                let is_in_inline_operation = self.state.inline_operation_level.is_some();
+                let is_in_operation = self.state.is_in_operation();
+                // operators and whitespace quoted within an operand are a part of it
+                let mut is_in_quotes = is_in_operation && character == '"';
                 let mut lexeme = String::new();
                 let mut relative_parenthesis_level: usize = 0;
                 let mut relative_bracket_level: usize = 0;
@@ -223,10 +299,16 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                 let mut length_exceeded = false;
                 while let Some(triple) = self.input.next_if(|result| {
                     result.as_ref().is_ok_and(|(_, c, _)| match c {
+                        _ if is_parameter_name => c.is_ascii_alphanumeric() || *c == '_',
+                        '"' if is_in_operation => {
+                            is_in_quotes = !is_in_quotes;
+                            true
+                        }
+                        c if is_in_quotes && (is_operator(*c) || c.is_whitespace()) => true,
                         '|' => false,
                         '$' => true, // TODO: come up with something clever
                         '^' | ';' => relative_brace_level > 0,
-                        '+' | '-' | '*' | '@' | '%' => self.state.bracket_level == 0,
+                        c if is_operator(*c) && *c != '=' => !is_in_operation,
                         ',' => relative_parenthesis_level > 0,
                         '(' => {
                             relative_parenthesis_level += 1;
@@ -277,6 +359,10 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                         lexeme.push(c);
                     }
                 }
+                if is_in_inline_operation {
+                    // whitespace before the next operator belongs to neither operand
+                    lexeme.truncate(lexeme.trim_end().len());
+                }
                 if length_exceeded {
                     self.issue_manager
                         .emit_issue(LexerIssue::Error(LexerError::LexemeTooLong {
@@ -296,6 +382,7 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
                     // TODO: check if case should be ignored
                     return Some(Ok((pos, CnvToken::KeywordNull, self.next_position)));
                 }
+                self.state.after_operand = is_parameter_name;
                 Some(Ok((pos, CnvToken::Identifier(lexeme), self.next_position)))
             }
             Some(Err(err)) => Some(Err(LexerFatal::IoError {
@@ -308,6 +395,10 @@ impl<I: Iterator<Item = LexerInput> + 'static> Iterator for CnvLexer<I> {
     }
 }
 
+fn is_operator(c: char) -> bool {
+    matches!(c, '+' | '-' | '*' | '@' | '%' | '<' | '>' | '=' | '!')
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CnvToken {
     Unexpected(char),
@@ -322,6 +413,12 @@ pub enum CnvToken {
     Asterisk,
     At,
     Percent,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
     Caret,
     Pipe,
     Comma,
@@ -335,3 +432,65 @@ pub enum CnvToken {
     LeftBrace,
     RightBrace,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::vec::IntoIter;
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::scanner::CnvScanner;
+
+    fn lex(code: &str) -> Vec<CnvToken> {
+        let scanner =
+            CnvScanner::<IntoIter<_>>::new(code.chars().map(Ok).collect::<Vec<_>>().into_iter());
+        CnvLexer::new(scanner, Default::default(), Default::default())
+            .map(|result| result.unwrap().1)
+            .collect()
+    }
+
+    #[test_case("[S+\"a=b\"]", "\"a=b\"" ; "equals sign in brackets")]
+    #[test_case("[S+\"a<b>!c\"]", "\"a<b>!c\"" ; "comparison characters in brackets")]
+    #[test_case("O^SET($1 + \"a b\")", "\"a b\"" ; "whitespace in inline operation")]
+    #[test_case("O^SET($1 + \"a-b*c\")", "\"a-b*c\"" ; "arithmetic characters in inline operation")]
+    fn quoted_operand_should_be_lexed_as_single_identifier(code: &str, expected: &str) {
+        let tokens = lex(code);
+        assert!(tokens.contains(&CnvToken::Plus));
+        assert!(tokens.contains(&CnvToken::Identifier(expected.to_owned())));
+    }
+
+    #[test]
+    fn parameter_name_should_allow_underscores() {
+        assert_eq!(
+            lex("O^SET($MY_ARG + 1)")[5..8],
+            [
+                CnvToken::Identifier("MY_ARG".to_owned()),
+                CnvToken::Plus,
+                CnvToken::Identifier("1".to_owned()),
+            ]
+        );
+    }
+
+    #[test_case("O^SET($1 == 2)", CnvToken::Equal ; "double equals sign")]
+    #[test_case("O^SET($1 = 2)", CnvToken::Unexpected('=') ; "single equals sign")]
+    #[test_case("O^SET($1 != 2)", CnvToken::NotEqual ; "exclamation mark")]
+    #[test_case("O^SET($1 <> 2)", CnvToken::NotEqual ; "angle brackets")]
+    fn equality_operators_should_be_lexed(code: &str, expected: CnvToken) {
+        assert_eq!(lex(code)[6], expected);
+    }
+
+    #[test]
+    fn equals_sign_should_not_end_identifier_in_brackets() {
+        assert_eq!(
+            lex("[A=B+1]"),
+            [
+                CnvToken::LeftBracket,
+                CnvToken::Identifier("A=B".to_owned()),
+                CnvToken::Plus,
+                CnvToken::Identifier("1".to_owned()),
+                CnvToken::RightBracket,
+            ]
+        );
+    }
+}
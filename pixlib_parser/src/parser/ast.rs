@@ -46,6 +46,12 @@ pub enum Operation {
     Subtraction,
     Division,
     Remainder,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
 }
 
 #[derive(Error, Debug)]
@@ -1,7 +1,6 @@
-use log::error;
 use thiserror::Error;
 
-use crate::common::{Bounds, Issue, IssueKind, Position, Spanned};
+use crate::common::{Bounds, Issue, IssueKind, IssueManager, Position, Spanned};
 use std::iter::Peekable;
 
 pub type ParserInput = Spanned<char, Position, std::io::Error>;
@@ -39,8 +38,16 @@ pub enum ParserFatal {
         position: Position,
         source: std::io::Error,
     },
-    #[error("Other error")]
-    Other,
+    #[error("Other error at {position}")]
+    Other { position: Position },
+}
+
+impl ParserFatal {
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Self::IoError { position, .. } | Self::Other { position } => Bounds::unit(*position),
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -63,6 +70,20 @@ pub enum ParserError {
     },
 }
 
+impl ParserError {
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Self::ExpectedCharacter { position, .. }
+            | Self::ExpectedKeyword { position, .. }
+            | Self::UnexpectedEtx { position } => Bounds::unit(*position),
+            Self::UnexpectedCharacter { position, .. } => {
+                Bounds::new(*position, position.with_incremented_column())
+            }
+            Self::LineTooLong { bounds, .. } => *bounds,
+        }
+    }
+}
+
 impl From<ParserError> for ParserIssue {
     fn from(value: ParserError) -> Self {
         Self::Error(value)
@@ -82,6 +103,16 @@ pub enum ParserIssue {
     Warning(ParserWarning),
 }
 
+impl ParserIssue {
+    pub fn bounds(&self) -> Option<Bounds> {
+        match self {
+            Self::Fatal(fatal) => Some(fatal.bounds()),
+            Self::Error(error) => Some(error.bounds()),
+            Self::Warning(_) => None,
+        }
+    }
+}
+
 impl Issue for ParserIssue {
     fn kind(&self) -> IssueKind {
         match *self {
@@ -97,14 +128,20 @@ pub struct DeclarativeParser<I: Iterator<Item = ParserInput>> {
     input: Peekable<I>,
     settings: ParsingSettings,
     next_position: Position,
+    issue_manager: IssueManager<ParserIssue>,
 }
 
 impl<I: Iterator<Item = ParserInput>> DeclarativeParser<I> {
-    pub fn new(input: I, settings: ParsingSettings) -> Self {
+    pub fn new(
+        input: I,
+        settings: ParsingSettings,
+        issue_manager: IssueManager<ParserIssue>,
+    ) -> Self {
         Self {
             input: input.peekable(),
             settings,
             next_position: Position::default(),
+            issue_manager,
         }
     }
 
@@ -169,7 +206,10 @@ struct LineToSplit {
 }
 
 impl LineToSplit {
-    pub fn split(self) -> (Position, CnvDeclaration, Position) {
+    pub fn split(
+        self,
+        issue_manager: &mut IssueManager<ParserIssue>,
+    ) -> (Position, CnvDeclaration, Position) {
         let declaration = if let Some(colon_index) = self.colon_index {
             let property = if let Some(caret_index) = self.caret_index {
                 self.content[(colon_index + 1)..caret_index].to_owned()
@@ -207,12 +247,12 @@ impl LineToSplit {
                 .starts_with("OBJECT")
                 && self.content[6..eq_index].chars().all(|c| c.is_whitespace()))
             {
-                error!(
-                    "{}",
+                issue_manager.emit_issue(
                     ParserError::ExpectedKeyword {
                         position: self.start_position,
                         keyword: "OBJECT",
                     }
+                    .into(),
                 );
                 0
             } else {
@@ -225,12 +265,12 @@ impl LineToSplit {
             name.drain(..(first_non_whitespace + offset));
             CnvDeclaration::ObjectInitialization(name)
         } else {
-            error!(
-                "{}",
+            issue_manager.emit_issue(
                 ParserError::ExpectedCharacter {
                     position: self.next_position,
                     character: '=',
                 }
+                .into(),
             );
             CnvDeclaration::ObjectInitialization(self.content)
         };
@@ -271,8 +311,7 @@ impl<I: Iterator<Item = ParserInput>> Iterator for DeclarativeParser<I> {
             if line_state.content.len() >= self.settings.max_line_length {
                 self.skip_line(line_state.had_slash);
 
-                error!(
-                    "{}",
+                self.issue_manager.emit_issue(
                     ParserError::LineTooLong {
                         bounds: Bounds::new(
                             line_state.start_position.unwrap_or_default(),
@@ -280,6 +319,7 @@ impl<I: Iterator<Item = ParserInput>> Iterator for DeclarativeParser<I> {
                         ),
                         max_allowed_len: self.settings.max_line_length,
                     }
+                    .into(),
                 );
             }
         }
@@ -295,42 +335,42 @@ impl<I: Iterator<Item = ParserInput>> Iterator for DeclarativeParser<I> {
             match c {
                 '=' => {
                     if line_to_split.eq_index.is_some() {
-                        error!(
-                            "{}",
+                        self.issue_manager.emit_issue(
                             ParserError::UnexpectedCharacter {
                                 position: &line_to_split.start_position + i,
                                 character: c,
                             }
+                            .into(),
                         );
-                        return Some(Ok(line_to_split.split()));
+                        return Some(Ok(line_to_split.split(&mut self.issue_manager)));
                     } else {
                         line_to_split.eq_index = Some(i);
                     }
                 }
                 ':' if line_to_split.eq_index.is_none() => {
                     if line_to_split.colon_index.is_some() {
-                        error!(
-                            "{}",
+                        self.issue_manager.emit_issue(
                             ParserError::UnexpectedCharacter {
                                 position: &line_to_split.start_position + i,
                                 character: c,
                             }
+                            .into(),
                         );
-                        return Some(Ok(line_to_split.split()));
+                        return Some(Ok(line_to_split.split(&mut self.issue_manager)));
                     } else {
                         line_to_split.colon_index = Some(i);
                     }
                 }
                 '^' if line_to_split.eq_index.is_none() => {
                     if line_to_split.colon_index.is_none() || line_to_split.caret_index.is_some() {
-                        error!(
-                            "{}",
+                        self.issue_manager.emit_issue(
                             ParserError::UnexpectedCharacter {
                                 position: &line_to_split.start_position + i,
                                 character: c,
                             }
+                            .into(),
                         );
-                        return Some(Ok(line_to_split.split()));
+                        return Some(Ok(line_to_split.split(&mut self.issue_manager)));
                     } else {
                         line_to_split.caret_index = Some(i);
                     }
@@ -338,6 +378,74 @@ impl<I: Iterator<Item = ParserInput>> Iterator for DeclarativeParser<I> {
                 _ => (),
             }
         }
-        Some(Ok(line_to_split.split()))
+        Some(Ok(line_to_split.split(&mut self.issue_manager)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::common::IssueHandler;
+
+    #[derive(Debug, Default, Clone)]
+    struct IssueCollector(Arc<Mutex<Vec<ParserIssue>>>);
+
+    impl IssueHandler<ParserIssue> for IssueCollector {
+        fn handle(&mut self, issue: ParserIssue) {
+            self.0.lock().unwrap().push(issue);
+        }
+    }
+
+    fn as_parser_input(string: &str) -> impl Iterator<Item = ParserInput> + '_ {
+        let mut position = Position::default();
+        string.chars().map(move |c| {
+            let next_position = if c == '\n' {
+                position.with_incremented_line(1)
+            } else {
+                position.with_incremented_column()
+            };
+            Ok((position, c, position.assign(next_position)))
+        })
+    }
+
+    #[test]
+    fn test_unexpected_character_issue_points_at_offending_character() {
+        let input = "OBJECT=FOO\nFOO:TYPE=INTEGER\nFOO:VAL:UE=5\n";
+        let collector = IssueCollector::default();
+        let mut issue_manager = IssueManager::default();
+        issue_manager.set_handler(Box::new(collector.clone()));
+
+        let declarations: Vec<_> =
+            DeclarativeParser::new(as_parser_input(input), Default::default(), issue_manager)
+                .collect();
+
+        assert_eq!(declarations.len(), 3);
+        let issues = collector.0.lock().unwrap();
+        assert_eq!(issues.len(), 1);
+        let ParserIssue::Error(ParserError::UnexpectedCharacter {
+            position,
+            character,
+        }) = &issues[0]
+        else {
+            panic!(
+                "Expected an unexpected character error, got: {:?}",
+                issues[0]
+            );
+        };
+        assert_eq!(*character, ':');
+        assert_eq!(
+            *position,
+            Position {
+                character: 35,
+                line: 3,
+                column: 8,
+            }
+        );
+        assert_eq!(
+            issues[0].bounds(),
+            Some(Bounds::new(*position, position.with_incremented_column()))
+        );
     }
 }
@@ -42,4 +42,105 @@ mod imperative_parser_test {
             vec![Expression::Identifier("REKSIO17A".into())]
         );
     }
+
+    #[test]
+    fn test_global_function_with_block_argument() {
+        let code_to_parse = "@WHILE(COUNTER,'<',3,{COUNTER^INC();})";
+        let scanner = CnvScanner::<IntoIter<_>>::new(
+            code_to_parse
+                .chars()
+                .map(Ok)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        );
+        let lexer = CnvLexer::new(scanner, Default::default(), Default::default());
+        let result = CodeParser::new().parse(&Default::default(), lexer).unwrap();
+        info!("{:?}", result);
+        let Expression::Invocation(invocation) = result.value else {
+            panic!();
+        };
+        assert_eq!(invocation.parent, None);
+        assert_eq!(invocation.name, "WHILE");
+        assert_eq!(
+            invocation.arguments[..3],
+            [
+                Expression::Identifier("COUNTER".into()),
+                Expression::Identifier("'<'".into()),
+                Expression::Identifier("3".into()),
+            ]
+        );
+        assert!(matches!(invocation.arguments[3], Expression::Block(_)));
+    }
+
+    #[test]
+    fn test_inline_operation_in_arguments() {
+        let code_to_parse = "OBJ^SET($1 + 2, $2-B, A+B)";
+        let scanner = CnvScanner::<IntoIter<_>>::new(
+            code_to_parse
+                .chars()
+                .map(Ok)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        );
+        let lexer = CnvLexer::new(scanner, Default::default(), Default::default());
+        let result = CodeParser::new().parse(&Default::default(), lexer).unwrap();
+        info!("{:?}", result);
+        let Expression::Invocation(invocation) = result.value else {
+            panic!();
+        };
+        assert_eq!(
+            invocation.arguments,
+            vec![
+                Expression::Operation(
+                    Box::new(Expression::Parameter("1".into())),
+                    vec![(ast::Operation::Addition, Expression::Identifier("2".into()))]
+                ),
+                Expression::Operation(
+                    Box::new(Expression::Parameter("2".into())),
+                    vec![(
+                        ast::Operation::Subtraction,
+                        Expression::Identifier("B".into())
+                    )]
+                ),
+                Expression::Identifier("A+B".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_comparison_after_invocation_in_arguments() {
+        let code_to_parse = "OBJ^SET(A^GET() >= $1, $2<>B)";
+        let scanner = CnvScanner::<IntoIter<_>>::new(
+            code_to_parse
+                .chars()
+                .map(Ok)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        );
+        let lexer = CnvLexer::new(scanner, Default::default(), Default::default());
+        let result = CodeParser::new().parse(&Default::default(), lexer).unwrap();
+        info!("{:?}", result);
+        let Expression::Invocation(invocation) = result.value else {
+            panic!();
+        };
+        assert_eq!(invocation.arguments.len(), 2);
+        let Expression::Operation(left, operations) = &invocation.arguments[0] else {
+            panic!();
+        };
+        assert!(matches!(**left, Expression::Invocation(_)));
+        assert_eq!(
+            operations,
+            &vec![(
+                ast::Operation::GreaterEqual,
+                Expression::Parameter("1".into())
+            )]
+        );
+        assert_eq!(
+            invocation.arguments[1],
+            Expression::Operation(
+                Box::new(Expression::Parameter("2".into())),
+                vec![(ast::Operation::NotEqual, Expression::Identifier("B".into()))]
+            )
+        );
+    }
 }
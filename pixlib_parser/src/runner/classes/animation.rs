@@ -1,7 +1,7 @@
 use super::super::{
-    content::EventHandler,
+    content::{get_matching_handler, EventHandler},
     initable::Initable,
-    parsers::{discard_if_empty, parse_bool, parse_event_handler, parse_i32},
+    parsers::{discard_if_empty, parse_bool, parse_event_handlers, parse_i32},
 };
 use ::rand::{seq::SliceRandom, thread_rng};
 use pixlib_formats::file_formats::ann::{parse_ann, LoopingSettings};
@@ -33,18 +33,18 @@ pub struct AnimationProperties {
     pub to_canvas: Option<bool>,               // TOCANVAS
     pub visible: Option<bool>,                 // VISIBLE
 
-    pub on_click: Option<Arc<ParsedScript>>, // ONCLICK signal
+    pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
     pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
     pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,  // ONDONE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
     pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
     pub on_first_frame: HashMap<String, Arc<ParsedScript>>, // ONFIRSTFRAME signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
     pub on_frame_changed: HashMap<String, Arc<ParsedScript>>, // ONFRAMECHANGED signal
-    pub on_init: Option<Arc<ParsedScript>>,  // ONINIT signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
     pub on_paused: HashMap<String, Arc<ParsedScript>>, // ONPAUSED signal
-    pub on_release: Option<Arc<ParsedScript>>, // ONRELEASE signal
+    pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
     pub on_resumed: HashMap<String, Arc<ParsedScript>>, // ONRESUMED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
@@ -55,7 +55,8 @@ struct AnimationState {
     // initialized from properties
     pub is_button: bool,
     pub file_data: Arc<AnimationFileData>,
-    pub fps: usize,
+    pub fps: i32,
+    pub has_explicit_fps: bool,
     pub does_monitor_collision: bool,
     pub priority: isize,
     pub is_visible: bool,
@@ -63,7 +64,7 @@ struct AnimationState {
     // general graphics state
     pub position: (isize, isize),
     pub opacity: usize,
-    // anchor: ???,
+    pub anchor: Option<Anchor>,
     pub is_flipped_horizontally: bool,
     pub is_flipped_vertically: bool,
 
@@ -75,11 +76,15 @@ struct AnimationState {
     pub sprite_idx_override: Option<usize>,
     // more temporary
     pub current_frame_duration: f64,
+    pub does_emit_first_frame: bool,
+    pub queued_sequences: VecDeque<String>,
 
     // related to sound
-    pub panning: isize,
+    pub panning: Option<isize>, // explicit SETPAN, positional otherwise
     pub volume: isize,
+    pub frequency: Option<usize>,
     pub current_sfx: SoundFileData,
+    pub is_playing_sfx: bool,
 
     // related to button
     pub cursor_interaction: CursorInteraction,
@@ -88,18 +93,18 @@ struct AnimationState {
 
 #[derive(Debug, Clone)]
 pub struct AnimationEventHandlers {
-    pub on_click: Option<Arc<ParsedScript>>, // ONCLICK signal
+    pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
     pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
     pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,  // ONDONE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
     pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
     pub on_first_frame: HashMap<String, Arc<ParsedScript>>, // ONFIRSTFRAME signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
     pub on_frame_changed: HashMap<String, Arc<ParsedScript>>, // ONFRAMECHANGED signal
-    pub on_init: Option<Arc<ParsedScript>>,  // ONINIT signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
     pub on_paused: HashMap<String, Arc<ParsedScript>>, // ONPAUSED signal
-    pub on_release: Option<Arc<ParsedScript>>, // ONRELEASE signal
+    pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
     pub on_resumed: HashMap<String, Arc<ParsedScript>>, // ONRESUMED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
@@ -108,39 +113,21 @@ pub struct AnimationEventHandlers {
 impl EventHandler for AnimationEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCLICK" => self.on_click.as_ref(),
-            "ONCOLLISION" => argument
-                .and_then(|a| self.on_collision.get(a))
-                .or(self.on_collision.get("")),
-            "ONCOLLISIONFINISHED" => argument
-                .and_then(|a| self.on_collision_finished.get(a))
-                .or(self.on_collision_finished.get("")),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONFINISHED" => argument
-                .and_then(|a| self.on_finished.get(a))
-                .or(self.on_finished.get("")),
-            "ONFIRSTFRAME" => argument
-                .and_then(|a| self.on_first_frame.get(a))
-                .or(self.on_first_frame.get("")),
-            "ONFOCUSOFF" => self.on_focus_off.as_ref(),
-            "ONFOCUSON" => self.on_focus_on.as_ref(),
-            "ONFRAMECHANGED" => argument
-                .and_then(|a| self.on_frame_changed.get(a))
-                .or(self.on_frame_changed.get("")),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONPAUSED" => argument
-                .and_then(|a| self.on_paused.get(a))
-                .or(self.on_paused.get("")),
-            "ONRELEASE" => self.on_release.as_ref(),
-            "ONRESUMED" => argument
-                .and_then(|a| self.on_resumed.get(a))
-                .or(self.on_resumed.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
-            "ONSTARTED" => argument
-                .and_then(|a| self.on_started.get(a))
-                .or(self.on_started.get("")),
+            "ONCLICK" => get_matching_handler(&self.on_click, argument),
+            "ONCOLLISION" => get_matching_handler(&self.on_collision, argument),
+            "ONCOLLISIONFINISHED" => get_matching_handler(&self.on_collision_finished, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONFINISHED" => get_matching_handler(&self.on_finished, argument),
+            "ONFIRSTFRAME" => get_matching_handler(&self.on_first_frame, argument),
+            "ONFOCUSOFF" => get_matching_handler(&self.on_focus_off, argument),
+            "ONFOCUSON" => get_matching_handler(&self.on_focus_on, argument),
+            "ONFRAMECHANGED" => get_matching_handler(&self.on_frame_changed, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONPAUSED" => get_matching_handler(&self.on_paused, argument),
+            "ONRELEASE" => get_matching_handler(&self.on_release, argument),
+            "ONRESUMED" => get_matching_handler(&self.on_resumed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONSTARTED" => get_matching_handler(&self.on_started, argument),
             _ => None,
         }
     }
@@ -167,10 +154,14 @@ impl Animation {
             parent: parent.clone(),
             state: RefCell::new(AnimationState {
                 is_button: props.as_button.unwrap_or_default(),
-                fps: props.fps.unwrap_or(16) as usize,
+                fps: props.fps.unwrap_or(16),
+                has_explicit_fps: props.fps.is_some(),
                 does_monitor_collision: props.monitor_collision.unwrap_or_default(),
                 priority: props.priority.unwrap_or_default() as isize,
                 is_visible: props.visible.unwrap_or(true),
+                volume: 1000,
+                opacity: 255,
+                does_emit_first_frame: true,
                 ..AnimationState::default()
             }),
             event_handlers: AnimationEventHandlers {
@@ -203,8 +194,71 @@ impl Animation {
         animation
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.state
+            .borrow()
+            .file_data
+            .get_filename()
+            .map(|f| f.to_owned())
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops the decoded file data if it comes from the given file, so it is loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(referenced) = state.file_data.get_filename().map(|f| f.to_owned()) else {
+            return false;
+        };
+        let script = self.parent.parent.as_ref();
+        let resolved_paths = script
+            .runner
+            .game_paths
+            .get_scene_asset_paths(&script.path.with_file_path(&referenced));
+        if !is_same_file(&resolved_paths, filename) {
+            return false;
+        }
+        state.file_data = Arc::new(AnimationFileData::NotLoaded(referenced));
+        true
+    }
+
+    /// Composites the current frame permanently onto the canvas background.
+    pub fn bake_onto_canvas(&self, context: RunnerContext) -> anyhow::Result<()> {
+        let graphics: &dyn GeneralGraphics = self;
+        if !graphics.is_visible()? {
+            return Ok(());
+        }
+        let Some(rect) = graphics.get_rect()? else {
+            return Ok(());
+        };
+        let pixel_data = graphics.get_pixel_data()?;
+        let canvas_observer_object =
+            context
+                .runner
+                .get_object("CANVAS_OBSERVER")
+                .ok_or(RunnerError::ObjectNotFound {
+                    name: "CANVAS_OBSERVER".to_owned(),
+                })?;
+        let CnvContent::CanvasObserver(canvas_observer) = &canvas_observer_object.content else {
+            return Err(RunnerError::UnexpectedType {
+                object_name: canvas_observer_object.name.clone(),
+                expected: "CANVASOBSERVER".to_owned(),
+                actual: canvas_observer_object.content.get_type_id().to_owned(),
+            }
+            .into());
+        };
+        canvas_observer.bake_graphics(rect, &pixel_data)
+    }
+
     pub fn get_base_position(&self) -> anyhow::Result<(isize, isize)> {
         self.state.borrow().get_base_position()
     }
@@ -251,7 +305,11 @@ impl Animation {
 
     pub fn step(&self, seconds: f64) -> anyhow::Result<()> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
-        self.state.borrow_mut().step(context, seconds)
+        let is_done = self.state.borrow_mut().step(context, seconds)?;
+        if is_done && self.should_flush_after_played && self.should_release {
+            self.state.borrow_mut().flush_file_data();
+        }
+        Ok(())
     }
 
     pub fn get_frame_to_show(&self) -> anyhow::Result<Option<(Rect, SpriteData)>> {
@@ -311,6 +369,65 @@ impl Animation {
         Ok(Some((Rect::from(position, size), sprite.1.clone())))
     }
 
+    /// Renders the given frame of the given sequence without touching the playback state.
+    pub fn render_frame(
+        &self,
+        sequence_idx: usize,
+        frame_idx: usize,
+    ) -> anyhow::Result<(Rect, SpriteData)> {
+        let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
+        self.state
+            .borrow_mut()
+            .use_and_drop_mut(|s| s.load_if_needed(context.clone()))?;
+        self.state
+            .borrow()
+            .render_frame(context, sequence_idx, frame_idx)
+    }
+
+    /// Tiles every sprite into a single RGBA8888 image for debugging.
+    ///
+    /// Sprites are placed in sprite index order, row by row, on a square-ish
+    /// grid of cells sized to fit the largest sprite. Returns the sheet width,
+    /// height and pixel data.
+    pub fn export_sprite_sheet(&self) -> anyhow::Result<(u32, u32, Vec<u8>)> {
+        let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
+        self.state
+            .borrow_mut()
+            .use_and_drop_mut(|s| s.load_if_needed(context.clone()))?;
+        let state = self.state.borrow();
+        let AnimationFileData::Loaded(ref loaded_data) = *state.file_data else {
+            return Ok((0, 0, Vec::new()));
+        };
+        let sprites = &loaded_data.sprites;
+        if sprites.is_empty() {
+            return Ok((0, 0, Vec::new()));
+        }
+        let cell_width = sprites.iter().map(|s| s.0.size_px.0).max().unwrap_or(0) as usize;
+        let cell_height = sprites.iter().map(|s| s.0.size_px.1).max().unwrap_or(0) as usize;
+        let columns = (sprites.len() as f64).sqrt().ceil() as usize;
+        let rows = sprites.len().div_ceil(columns);
+        let sheet_width = columns * cell_width;
+        let sheet_height = rows * cell_height;
+        let mut data = vec![0u8; sheet_width * sheet_height * 4];
+        for (i, (definition, sprite)) in sprites.iter().enumerate() {
+            let cell_x = (i % columns) * cell_width;
+            let cell_y = (i / columns) * cell_height;
+            let width = definition.size_px.0 as usize;
+            for y in 0..(definition.size_px.1 as usize) {
+                let source = sprite
+                    .data
+                    .get((y * width * 4)..((y + 1) * width * 4))
+                    .ok_or(RunnerError::InvalidSpriteData {
+                        object_name: self.parent.name.clone(),
+                        index: i,
+                    })?;
+                let offset = ((cell_y + y) * sheet_width + cell_x) * 4;
+                data[offset..(offset + width * 4)].copy_from_slice(source);
+            }
+        }
+        Ok((sheet_width as u32, sheet_height as u32, data))
+    }
+
     pub fn play(&self, sequence_name: &str) -> anyhow::Result<()> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
         self.state.borrow_mut().play(context, sequence_name)
@@ -346,6 +463,15 @@ impl Animation {
     pub fn is_playing(&self) -> anyhow::Result<bool> {
         Ok(self.state.borrow().is_playing)
     }
+
+    pub fn is_playing_sfx(&self) -> anyhow::Result<bool> {
+        Ok(self.state.borrow().is_playing_sfx)
+    }
+
+    pub fn handle_sfx_finished(&self) -> anyhow::Result<()> {
+        self.state.borrow_mut().is_playing_sfx = false;
+        Ok(())
+    }
 }
 
 impl GeneralGraphics for Animation {
@@ -370,8 +496,9 @@ impl GeneralGraphics for Animation {
     }
 
     fn get_pixel_data(&self) -> anyhow::Result<Arc<Vec<u8>>> {
+        let opacity = self.state.borrow().opacity;
         self.get_frame_to_show()?
-            .map(|r| r.1.data)
+            .map(|r| apply_opacity(r.1.data, opacity))
             .ok_or(RunnerError::NoImageDataLoaded(self.parent.name.clone()).into())
     }
 }
@@ -528,20 +655,26 @@ impl CnvType for Animation {
             CallableIdentifier::Method("FLIPV") => {
                 self.state.borrow_mut().flip_v().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("GETALPHA") => {
-                self.state.borrow().get_alpha().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETALPHA") => self
+                .state
+                .borrow()
+                .get_alpha()
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETANCHOR") => self
                 .state
                 .borrow()
                 .get_anchor()
                 .map(|v| CnvValue::String(v.to_owned())),
-            CallableIdentifier::Method("GETCENTERX") => {
-                self.state.borrow().get_center_x().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("GETCENTERY") => {
-                self.state.borrow().get_center_y().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETCENTERX") => self
+                .state
+                .borrow_mut()
+                .get_center_x(context)
+                .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("GETCENTERY") => self
+                .state
+                .borrow_mut()
+                .get_center_y(context)
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETCFRAMEINEVENT") => self
                 .state
                 .borrow()
@@ -549,20 +682,24 @@ impl CnvType for Animation {
                 .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETCURRFRAMEPOSX") => self
                 .state
-                .borrow()
-                .get_curr_frame_pos_x()
-                .map(|_| CnvValue::Null),
+                .borrow_mut()
+                .get_curr_frame_pos_x(context)
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETCURRFRAMEPOSY") => self
                 .state
-                .borrow()
-                .get_curr_frame_pos_y()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("GETENDX") => {
-                self.state.borrow().get_end_x().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("GETENDY") => {
-                self.state.borrow().get_end_y().map(|_| CnvValue::Null)
-            }
+                .borrow_mut()
+                .get_curr_frame_pos_y(context)
+                .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("GETENDX") => self
+                .state
+                .borrow_mut()
+                .get_end_x(context)
+                .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("GETENDY") => self
+                .state
+                .borrow_mut()
+                .get_end_y(context)
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETEVENTNAME") => self
                 .state
                 .borrow()
@@ -574,30 +711,38 @@ impl CnvType for Animation {
                 .get_sequence_index()
                 .map(|_| CnvValue::Null),
             CallableIdentifier::Method("GETFPS") => {
-                self.state.borrow().get_fps().map(|_| CnvValue::Null)
+                self.state.borrow().get_fps().map(CnvValue::Integer)
             }
             CallableIdentifier::Method("GETFRAME") => self
                 .state
                 .borrow()
                 .get_frame()
                 .map(|v| CnvValue::Integer(v as i32)),
-            CallableIdentifier::Method("GETFRAMENAME") => {
-                self.state.borrow().get_frame_name().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETFRAMENAME") => self
+                .state
+                .borrow()
+                .get_frame_name(context)
+                .map(CnvValue::String),
             CallableIdentifier::Method("GETFRAMENO") => self
                 .state
                 .borrow()
                 .get_frame_index()
                 .map(|v| CnvValue::Integer(v as i32)),
-            CallableIdentifier::Method("GETHEIGHT") => {
-                self.state.borrow().get_height().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("GETMAXHEIGHT") => {
-                self.state.borrow().get_max_height().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("GETMAXWIDTH") => {
-                self.state.borrow().get_max_width().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETHEIGHT") => self
+                .state
+                .borrow_mut()
+                .get_height(context)
+                .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("GETMAXHEIGHT") => self
+                .state
+                .borrow_mut()
+                .get_max_height(context)
+                .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("GETMAXWIDTH") => self
+                .state
+                .borrow_mut()
+                .get_max_width(context)
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETNOE") => self
                 .state
                 .borrow()
@@ -608,14 +753,18 @@ impl CnvType for Animation {
                 .borrow()
                 .get_total_frame_count()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("GETNOFINEVENT") => self
+            CallableIdentifier::Method("GETNOFINEVENT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .get_sequence_frame_count(&arguments[0].to_str())
+                    .map(|v| CnvValue::Integer(v as i32))
+            }
+            CallableIdentifier::Method("GETOPACITY") => self
                 .state
                 .borrow()
-                .get_sequence_frame_count(&arguments[0].to_str())
+                .get_opacity()
                 .map(|v| CnvValue::Integer(v as i32)),
-            CallableIdentifier::Method("GETOPACITY") => {
-                self.state.borrow().get_opacity().map(|_| CnvValue::Null)
-            }
             CallableIdentifier::Method("GETPIXEL") => {
                 self.state.borrow().get_pixel().map(|_| CnvValue::Null)
             }
@@ -634,9 +783,11 @@ impl CnvType for Animation {
                 .borrow()
                 .get_priority()
                 .map(|v| CnvValue::Integer(v as i32)),
-            CallableIdentifier::Method("GETWIDTH") => {
-                self.state.borrow().get_width().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETWIDTH") => self
+                .state
+                .borrow_mut()
+                .get_width(context)
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("HIDE") => {
                 self.state.borrow_mut().hide().map(|_| CnvValue::Null)
             }
@@ -644,12 +795,35 @@ impl CnvType for Animation {
                 self.state.borrow_mut().invalidate().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("ISAT") => {
-                self.state.borrow().is_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .is_at(
+                        context,
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                        arguments.get(2).map(|v| v.to_bool()).unwrap_or_default(),
+                    )
+                    .map(CnvValue::Bool)
             }
             CallableIdentifier::Method("ISINSIDE") => {
-                self.state.borrow().is_inside().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 4..)?;
+                self.state
+                    .borrow_mut()
+                    .is_inside(
+                        context,
+                        (
+                            arguments[0].to_int() as isize,
+                            arguments[1].to_int() as isize,
+                            arguments[2].to_int() as isize,
+                            arguments[3].to_int() as isize,
+                        )
+                            .into(),
+                    )
+                    .map(CnvValue::Bool)
             }
             CallableIdentifier::Method("ISNEAR") => {
+                check_argument_count(arguments, 2..)?;
                 let name = arguments[0].to_str();
                 let other = context
                     .runner
@@ -664,13 +838,26 @@ impl CnvType for Animation {
                 self.state.borrow().is_playing().map(CnvValue::Bool)
             }
             CallableIdentifier::Method("ISVISIBLE") => {
-                self.state.borrow().is_visible().map(CnvValue::Bool)
+                let has_data =
+                    matches!(*self.state.borrow().file_data, AnimationFileData::Loaded(_));
+                Ok(CnvValue::Bool(
+                    has_data
+                        && context.runner.is_effectively_visible(
+                            &self.parent,
+                            &context.runner.get_objects_hidden_by_groups(),
+                        )?,
+                ))
+            }
+            CallableIdentifier::Method("LOAD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .load(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("MERGEALPHA") if arguments.is_empty() => {
+                self.bake_onto_canvas(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("LOAD") => self
-                .state
-                .borrow_mut()
-                .load(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
             CallableIdentifier::Method("MERGEALPHA") => self
                 .state
                 .borrow_mut()
@@ -681,39 +868,55 @@ impl CnvType for Animation {
                 .borrow_mut()
                 .monitor_collision()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("MOVE") => self
-                .state
-                .borrow_mut()
-                .move_by(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("MOVE") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .move_by(
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("NEXTFRAME") => {
                 self.state.borrow_mut().next_frame().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("NPLAY") => {
-                self.state.borrow_mut().n_play().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("PAUSE") => self
-                .state
-                .borrow_mut()
-                .pause(context)
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("PLAY") => self
+            CallableIdentifier::Method("NPLAY") => self
                 .state
                 .borrow_mut()
-                .play(context, &arguments[0].to_str())
+                .n_play(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("PLAYRAND") => self
+            CallableIdentifier::Method("PAUSE") => self
                 .state
                 .borrow_mut()
-                .play_rand(
-                    &arguments[0].to_str(),
-                    arguments[1].to_int() as usize,
-                    arguments[2].to_int() as usize,
-                )
+                .pause(context)
                 .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("PLAY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .play(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("PLAYQUEUED") => {
+                // custom: plays the sequence after the ones already playing or queued
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .play_queued(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("PLAYRAND") => {
+                check_argument_count(arguments, 3..)?;
+                self.state
+                    .borrow_mut()
+                    .play_rand(
+                        &arguments[0].to_str(),
+                        arguments[1].to_int() as usize,
+                        arguments[2].to_int() as usize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("PLAYREVERSE") => self
                 .state
                 .borrow_mut()
@@ -742,16 +945,20 @@ impl CnvType for Animation {
                 .borrow_mut()
                 .resume(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETANCHOR") => self
-                .state
-                .borrow_mut()
-                .set_anchor(&arguments[0].to_str())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETASBUTTON") => self
-                .state
-                .borrow_mut()
-                .set_as_button(arguments[0].to_bool(), arguments[1].to_bool())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETANCHOR") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_anchor(&arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETASBUTTON") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .set_as_button(arguments[0].to_bool(), arguments[1].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETBACKWARD") => self
                 .state
                 .borrow_mut()
@@ -767,11 +974,13 @@ impl CnvType for Animation {
                 .borrow_mut()
                 .set_forward()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETFPS") => self
-                .state
-                .borrow_mut()
-                .set_fps(arguments[0].to_int() as usize)
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETFPS") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_fps(arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETFRAME") => {
                 let (sequence_name, frame_no) = match arguments.len() {
                     1 => (None, arguments[0].to_int()),
@@ -805,34 +1014,55 @@ impl CnvType for Animation {
                 .set_frame_name()
                 .map(|_| CnvValue::Null),
             CallableIdentifier::Method("SETFREQ") => {
-                self.state.borrow_mut().set_freq().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("SETONFF") => {
-                self.state.borrow_mut().set_onff().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_freq(context, arguments[0].to_int().max(0) as usize)
+                    .map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SETOPACITY") => self
+            CallableIdentifier::Method("SETONFF") => self
                 .state
                 .borrow_mut()
-                .set_opacity()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPOSITION") => self
-                .state
-                .borrow_mut()
-                .set_position(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_priority(arguments[0].to_int() as isize)
+                .set_onff(arguments.first().map(|v| v.to_bool()).unwrap_or(true))
                 .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETOPACITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_opacity(arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETPOSITION") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .set_position(
+                        context,
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETPAN") => {
-                self.state.borrow_mut().set_pan().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_pan(context, arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SETVOLUME") => {
-                self.state.borrow_mut().set_volume().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_volume(context, arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SHOW") => {
                 self.state.borrow_mut().show().map(|_| CnvValue::Null)
@@ -842,11 +1072,7 @@ impl CnvType for Animation {
                 .borrow_mut()
                 .stop(
                     context,
-                    if arguments.is_empty() {
-                        true // TODO: check
-                    } else {
-                        arguments[0].to_bool()
-                    },
+                    arguments.first().map(|v| v.to_bool()).unwrap_or(true),
                 )
                 .map(|_| CnvValue::Null),
             CallableIdentifier::Event(event_name) => {
@@ -922,109 +1148,21 @@ impl CnvType for Animation {
             .and_then(discard_if_empty)
             .map(parse_bool)
             .transpose()?;
-        let on_click = properties
-            .remove("ONCLICK")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_collision = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCOLLISION" {
-                on_collision.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCOLLISION^") {
-                on_collision.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_collision_finished = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCOLLISIONFINISHED" {
-                on_collision_finished.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCOLLISIONFINISHED^") {
-                on_collision_finished
-                    .insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_finished = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONFINISHED" {
-                on_finished.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONFINISHED^") {
-                on_finished.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_first_frame = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONFIRSTFRAME" {
-                on_first_frame.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONFIRSTFRAME^") {
-                on_first_frame.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_focus_off = properties
-            .remove("ONFOCUSOFF")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_focus_on = properties
-            .remove("ONFOCUSON")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_frame_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONFRAMECHANGED" {
-                on_frame_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONFRAMECHANGED^") {
-                on_frame_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_paused = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONPAUSED" {
-                on_paused.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONPAUSED^") {
-                on_paused.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_release = properties
-            .remove("ONRELEASE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_resumed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONRESUMED" {
-                on_resumed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONRESUMED^") {
-                on_resumed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_started = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSTARTED" {
-                on_started.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSTARTED^") {
-                on_started.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_click = parse_event_handlers(&properties, "ONCLICK")?;
+        let on_collision = parse_event_handlers(&properties, "ONCOLLISION")?;
+        let on_collision_finished = parse_event_handlers(&properties, "ONCOLLISIONFINISHED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_finished = parse_event_handlers(&properties, "ONFINISHED")?;
+        let on_first_frame = parse_event_handlers(&properties, "ONFIRSTFRAME")?;
+        let on_focus_off = parse_event_handlers(&properties, "ONFOCUSOFF")?;
+        let on_focus_on = parse_event_handlers(&properties, "ONFOCUSON")?;
+        let on_frame_changed = parse_event_handlers(&properties, "ONFRAMECHANGED")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_paused = parse_event_handlers(&properties, "ONPAUSED")?;
+        let on_release = parse_event_handlers(&properties, "ONRELEASE")?;
+        let on_resumed = parse_event_handlers(&properties, "ONRESUMED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_started = parse_event_handlers(&properties, "ONSTARTED")?;
         Ok(CnvContent::Animation(Animation::from_initial_properties(
             parent,
             AnimationProperties {
@@ -1105,49 +1243,55 @@ impl AnimationState {
         Ok(())
     }
 
-    pub fn get_alpha(&self) -> anyhow::Result<()> {
+    pub fn get_alpha(&self) -> anyhow::Result<usize> {
         // GETALPHA
-        todo!()
+        Ok(self.opacity)
     }
 
     pub fn get_anchor(&self) -> anyhow::Result<&str> {
         // GETANCHOR STRING
-        todo!()
+        Ok(self.anchor.map(|a| a.get_name()).unwrap_or_default())
     }
 
-    pub fn get_center_x(&self) -> anyhow::Result<()> {
-        // GETCENTERX
-        todo!()
+    pub fn get_center_x(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETCENTERX INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_center_frame_position(context).map(|p| p.0)
     }
 
-    pub fn get_center_y(&self) -> anyhow::Result<()> {
-        // GETCENTERY
-        todo!()
+    pub fn get_center_y(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETCENTERY INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_center_frame_position(context).map(|p| p.1)
     }
 
     pub fn get_cframe_in_event(&self) -> anyhow::Result<usize> {
         // GETCFRAMEINEVENT INTEGER
-        todo!()
+        Ok(self.current_frame.frame_idx)
     }
 
-    pub fn get_curr_frame_pos_x(&self) -> anyhow::Result<()> {
-        // GETCURRFRAMEPOSX
-        todo!()
+    pub fn get_curr_frame_pos_x(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETCURRFRAMEPOSX INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_frame_position(context).map(|p| p.0)
     }
 
-    pub fn get_curr_frame_pos_y(&self) -> anyhow::Result<()> {
-        // GETCURRFRAMEPOSY
-        todo!()
+    pub fn get_curr_frame_pos_y(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETCURRFRAMEPOSY INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_frame_position(context).map(|p| p.1)
     }
 
-    pub fn get_end_x(&self) -> anyhow::Result<()> {
-        // GETENDX
-        todo!()
+    pub fn get_end_x(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETENDX INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_end_position(context).map(|p| p.0)
     }
 
-    pub fn get_end_y(&self) -> anyhow::Result<()> {
-        // GETENDY
-        todo!()
+    pub fn get_end_y(&mut self, context: RunnerContext) -> anyhow::Result<isize> {
+        // GETENDY INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_end_position(context).map(|p| p.1)
     }
 
     pub fn get_sequence_name(&self, context: RunnerContext) -> anyhow::Result<String> {
@@ -1161,9 +1305,9 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn get_fps(&self) -> anyhow::Result<()> {
-        // GETFPS
-        todo!()
+    pub fn get_fps(&self) -> anyhow::Result<i32> {
+        // GETFPS INTEGER
+        Ok(self.fps)
     }
 
     pub fn get_frame(&self) -> anyhow::Result<usize> {
@@ -1171,9 +1315,10 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn get_frame_name(&self) -> anyhow::Result<()> {
-        // GETFRAMENAME
-        todo!()
+    pub fn get_frame_name(&self, context: RunnerContext) -> anyhow::Result<String> {
+        // GETFRAMENAME STRING
+        let (_, frame) = self.get_frame_data(context)?;
+        Ok(frame.name.clone())
     }
 
     pub fn get_frame_index(&self) -> anyhow::Result<usize> {
@@ -1181,19 +1326,22 @@ impl AnimationState {
         Ok(self.current_frame.frame_idx)
     }
 
-    pub fn get_height(&self) -> anyhow::Result<()> {
-        // GETHEIGHT
-        todo!()
+    pub fn get_height(&mut self, context: RunnerContext) -> anyhow::Result<usize> {
+        // GETHEIGHT INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_frame_size(context).map(|s| s.1)
     }
 
-    pub fn get_max_height(&self) -> anyhow::Result<()> {
-        // GETMAXHEIGHT
-        todo!()
+    pub fn get_max_height(&mut self, context: RunnerContext) -> anyhow::Result<usize> {
+        // GETMAXHEIGHT INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_max_sprite_size(context).map(|s| s.1)
     }
 
-    pub fn get_max_width(&self) -> anyhow::Result<()> {
-        // GETMAXWIDTH
-        todo!()
+    pub fn get_max_width(&mut self, context: RunnerContext) -> anyhow::Result<usize> {
+        // GETMAXWIDTH INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_max_sprite_size(context).map(|s| s.0)
     }
 
     pub fn get_sequence_count(&self) -> anyhow::Result<()> {
@@ -1211,9 +1359,9 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn get_opacity(&self) -> anyhow::Result<()> {
+    pub fn get_opacity(&self) -> anyhow::Result<usize> {
         // GETOPACITY
-        todo!()
+        Ok(self.opacity)
     }
 
     pub fn get_pixel(&self) -> anyhow::Result<()> {
@@ -1236,9 +1384,10 @@ impl AnimationState {
         Ok(self.priority)
     }
 
-    pub fn get_width(&self) -> anyhow::Result<()> {
-        // GETWIDTH
-        todo!()
+    pub fn get_width(&mut self, context: RunnerContext) -> anyhow::Result<usize> {
+        // GETWIDTH INTEGER
+        self.load_if_needed(context.clone())?;
+        self.get_frame_size(context).map(|s| s.0)
     }
 
     pub fn hide(&mut self) -> anyhow::Result<()> {
@@ -1252,14 +1401,48 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn is_at(&self) -> anyhow::Result<()> {
-        // ISAT
-        todo!()
+    pub fn is_at(
+        &mut self,
+        context: RunnerContext,
+        x: isize,
+        y: isize,
+        respect_alpha: bool,
+    ) -> anyhow::Result<bool> {
+        // ISAT (INTEGER, INTEGER, [BOOL])
+        self.load_if_needed(context.clone())?;
+        if !matches!(*self.file_data, AnimationFileData::Loaded(_)) {
+            return Ok(false);
+        }
+        let (rect, sprite) = self.get_sprite_data(context)?;
+        if x < rect.top_left_x
+            || y < rect.top_left_y
+            || x >= rect.bottom_right_x
+            || y >= rect.bottom_right_y
+        {
+            return Ok(false);
+        }
+        if !respect_alpha {
+            return Ok(true);
+        }
+        let pixel_idx =
+            (y - rect.top_left_y) as usize * rect.get_width() + (x - rect.top_left_x) as usize;
+        Ok(sprite
+            .data
+            .get(pixel_idx * 4 + 3)
+            .is_some_and(|alpha| *alpha > 0))
     }
 
-    pub fn is_inside(&self) -> anyhow::Result<()> {
-        // ISINSIDE
-        todo!()
+    pub fn is_inside(&mut self, context: RunnerContext, bounds: Rect) -> anyhow::Result<bool> {
+        // ISINSIDE (INTEGER, INTEGER, INTEGER, INTEGER)
+        self.load_if_needed(context.clone())?;
+        if !matches!(*self.file_data, AnimationFileData::Loaded(_)) {
+            return Ok(false);
+        }
+        let (rect, _) = self.get_sprite_data(context)?;
+        Ok(rect.top_left_x >= bounds.top_left_x
+            && rect.top_left_y >= bounds.top_left_y
+            && rect.bottom_right_x <= bounds.bottom_right_x
+            && rect.bottom_right_y <= bounds.bottom_right_y)
     }
 
     pub fn is_near(
@@ -1345,6 +1528,10 @@ impl AnimationState {
                 RunnerError::IoError { source: e }
             })?;
         let data = parse_ann(&data);
+        if !self.has_explicit_fps && data.header.frames_per_second > 0 {
+            // FPS property and SETFPS take precedence over the file header
+            self.fps = data.header.frames_per_second.min(i32::MAX as u32) as i32;
+        }
         self.current_frame = FrameIdentifier {
             sequence_idx: data
                 .sequences
@@ -1428,9 +1615,34 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn n_play(&self) -> anyhow::Result<()> {
+    pub fn n_play(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // NPLAY
-        todo!()
+        self.load_if_needed(context.clone())?;
+        let AnimationFileData::Loaded(ref loaded_data) = *self.file_data.clone() else {
+            return Err(
+                RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
+            );
+        };
+        let Some(sequence) = loaded_data.sequences.get(self.current_frame.sequence_idx) else {
+            return Ok(());
+        };
+        self.is_playing = true;
+        self.is_paused = false;
+        self.is_reversed = false;
+        self.is_visible = true;
+        context
+            .runner
+            .internal_events
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(InternalEvent {
+                    context: context
+                        .clone()
+                        .with_arguments(vec![CnvValue::String(sequence.name.clone())]),
+                    callable: CallableIdentifier::Event("ONSTARTED").to_owned(),
+                });
+            });
+        Ok(())
     }
 
     pub fn pause(&mut self, context: RunnerContext) -> anyhow::Result<()> {
@@ -1476,14 +1688,22 @@ impl AnimationState {
                 object_name: context.current_object.name.clone(),
                 sequence_name: sequence_name.to_owned(),
             })?;
+        // restarts from the first frame even if the sequence is already playing
+        self.queued_sequences.clear();
         self.current_frame = FrameIdentifier {
             sequence_idx,
             frame_idx: 0,
         };
+        self.current_frame_duration = 0.0;
+        self.sprite_idx_override = None;
         self.is_playing = true;
         self.is_paused = false;
         self.is_reversed = false;
-        if let Some(sfx) = sequence.frames[0].sfx.choose(&mut thread_rng()).cloned() {
+        if let Some(sfx) = sequence
+            .frames
+            .first()
+            .and_then(|f| f.sfx.choose(&mut thread_rng()).cloned())
+        {
             self.play_sfx(context.clone(), &sfx).ok_or_error();
         }
         context
@@ -1497,12 +1717,14 @@ impl AnimationState {
                         .with_arguments(vec![CnvValue::String(sequence_name.to_owned())]),
                     callable: CallableIdentifier::Event("ONSTARTED").to_owned(),
                 });
-                events.push_back(InternalEvent {
-                    context: context
-                        .clone()
-                        .with_arguments(vec![CnvValue::String(sequence_name.to_owned())]),
-                    callable: CallableIdentifier::Event("ONFIRSTFRAME").to_owned(),
-                })
+                if self.does_emit_first_frame {
+                    events.push_back(InternalEvent {
+                        context: context
+                            .clone()
+                            .with_arguments(vec![CnvValue::String(sequence_name.to_owned())]),
+                        callable: CallableIdentifier::Event("ONFIRSTFRAME").to_owned(),
+                    })
+                }
             });
         self.is_visible = true;
         Ok(())
@@ -1566,9 +1788,12 @@ impl AnimationState {
         Ok(())
     }
 
-    pub fn set_anchor(&self, _arg1: &str) -> anyhow::Result<()> {
+    pub fn set_anchor(&mut self, anchor: &str) -> anyhow::Result<()> {
         // SETANCHOR (STRING)
-        todo!()
+        self.anchor = Some(Anchor::parse(anchor).ok_or(RunnerError::InvalidAnchor {
+            anchor: anchor.to_owned(),
+        })?);
+        Ok(())
     }
 
     pub fn set_as_button(
@@ -1603,9 +1828,11 @@ impl AnimationState {
         Ok(())
     }
 
-    pub fn set_fps(&mut self, fps: usize) -> anyhow::Result<()> {
+    pub fn set_fps(&mut self, fps: i32) -> anyhow::Result<()> {
         // SETFPS
+        // 0 holds the current frame, negative values play backwards
         self.fps = fps;
+        self.has_explicit_fps = true;
         Ok(())
     }
 
@@ -1616,15 +1843,50 @@ impl AnimationState {
         frame_no: usize,
     ) -> anyhow::Result<()> {
         // SETFRAME ([STRING], INTEGER)
-        self.load_if_needed(context)?;
+        self.load_if_needed(context.clone())?;
         let AnimationFileData::Loaded(ref loaded_data) = *self.file_data else {
             return Ok(());
         };
-        if let Some(_sequence_name) = sequence_name {
-            todo!()
-        } else if loaded_data.sprites.len() > frame_no {
-            self.sprite_idx_override = Some(frame_no);
+        // the engine clamps frames past the end of the sequence to the last one
+        if let Some(sequence_name) = sequence_name {
+            let sequence_idx = loaded_data
+                .sequences
+                .iter()
+                .position(|s| s.name.eq_ignore_ascii_case(sequence_name))
+                .ok_or(RunnerError::SequenceNameNotFound {
+                    object_name: context.current_object.name.clone(),
+                    sequence_name: sequence_name.to_owned(),
+                })?;
+            let sequence = &loaded_data.sequences[sequence_idx];
+            let frame_idx = frame_no.min(sequence.frames.len().saturating_sub(1));
+            self.current_frame = FrameIdentifier {
+                sequence_idx,
+                frame_idx,
+            };
+            self.current_frame_duration = 0.0;
+            self.sprite_idx_override = None;
+        } else if !loaded_data.sprites.is_empty() {
+            self.sprite_idx_override = Some(frame_no.min(loaded_data.sprites.len() - 1));
+        } else {
+            return Ok(());
         }
+        let sequence_name = loaded_data
+            .sequences
+            .get(self.current_frame.sequence_idx)
+            .map(|s| s.name.clone())
+            .unwrap_or_default();
+        context
+            .runner
+            .internal_events
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(InternalEvent {
+                    context: context
+                        .clone()
+                        .with_arguments(vec![CnvValue::String(sequence_name)]),
+                    callable: CallableIdentifier::Event("ONFRAMECHANGED").to_owned(),
+                })
+            });
         Ok(())
     }
 
@@ -1633,24 +1895,43 @@ impl AnimationState {
         todo!()
     }
 
-    pub fn set_freq(&self) -> anyhow::Result<()> {
-        // SETFREQ
-        todo!()
+    pub fn set_freq(&mut self, context: RunnerContext, frequency: usize) -> anyhow::Result<()> {
+        // SETFREQ (INTEGER)
+        self.frequency = Some(frequency);
+        self.update_sfx_parameters(context);
+        Ok(())
     }
 
-    pub fn set_onff(&self) -> anyhow::Result<()> {
-        // SETONFF
-        todo!()
+    pub fn set_onff(&mut self, does_emit_first_frame: bool) -> anyhow::Result<()> {
+        // SETONFF ([BOOL])
+        self.does_emit_first_frame = does_emit_first_frame;
+        Ok(())
     }
 
-    pub fn set_opacity(&self) -> anyhow::Result<()> {
-        // SETOPACITY
-        todo!()
+    pub fn set_opacity(&mut self, opacity: i32) -> anyhow::Result<()> {
+        // SETOPACITY (INTEGER)
+        self.opacity = opacity.clamp(0, 255) as usize;
+        Ok(())
     }
 
-    pub fn set_position(&mut self, x: isize, y: isize) -> anyhow::Result<()> {
+    pub fn set_position(
+        &mut self,
+        context: RunnerContext,
+        x: isize,
+        y: isize,
+    ) -> anyhow::Result<()> {
         // SETPOSITION
-        self.position = (x, y);
+        let Some(anchor) = self.anchor else {
+            self.position = (x, y);
+            return Ok(());
+        };
+        self.load_if_needed(context.clone())?;
+        let (_, frame, sprite) = self.get_frame_sprite_data(context)?;
+        let anchor_offset = anchor.get_offset(pair_u32_to_usize(sprite.0.size_px));
+        self.position = (
+            x - frame.offset_px.0 as isize - sprite.0.offset_px.0 as isize - anchor_offset.0,
+            y - frame.offset_px.1 as isize - sprite.0.offset_px.1 as isize - anchor_offset.1,
+        );
         Ok(())
     }
 
@@ -1660,14 +1941,18 @@ impl AnimationState {
         Ok(())
     }
 
-    pub fn set_pan(&self) -> anyhow::Result<()> {
-        // SETPAN
-        todo!()
+    pub fn set_pan(&mut self, context: RunnerContext, pan: isize) -> anyhow::Result<()> {
+        // SETPAN (INTEGER)
+        self.panning = Some(pan.clamp(-1000, 1000));
+        self.update_sfx_parameters(context);
+        Ok(())
     }
 
-    pub fn set_volume(&self) -> anyhow::Result<()> {
-        // SETVOLUME
-        todo!()
+    pub fn set_volume(&mut self, context: RunnerContext, volume: isize) -> anyhow::Result<()> {
+        // SETVOLUME (INTEGER)
+        self.volume = volume.clamp(0, 1000);
+        self.update_sfx_parameters(context);
+        Ok(())
     }
 
     pub fn show(&mut self) -> anyhow::Result<()> {
@@ -1684,6 +1969,7 @@ impl AnimationState {
                 RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
             );
         };
+        self.queued_sequences.clear();
         if !self.is_playing {
             return Ok(());
         }
@@ -1692,6 +1978,7 @@ impl AnimationState {
         self.is_playing = false;
         self.is_paused = false;
         self.is_reversed = false;
+        self.is_playing_sfx = false;
         context
             .runner
             .events_out
@@ -1714,6 +2001,10 @@ impl AnimationState {
                             .clone()
                             .with_arguments(vec![CnvValue::String(sequence.name.clone())]),
                         callable: CallableIdentifier::Event("ONFINISHED").to_owned(),
+                    });
+                    events.push_back(InternalEvent {
+                        context: context.clone().with_arguments(Vec::new()),
+                        callable: CallableIdentifier::Event("ONDONE").to_owned(),
                     })
                 });
         }
@@ -1722,8 +2013,36 @@ impl AnimationState {
 
     // custom
 
+    pub fn play_queued(
+        &mut self,
+        context: RunnerContext,
+        sequence_name: &str,
+    ) -> anyhow::Result<()> {
+        if !self.is_playing {
+            return self.play(context, sequence_name);
+        }
+        let AnimationFileData::Loaded(ref loaded_data) = *self.file_data.clone() else {
+            return Err(
+                RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
+            );
+        };
+        if !loaded_data
+            .sequences
+            .iter()
+            .any(|s| s.name.eq_ignore_ascii_case(sequence_name))
+        {
+            return Err(RunnerError::SequenceNameNotFound {
+                object_name: context.current_object.name.clone(),
+                sequence_name: sequence_name.to_owned(),
+            }
+            .into());
+        }
+        self.queued_sequences.push_back(sequence_name.to_owned());
+        Ok(())
+    }
+
     fn get_max_frame_duration(&self) -> anyhow::Result<f64> {
-        Ok(1f64 / (self.fps as f64))
+        Ok(1f64 / (self.fps.unsigned_abs() as f64))
     }
 
     pub fn get_base_position(&self) -> anyhow::Result<(isize, isize)> {
@@ -1738,9 +2057,48 @@ impl AnimationState {
         ))
     }
 
+    /// Top-left position the object will have at the last frame of the current sequence.
+    pub fn get_end_position(&self, context: RunnerContext) -> anyhow::Result<(isize, isize)> {
+        let AnimationFileData::Loaded(ref loaded_file) = *self.file_data else {
+            return Err(
+                RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
+            );
+        };
+        let sequence = self.get_sequence_data(context.clone())?;
+        let Some(frame) = sequence.frames.last() else {
+            return Ok(self.position);
+        };
+        let Some(sprite) = loaded_file.sprites.get(frame.sprite_idx) else {
+            return Err(RunnerError::SpriteIndexNotFound {
+                object_name: context.current_object.name.clone(),
+                index: frame.sprite_idx,
+            }
+            .into());
+        };
+        Ok((
+            self.position.0 + frame.offset_px.0 as isize + sprite.0.offset_px.0 as isize,
+            self.position.1 + frame.offset_px.1 as isize + sprite.0.offset_px.1 as isize,
+        ))
+    }
+
+    /// Returns the size of the currently displayed sprite.
     pub fn get_frame_size(&self, context: RunnerContext) -> anyhow::Result<(usize, usize)> {
-        let (_, _, sprite) = self.get_frame_sprite_data(context)?;
-        Ok((sprite.0.size_px.0 as usize, sprite.0.size_px.1 as usize))
+        let (rect, _) = self.get_sprite_data(context)?;
+        Ok((rect.get_width(), rect.get_height()))
+    }
+
+    /// Returns the largest width and height among all sprites of the animation.
+    fn get_max_sprite_size(&self, context: RunnerContext) -> anyhow::Result<(usize, usize)> {
+        let AnimationFileData::Loaded(ref loaded_file) = *self.file_data else {
+            return Err(
+                RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
+            );
+        };
+        Ok(loaded_file
+            .sprites
+            .iter()
+            .map(|(definition, _)| pair_u32_to_usize(definition.size_px))
+            .fold((0, 0), |max, size| (max.0.max(size.0), max.1.max(size.1))))
     }
 
     pub fn get_frame_rect(&self, context: RunnerContext) -> anyhow::Result<Rect> {
@@ -1820,6 +2178,45 @@ impl AnimationState {
         Ok((sequence, frame, sprite))
     }
 
+    pub fn render_frame(
+        &self,
+        context: RunnerContext,
+        sequence_idx: usize,
+        frame_idx: usize,
+    ) -> anyhow::Result<(Rect, SpriteData)> {
+        let AnimationFileData::Loaded(ref loaded_file) = *self.file_data else {
+            return Err(
+                RunnerError::NoAnimationDataLoaded(context.current_object.name.clone()).into(),
+            );
+        };
+        let Some(sequence) = loaded_file.sequences.get(sequence_idx) else {
+            return Err(RunnerError::SequenceIndexNotFound {
+                object_name: context.current_object.name.clone(),
+                index: sequence_idx,
+            }
+            .into());
+        };
+        let Some(frame) = sequence.frames.get(frame_idx) else {
+            return Err(RunnerError::FrameIndexNotFound {
+                object_name: context.current_object.name.clone(),
+                sequence_name: sequence.name.clone(),
+                index: frame_idx,
+            }
+            .into());
+        };
+        let Some(sprite) = loaded_file.sprites.get(frame.sprite_idx) else {
+            return Err(RunnerError::SpriteIndexNotFound {
+                object_name: context.current_object.name.clone(),
+                index: frame.sprite_idx,
+            }
+            .into());
+        };
+        let position = add_tuples(self.position, pair_i32_to_isize(sprite.0.offset_px));
+        let position = add_tuples(position, pair_i32_to_isize(frame.offset_px));
+        let size = pair_u32_to_usize(sprite.0.size_px);
+        Ok((Rect::from(position, size), sprite.1.clone()))
+    }
+
     fn get_sprite_data(&self, context: RunnerContext) -> anyhow::Result<(Rect, SpriteData)> {
         let AnimationFileData::Loaded(ref loaded_file) = *self.file_data else {
             return Err(
@@ -1852,13 +2249,14 @@ impl AnimationState {
         Ok((Rect::from(position, size), sprite.1.clone()))
     }
 
-    pub fn step(&mut self, context: RunnerContext, seconds: f64) -> anyhow::Result<()> {
+    /// Advances playback, returning `true` once a non-looping sequence has reached its end.
+    pub fn step(&mut self, context: RunnerContext, seconds: f64) -> anyhow::Result<bool> {
         let file_data = self.file_data.clone();
         let AnimationFileData::Loaded(ref loaded_data) = *file_data else {
-            return Ok(());
+            return Ok(false);
         };
-        if !self.is_playing || self.is_paused {
-            return Ok(());
+        if !self.is_playing || self.is_paused || self.fps == 0 {
+            return Ok(false);
         }
         // log::trace!("Ticking animation {} with time {}, current frame: {:?}", animation.parent.name, duration, self.current_frame);
         self.sprite_idx_override = None;
@@ -1868,34 +2266,42 @@ impl AnimationState {
         let sequence_name = sequence.name.clone();
         self.current_frame_duration += seconds;
         let max_frame_duration = self.get_max_frame_duration()?;
-        while self.current_frame_duration >= max_frame_duration {
-            // log::trace!("{} / {}", self.current_frame_duration, max_frame_duration);
-            self.current_frame_duration -= max_frame_duration;
+        let due_frames = Clock::take_frames(&mut self.current_frame_duration, max_frame_duration);
+        let is_reversed = self.is_reversed != (self.fps < 0);
+        for _ in 0..due_frames {
             let prev_frame_idx = self.current_frame.frame_idx;
-            let finished = if self.is_reversed {
+            // looping sequences wrap around after their last frame instead of finishing
+            let (last_frame_idx, is_looping) = match sequence_looping {
+                LoopingSettings::LoopingAfter(frame_count) => {
+                    (frame_count.min(sequence_length), true)
+                }
+                LoopingSettings::NoLooping => (sequence_length, false),
+            };
+            let last_frame_idx = last_frame_idx.saturating_sub(1);
+            let finished = if is_reversed {
                 if self.current_frame.frame_idx == 0 {
-                    true
+                    if is_looping {
+                        self.current_frame.frame_idx = last_frame_idx;
+                    }
+                    !is_looping
                 } else {
                     self.current_frame.frame_idx -= 1;
                     false
-                } // TODO: looping after x
-            } else {
-                let limit = match sequence_looping {
-                    LoopingSettings::LoopingAfter(frame_count) => frame_count,
-                    LoopingSettings::NoLooping => sequence_length,
                 }
-                .saturating_sub(1);
-                if self.current_frame.frame_idx == limit {
-                    true
-                } else {
-                    self.current_frame.frame_idx += 1;
-                    false
+            } else if self.current_frame.frame_idx >= last_frame_idx {
+                if is_looping {
+                    self.current_frame.frame_idx = 0;
                 }
+                !is_looping
+            } else {
+                self.current_frame.frame_idx += 1;
+                false
             };
             if finished {
                 self.is_playing = false;
                 self.is_paused = false;
                 self.is_reversed = false;
+                self.is_playing_sfx = false;
                 context
                     .runner
                     .events_out
@@ -1917,8 +2323,21 @@ impl AnimationState {
                                 .clone()
                                 .with_arguments(vec![CnvValue::String(sequence_name.clone())]),
                             callable: CallableIdentifier::Event("ONFINISHED").to_owned(),
+                        });
+                        events.push_back(InternalEvent {
+                            context: context.clone().with_arguments(Vec::new()),
+                            callable: CallableIdentifier::Event("ONDONE").to_owned(),
                         })
                     });
+                // time left over after the last frame must not carry into the next playback
+                self.current_frame_duration = 0.0;
+                if let Some(next_sequence) = self.queued_sequences.pop_front() {
+                    let remaining = std::mem::take(&mut self.queued_sequences);
+                    self.play(context.clone(), &next_sequence)?;
+                    self.queued_sequences = remaining;
+                    return Ok(false);
+                }
+                return Ok(true);
             } else if self.current_frame.frame_idx != prev_frame_idx {
                 if let Some(sfx) = sequence.frames[self.current_frame.frame_idx]
                     .sfx
@@ -1942,7 +2361,7 @@ impl AnimationState {
             }
         }
         // log::trace!("Moved animation {} to frame: {:?}", animation.parent.name, self.current_frame);
-        Ok(())
+        Ok(false)
     }
 
     fn load_sfx(&mut self, context: RunnerContext, path: &ScenePath) -> anyhow::Result<()> {
@@ -1986,6 +2405,8 @@ impl AnimationState {
                 &context.current_object.parent.path.with_file_path(path),
             )?;
         }
+        let parameters = self.get_sfx_parameters(context.clone());
+        self.is_playing_sfx = true;
         context
             .runner
             .events_out
@@ -1996,6 +2417,13 @@ impl AnimationState {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
                 }));
+                events.push_back(SoundEvent::SoundParametersChanged {
+                    source: SoundSource::AnimationSfx {
+                        script_path: context.current_object.parent.path.clone(),
+                        object_name: context.current_object.name.clone(),
+                    },
+                    parameters,
+                });
                 events.push_back(SoundEvent::SoundStarted(SoundSource::AnimationSfx {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
@@ -2004,6 +2432,62 @@ impl AnimationState {
         Ok(())
     }
 
+    fn update_sfx_parameters(&self, context: RunnerContext) {
+        if !self.is_playing_sfx {
+            return;
+        }
+        let parameters = self.get_sfx_parameters(context.clone());
+        context
+            .runner
+            .events_out
+            .sound
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(SoundEvent::SoundParametersChanged {
+                    source: SoundSource::AnimationSfx {
+                        script_path: context.current_object.parent.path.clone(),
+                        object_name: context.current_object.name.clone(),
+                    },
+                    parameters,
+                })
+            });
+    }
+
+    fn get_sfx_parameters(&self, context: RunnerContext) -> SoundParameters {
+        let pan = match self.panning {
+            Some(panning) => panning as f32 / 1000f32,
+            None => self.get_positional_pan(context),
+        };
+        SoundParameters {
+            volume: self.volume as f32 / 1000f32,
+            pan,
+            frequency: self.frequency,
+        }
+    }
+
+    fn get_positional_pan(&self, context: RunnerContext) -> f32 {
+        let window_rect = *context.runner.window_rect.borrow();
+        let half_width = window_rect.get_width() as f32 / 2f32;
+        if half_width <= 0f32 {
+            return 0f32;
+        }
+        let Some(center) = self.get_center_frame_position(context).ok_or_error() else {
+            return 0f32;
+        };
+        ((center.0 - window_rect.get_center().0) as f32 / half_width).clamp(-1f32, 1f32)
+    }
+
+    /// Drops decoded animation data so that it gets loaded again on next use.
+    fn flush_file_data(&mut self) {
+        if let AnimationFileData::Loaded(LoadedAnimation {
+            filename: Some(ref filename),
+            ..
+        }) = *self.file_data
+        {
+            self.file_data = Arc::new(AnimationFileData::NotLoaded(filename.clone()));
+        }
+    }
+
     fn load_if_needed(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         if let AnimationFileData::NotLoaded(ref filename) = *self.file_data {
             let filename = filename.clone();
@@ -86,6 +86,13 @@ impl Application {
         app
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
     pub fn get_episode_list(&self) -> Vec<String> {
@@ -158,18 +165,22 @@ impl CnvType for Application {
                 self.state.borrow_mut().restart().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("RUN") => {
-                self.state
-                    .borrow_mut()
-                    .run(
-                        context,
-                        arguments[0].to_str(),
-                        arguments[1].to_str(),
-                        arguments.iter().skip(2).map(|v| v.to_owned()).collect(),
-                    )
-                    .map(|_| CnvValue::Null) // TODO: return something
+                check_argument_count(arguments, 1..)?;
+                self.state.borrow().run(
+                    context,
+                    arguments[0].to_str(),
+                    arguments.get(1).map(|v| v.to_str()),
+                    arguments.iter().skip(2).map(|v| v.to_owned()).collect(),
+                )
             }
             CallableIdentifier::Method("RUNENV") => {
-                self.state.borrow_mut().run_env().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state.borrow().run_env(
+                    context,
+                    arguments[0].to_str(),
+                    arguments.get(1).map(|v| v.to_str()),
+                    arguments.iter().skip(2).map(|v| v.to_owned()).collect(),
+                )
             }
             CallableIdentifier::Method("SETLANGUAGE") => self
                 .state
@@ -311,33 +322,47 @@ impl ApplicationState {
     }
 
     pub fn run(
-        &mut self,
+        &self,
         context: RunnerContext,
         object_name: String,
-        method_name: String,
+        method_name: Option<String>,
         arguments: Vec<CnvValue>,
-    ) -> anyhow::Result<()> {
-        // RUN
+    ) -> anyhow::Result<CnvValue> {
+        // RUN (STRING, [STRING, ...])
         let Some(object) = context.runner.get_object(&object_name) else {
             return Err(RunnerError::ObjectNotFound { name: object_name }.into());
         };
-        let evt_context = RunnerContext::new(&context.runner, &object, &object, &arguments);
-        context
-            .runner
-            .internal_events
-            .borrow_mut()
-            .use_and_drop_mut(move |events| {
-                events.push_back(InternalEvent {
-                    context: evt_context,
-                    callable: CallableIdentifierOwned::Method(method_name),
-                })
-            });
-        Ok(())
+        let method_name = method_name.unwrap_or_else(|| "RUN".to_owned());
+        object.call_method(
+            CallableIdentifier::Method(&method_name),
+            &arguments,
+            Some(context.with_arguments(arguments.clone())),
+        )
     }
 
-    pub fn run_env(&mut self) -> anyhow::Result<()> {
-        // RUNENV
-        todo!()
+    pub fn run_env(
+        &self,
+        context: RunnerContext,
+        object_name: String,
+        method_name: Option<String>,
+        arguments: Vec<CnvValue>,
+    ) -> anyhow::Result<CnvValue> {
+        // RUNENV (STRING, [STRING, ...])
+        let Some(object) = context.runner.get_object(&object_name) else {
+            return Err(RunnerError::ObjectNotFound { name: object_name }.into());
+        };
+        let method_name = method_name.unwrap_or_else(|| "RUN".to_owned());
+        let env_context = RunnerContext::new(
+            &context.runner,
+            &context.current_object,
+            &object,
+            &arguments,
+        );
+        object.call_method(
+            CallableIdentifier::Method(&method_name),
+            &arguments,
+            Some(env_context),
+        )
     }
 
     pub fn set_language(&mut self) -> anyhow::Result<()> {
@@ -7,9 +7,9 @@ use pixlib_formats::file_formats::{
 };
 
 use super::super::{
-    content::EventHandler,
+    content::{get_matching_handler, EventHandler},
     initable::Initable,
-    parsers::{discard_if_empty, parse_bool, parse_event_handler},
+    parsers::{discard_if_empty, parse_bool, parse_event_handlers},
 };
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
@@ -23,10 +23,10 @@ pub struct ArrayProperties {
     // ARRAY
     pub send_on_change: Option<bool>, // SENDONCHANGE
 
-    pub on_change: Option<Arc<ParsedScript>>, // ONCHANGE signal
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_change: HashMap<String, Arc<ParsedScript>>, // ONCHANGE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,   // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,   // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -42,19 +42,19 @@ struct ArrayState {
 
 #[derive(Debug, Clone)]
 pub struct ArrayEventHandlers {
-    pub on_change: Option<Arc<ParsedScript>>, // ONCHANGE signal
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_change: HashMap<String, Arc<ParsedScript>>, // ONCHANGE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,   // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,   // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for ArrayEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCHANGE" => self.on_change.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONCHANGE" => get_matching_handler(&self.on_change, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -84,6 +84,13 @@ impl Array {
             },
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl CnvType for Array {
@@ -112,7 +119,11 @@ impl CnvType for Array {
                 .add(arguments)
                 .map(|_| CnvValue::Null),
             CallableIdentifier::Method("ADDAT") => {
-                self.state.borrow_mut().add_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .add_at(arguments[0].to_int(), &arguments[1])
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("ADDCLONES") => {
                 self.state.borrow_mut().add_clones().map(|_| CnvValue::Null)
@@ -121,7 +132,11 @@ impl CnvType for Array {
                 self.state.borrow_mut().change_at().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("CLAMPAT") => {
-                self.state.borrow_mut().clamp_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 3..)?;
+                self.state
+                    .borrow_mut()
+                    .clamp_at(arguments[0].to_int(), &arguments[1], &arguments[2])
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("COMPARE") => {
                 self.state.borrow().compare().map(|_| CnvValue::Null)
@@ -130,7 +145,11 @@ impl CnvType for Array {
                 self.state.borrow().contains().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("COPYTO") => {
-                self.state.borrow_mut().copy_to().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .copy_to(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("DIR") => {
                 self.state.borrow_mut().dir().map(|_| CnvValue::Null)
@@ -154,6 +173,7 @@ impl CnvType for Array {
                 self.state.borrow().find_all().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("GET") => {
+                check_argument_count(arguments, 1..)?;
                 self.state.borrow().get(arguments[0].to_int() as usize)
             }
             CallableIdentifier::Method("GETMARKERPOS") => {
@@ -168,13 +188,19 @@ impl CnvType for Array {
             CallableIdentifier::Method("INSERTAT") => {
                 self.state.borrow_mut().insert_at().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("LOAD") => self
-                .state
-                .borrow_mut()
-                .load(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("LOAD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .load(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("LOADINI") => {
-                self.state.borrow_mut().load_ini().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .load_ini(context, &arguments[0].to_str(), &arguments[1].to_str())
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("MAX") => {
                 self.state.borrow_mut().max().map(|_| CnvValue::Null)
@@ -189,7 +215,11 @@ impl CnvType for Array {
                 self.state.borrow_mut().min_d().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("MODAT") => {
-                self.state.borrow_mut().mod_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .mod_at(context, arguments[0].to_int(), &arguments[1])
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("MUL") => {
                 self.state.borrow_mut().mul().map(|_| CnvValue::Null)
@@ -198,7 +228,11 @@ impl CnvType for Array {
                 self.state.borrow_mut().mul_a().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("MULAT") => {
-                self.state.borrow_mut().mul_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .mul_at(arguments[0].to_int(), &arguments[1])
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("NEXT") => {
                 self.state.borrow_mut().next().map(|_| CnvValue::Null)
@@ -238,13 +272,19 @@ impl CnvType for Array {
                 .borrow_mut()
                 .rotate_right()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SAVE") => self
-                .state
-                .borrow_mut()
-                .save(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SAVE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .save(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SAVEINI") => {
-                self.state.borrow_mut().save_ini().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .save_ini(context, &arguments[0].to_str(), &arguments[1].to_str())
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SENDONCHANGE") => self
                 .state
@@ -277,7 +317,11 @@ impl CnvType for Array {
                 self.state.borrow_mut().sub_a().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SUBAT") => {
-                self.state.borrow_mut().sub_at().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .sub_at(arguments[0].to_int(), &arguments[1])
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SUM") => {
                 self.state.borrow_mut().sum().map(|_| CnvValue::Null)
@@ -316,26 +360,10 @@ impl CnvType for Array {
             .map(parse_bool)
             .transpose()?;
         // TODO: error when there are superfluous properties
-        let on_change = properties
-            .remove("ONCHANGE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_change = parse_event_handlers(&properties, "ONCHANGE")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Array(Self::from_initial_properties(
             parent,
             ArrayProperties {
@@ -373,9 +401,12 @@ impl ArrayState {
         Ok(())
     }
 
-    pub fn add_at(&mut self) -> anyhow::Result<()> {
+    pub fn add_at(&mut self, index: i32, value: &CnvValue) -> anyhow::Result<()> {
         // ADDAT
-        todo!()
+        if let Some(element) = self.get_element_mut(index) {
+            *element = &*element + value;
+        }
+        Ok(())
     }
 
     pub fn add_clones(&mut self) -> anyhow::Result<()> {
@@ -388,9 +419,15 @@ impl ArrayState {
         todo!()
     }
 
-    pub fn clamp_at(&mut self) -> anyhow::Result<()> {
+    pub fn clamp_at(&mut self, index: i32, min: &CnvValue, max: &CnvValue) -> anyhow::Result<()> {
         // CLAMPAT
-        todo!()
+        if let Some(element) = self.get_element_mut(index) {
+            *element = match &*element {
+                CnvValue::Double(d) => CnvValue::Double(d.max(min.to_dbl()).min(max.to_dbl())),
+                other => CnvValue::Integer(other.to_int().max(min.to_int()).min(max.to_int())),
+            };
+        }
+        Ok(())
     }
 
     pub fn compare(&self) -> anyhow::Result<()> {
@@ -403,9 +440,28 @@ impl ArrayState {
         todo!()
     }
 
-    pub fn copy_to(&mut self) -> anyhow::Result<()> {
+    pub fn copy_to(&self, context: RunnerContext, target_name: &str) -> anyhow::Result<()> {
         // COPYTO
-        todo!()
+        let target_object =
+            context
+                .runner
+                .get_object(target_name)
+                .ok_or(RunnerError::ObjectNotFound {
+                    name: target_name.to_owned(),
+                })?;
+        if target_object == context.current_object {
+            return Ok(());
+        }
+        let CnvContent::Array(ref target_array) = &target_object.content else {
+            return Err(RunnerError::UnexpectedType {
+                object_name: target_name.to_owned(),
+                expected: "ARRAY".to_owned(),
+                actual: target_object.content.get_type_id().to_owned(),
+            }
+            .into());
+        };
+        target_array.state.borrow_mut().values = self.values.clone();
+        Ok(())
     }
 
     pub fn dir(&mut self) -> anyhow::Result<()> {
@@ -498,9 +554,39 @@ impl ArrayState {
         Ok(())
     }
 
-    pub fn load_ini(&mut self) -> anyhow::Result<()> {
-        // LOADINI
-        todo!()
+    pub fn load_ini(
+        &mut self,
+        context: RunnerContext,
+        filename: &str,
+        section: &str,
+    ) -> anyhow::Result<()> {
+        // LOADINI (STRING, STRING)
+        let script = context.current_object.parent.as_ref();
+        let filesystem = Arc::clone(&script.runner.filesystem);
+        let data = filesystem
+            .write()
+            .unwrap()
+            .read_scene_asset(
+                Arc::clone(&script.runner.game_paths),
+                &script.path.with_file_path(filename),
+            )
+            .map_err(|e| RunnerError::IoError { source: e })?;
+        let Some(text) = DecodedStr::from_bytes(&data).ok_or_error() else {
+            Err(RunnerError::CouldNotLoadFile(filename.to_owned()))?
+        };
+        let Some((_, entries)) = split_ini_sections(&text.0)
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(section))
+        else {
+            return Ok(());
+        };
+        self.values = entries
+            .into_iter()
+            .filter_map(|(key, value)| key.parse::<usize>().ok().map(|i| (i, value)))
+            .sorted_by_key(|(i, _)| *i)
+            .map(|(_, value)| parse_ini_value(&value))
+            .collect();
+        Ok(())
     }
 
     pub fn max(&mut self) -> anyhow::Result<()> {
@@ -523,9 +609,32 @@ impl ArrayState {
         todo!()
     }
 
-    pub fn mod_at(&mut self) -> anyhow::Result<()> {
+    pub fn mod_at(
+        &mut self,
+        context: RunnerContext,
+        index: i32,
+        divisor: &CnvValue,
+    ) -> anyhow::Result<()> {
         // MODAT
-        todo!()
+        let Some(element) = self.get_element_mut(index) else {
+            return Ok(());
+        };
+        // integer elements are divided by the divisor truncated to an integer
+        let is_zero = match element {
+            CnvValue::Integer(_) => divisor.to_int() == 0,
+            _ => divisor.to_dbl() == 0.0,
+        };
+        if is_zero {
+            return Err(RunnerError::DivisionByZero {
+                object_name: context.current_object.name.clone(),
+            }
+            .into());
+        }
+        *element = match element {
+            CnvValue::Integer(i) => CnvValue::Integer(i.wrapping_rem(divisor.to_int())),
+            _ => &*element % divisor,
+        };
+        Ok(())
     }
 
     pub fn mul(&mut self) -> anyhow::Result<()> {
@@ -538,9 +647,12 @@ impl ArrayState {
         todo!()
     }
 
-    pub fn mul_at(&mut self) -> anyhow::Result<()> {
+    pub fn mul_at(&mut self, index: i32, factor: &CnvValue) -> anyhow::Result<()> {
         // MULAT
-        todo!()
+        if let Some(element) = self.get_element_mut(index) {
+            *element = &*element * factor;
+        }
+        Ok(())
     }
 
     pub fn next(&mut self) -> anyhow::Result<()> {
@@ -625,9 +737,55 @@ impl ArrayState {
         Ok(())
     }
 
-    pub fn save_ini(&mut self) -> anyhow::Result<()> {
-        // SAVEINI
-        todo!()
+    pub fn save_ini(
+        &mut self,
+        context: RunnerContext,
+        filename: &str,
+        section: &str,
+    ) -> anyhow::Result<()> {
+        // SAVEINI (STRING, STRING)
+        let script = context.current_object.parent.as_ref();
+        let filesystem = Arc::clone(&script.runner.filesystem);
+        let path = script.path.with_file_path(filename);
+        let existing = filesystem
+            .write()
+            .unwrap()
+            .read_scene_asset(Arc::clone(&script.runner.game_paths), &path)
+            .ok()
+            .and_then(|data| DecodedStr::from_bytes(&data).ok())
+            .map(|text| text.0)
+            .unwrap_or_default();
+        let mut sections = split_ini_sections(&existing);
+        let entries = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), format_ini_value(v)))
+            .collect();
+        if let Some(existing_section) = sections
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(section))
+        {
+            existing_section.1 = entries;
+        } else {
+            sections.push((section.to_owned(), entries));
+        }
+        let mut text = String::new();
+        for (name, entries) in sections {
+            text += &format!("[{}]\r\n", name);
+            for (key, value) in entries {
+                text += &format!("{}={}\r\n", key, value);
+            }
+        }
+        let data = DecodedStr(text, None)
+            .to_bytes()
+            .map_err(|_| RunnerError::CouldNotLoadFile(filename.to_owned()))?;
+        filesystem
+            .write()
+            .unwrap()
+            .write_scene_asset(Arc::clone(&script.runner.game_paths), &path, &data)
+            .map_err(|e| RunnerError::IoError { source: e })?;
+        Ok(())
     }
 
     pub fn send_on_change(&mut self) -> anyhow::Result<()> {
@@ -670,9 +828,12 @@ impl ArrayState {
         todo!()
     }
 
-    pub fn sub_at(&mut self) -> anyhow::Result<()> {
+    pub fn sub_at(&mut self, index: i32, value: &CnvValue) -> anyhow::Result<()> {
         // SUBAT
-        todo!()
+        if let Some(element) = self.get_element_mut(index) {
+            *element = &*element - value;
+        }
+        Ok(())
     }
 
     pub fn sum(&mut self) -> anyhow::Result<()> {
@@ -689,4 +850,56 @@ impl ArrayState {
         // SWAP
         todo!()
     }
+
+    // custom
+
+    /// Returns the element at the given index, clamped to the bounds of the array.
+    fn get_element_mut(&mut self, index: i32) -> Option<&mut CnvValue> {
+        let last_index = self.values.len().checked_sub(1)?;
+        self.values.get_mut((index.max(0) as usize).min(last_index))
+    }
+}
+
+type IniSection = (String, Vec<(String, String)>);
+
+fn split_ini_sections(text: &str) -> Vec<IniSection> {
+    let mut sections: Vec<IniSection> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((name.trim().to_owned(), Vec::new()));
+        } else if let Some((key, value)) = line.split_once('=') {
+            if let Some((_, entries)) = sections.last_mut() {
+                entries.push((key.trim().to_owned(), value.trim().to_owned()));
+            }
+        }
+    }
+    sections
+}
+
+fn parse_ini_value(value: &str) -> CnvValue {
+    if let Some(s) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        CnvValue::String(s.to_owned())
+    } else if let Ok(i) = value.parse::<i32>() {
+        CnvValue::Integer(i)
+    } else if let Ok(d) = value.parse::<f64>() {
+        CnvValue::Double(d)
+    } else if value.eq_ignore_ascii_case("TRUE") {
+        CnvValue::Bool(true)
+    } else if value.eq_ignore_ascii_case("FALSE") {
+        CnvValue::Bool(false)
+    } else {
+        CnvValue::String(value.to_owned())
+    }
+}
+
+fn format_ini_value(value: &CnvValue) -> String {
+    match value {
+        CnvValue::Double(d) => format!("{:?}", d),
+        CnvValue::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_owned(),
+        CnvValue::String(s) if !matches!(parse_ini_value(s), CnvValue::String(ref parsed) if parsed == s) =>
+        {
+            format!("\"{}\"", s)
+        }
+        _ => value.to_str(),
+    }
 }
@@ -1,14 +1,10 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler, parse_program};
+use super::super::parsers::{discard_if_empty, parse_event_handlers, parse_program};
 
-use crate::{
-    common::DroppableRefMut,
-    parser::ast::ParsedScript,
-    runner::{CnvExpression, InternalEvent},
-};
+use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
 use super::super::common::*;
 use super::super::*;
@@ -20,8 +16,8 @@ pub struct BehaviorProperties {
     pub code: Option<Arc<ParsedScript>>,  // CODE
     pub condition: Option<ConditionName>, // CONDITION
 
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
@@ -33,19 +29,17 @@ struct BehaviorState {
 
 #[derive(Debug, Clone)]
 pub struct BehaviorEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for BehaviorEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -78,6 +72,13 @@ impl Behavior {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn run(
         &self,
         context: RunnerContext,
@@ -138,27 +139,24 @@ impl CnvType for Behavior {
                     self.state
                         .borrow()
                         .run(context, code.clone(), arguments.to_owned())
-                        .map(|_| CnvValue::Null)
                 } else {
                     Ok(CnvValue::Null)
                 }
             }
             CallableIdentifier::Method("RUNC") => {
                 if let Some(code) = self.code.as_ref() {
-                    self.state
-                        .borrow()
-                        .run_c(
-                            context,
-                            code.clone(),
-                            self.condition.as_deref(),
-                            arguments.to_owned(),
-                        )
-                        .map(|_| CnvValue::Null)
+                    self.state.borrow().run_c(
+                        context,
+                        code.clone(),
+                        self.condition.as_deref(),
+                        arguments.to_owned(),
+                    )
                 } else {
                     Ok(CnvValue::Null)
                 }
             }
             CallableIdentifier::Method("RUNLOOPED") => {
+                check_argument_count(arguments, 2..)?;
                 if let Some(code) = self.code.as_ref() {
                     self.state
                         .borrow()
@@ -202,24 +200,9 @@ impl CnvType for Behavior {
             .map(parse_program)
             .transpose()?;
         let condition = properties.remove("CONDITION").and_then(discard_if_empty);
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         properties.retain(|k, _| k != "ONSIGNAL" && !k.starts_with("ONSIGNAL^"));
         Ok(CnvContent::Behavior(Behavior::from_initial_properties(
             parent,
@@ -274,8 +257,7 @@ impl BehaviorState {
         //     context.current_object.name,
         //     arguments.iter().join(", ")
         // );
-        let context = context.with_arguments(arguments);
-        code.calculate(context)
+        calculate_script(&code, context.with_arguments(arguments))
     }
 
     pub fn disable(&mut self) -> anyhow::Result<()> {
@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -20,8 +20,8 @@ pub struct BoolVarProperties {
 
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -37,8 +37,8 @@ struct BoolVarState {
 struct BoolVarEventHandlers {
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -46,20 +46,12 @@ struct BoolVarEventHandlers {
 impl EventHandler for BoolVarEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONBRUTALCHANGED" => argument
-                .and_then(|a| self.on_brutal_changed.get(a))
-                .or(self.on_brutal_changed.get("")),
-            "ONCHANGED" => argument
-                .and_then(|a| self.on_changed.get(a))
-                .or(self.on_changed.get("")),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONNETCHANGED" => argument
-                .and_then(|a| self.on_net_changed.get(a))
-                .or(self.on_net_changed.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONBRUTALCHANGED" => get_matching_handler(&self.on_brutal_changed, argument),
+            "ONCHANGED" => get_matching_handler(&self.on_changed, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONNETCHANGED" => get_matching_handler(&self.on_net_changed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -98,6 +90,13 @@ impl BoolVar {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn get(&self) -> anyhow::Result<bool> {
         self.state.borrow().get()
     }
@@ -123,11 +122,13 @@ impl CnvType for BoolVar {
         context: RunnerContext,
     ) -> anyhow::Result<CnvValue> {
         match name {
-            CallableIdentifier::Method("AND") => self
-                .state
-                .borrow_mut()
-                .and(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("AND") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .and(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("CLEAR") => self
                 .state
                 .borrow_mut()
@@ -148,11 +149,13 @@ impl CnvType for BoolVar {
             CallableIdentifier::Method("NOT") => {
                 self.state.borrow_mut().not(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("OR") => self
-                .state
-                .borrow_mut()
-                .or(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("OR") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .or(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("RANDOM") => self
                 .state
                 .borrow_mut()
@@ -163,26 +166,32 @@ impl CnvType for BoolVar {
                 .borrow_mut()
                 .reset_ini(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SET") => self
-                .state
-                .borrow_mut()
-                .set(context, arguments[0].to_bool())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETDEFAULT") => self
-                .state
-                .borrow_mut()
-                .set_default(context, arguments[0].to_bool())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SET") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(context, arguments[0].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETDEFAULT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_default(context, arguments[0].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SWITCH") => self
                 .state
                 .borrow_mut()
                 .switch(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("XOR") => self
-                .state
-                .borrow_mut()
-                .xor(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("XOR") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .xor(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -225,49 +234,12 @@ impl CnvType for BoolVar {
             .and_then(discard_if_empty)
             .map(parse_bool)
             .transpose()?;
-        let mut on_brutal_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONBRUTALCHANGED" {
-                on_brutal_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONBRUTALCHANGED^") {
-                on_brutal_changed
-                    .insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCHANGED" {
-                on_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCHANGED^") {
-                on_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_net_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONNETCHANGED" {
-                on_net_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONNETCHANGED^") {
-                on_net_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_brutal_changed = parse_event_handlers(&properties, "ONBRUTALCHANGED")?;
+        let on_changed = parse_event_handlers(&properties, "ONCHANGED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_net_changed = parse_event_handlers(&properties, "ONNETCHANGED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Bool(Self::from_initial_properties(
             parent,
             BoolVarProperties {
@@ -1,9 +1,9 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
 use super::super::parsers::{
-    discard_if_empty, parse_bool, parse_event_handler, parse_i32, parse_rect, ReferenceRect,
+    discard_if_empty, parse_bool, parse_event_handlers, parse_i32, parse_rect, ReferenceRect,
 };
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
@@ -28,18 +28,18 @@ pub struct ButtonProperties {
     pub snd_on_move: Option<SoundName>,  // SNDONMOVE
     pub snd_standard: Option<SoundName>, // SNDSTANDARD
 
-    pub on_action: Option<Arc<ParsedScript>>, // ONACTION signal
-    pub on_clicked: Option<Arc<ParsedScript>>, // ONCLICKED signal
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_dragging: Option<Arc<ParsedScript>>, // ONDRAGGING signal
-    pub on_end_dragging: Option<Arc<ParsedScript>>, // ONENDDRAGGING signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>, // ONFOCUSON signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_paused: Option<Arc<ParsedScript>>, // ONPAUSED signal
-    pub on_released: Option<Arc<ParsedScript>>, // ONRELEASED signal
+    pub on_action: HashMap<String, Arc<ParsedScript>>, // ONACTION signal
+    pub on_clicked: HashMap<String, Arc<ParsedScript>>, // ONCLICKED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,   // ONDONE signal
+    pub on_dragging: HashMap<String, Arc<ParsedScript>>, // ONDRAGGING signal
+    pub on_end_dragging: HashMap<String, Arc<ParsedScript>>, // ONENDDRAGGING signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,   // ONINIT signal
+    pub on_paused: HashMap<String, Arc<ParsedScript>>, // ONPAUSED signal
+    pub on_released: HashMap<String, Arc<ParsedScript>>, // ONRELEASED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
-    pub on_start_dragging: Option<Arc<ParsedScript>>, // ONSTARTDRAGGING signal
+    pub on_start_dragging: HashMap<String, Arc<ParsedScript>>, // ONSTARTDRAGGING signal
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -51,10 +51,24 @@ pub enum Interaction {
     Pressing,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnableState {
+    #[default]
+    Enabled,
+    Disabled,
+    DisabledButVisible,
+}
+
+impl EnableState {
+    pub fn is_enabled(&self) -> bool {
+        *self == Self::Enabled
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ButtonState {
     // initialized from properties
-    pub is_enabled: bool,
+    pub enable_state: EnableState,
     pub is_accented: bool,
     pub is_draggable: bool,
     pub graphics_normal: Option<String>,
@@ -68,37 +82,35 @@ pub struct ButtonState {
 
 #[derive(Debug, Clone)]
 pub struct ButtonEventHandlers {
-    pub on_action: Option<Arc<ParsedScript>>,  // ONACTION signal
-    pub on_clicked: Option<Arc<ParsedScript>>, // ONCLICKED signal
-    pub on_done: Option<Arc<ParsedScript>>,    // ONDONE signal
-    pub on_dragging: Option<Arc<ParsedScript>>, // ONDRAGGING signal
-    pub on_end_dragging: Option<Arc<ParsedScript>>, // ONENDDRAGGING signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>, // ONFOCUSON signal
-    pub on_init: Option<Arc<ParsedScript>>,    // ONINIT signal
-    pub on_paused: Option<Arc<ParsedScript>>,  // ONPAUSED signal
-    pub on_released: Option<Arc<ParsedScript>>, // ONRELEASED signal
+    pub on_action: HashMap<String, Arc<ParsedScript>>, // ONACTION signal
+    pub on_clicked: HashMap<String, Arc<ParsedScript>>, // ONCLICKED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,   // ONDONE signal
+    pub on_dragging: HashMap<String, Arc<ParsedScript>>, // ONDRAGGING signal
+    pub on_end_dragging: HashMap<String, Arc<ParsedScript>>, // ONENDDRAGGING signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,   // ONINIT signal
+    pub on_paused: HashMap<String, Arc<ParsedScript>>, // ONPAUSED signal
+    pub on_released: HashMap<String, Arc<ParsedScript>>, // ONRELEASED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
-    pub on_start_dragging: Option<Arc<ParsedScript>>, // ONSTARTDRAGGING signal
+    pub on_start_dragging: HashMap<String, Arc<ParsedScript>>, // ONSTARTDRAGGING signal
 }
 
 impl EventHandler for ButtonEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONACTION" => self.on_action.as_ref(),
-            "ONCLICKED" => self.on_clicked.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONDRAGGING" => self.on_dragging.as_ref(),
-            "ONENDDRAGGING" => self.on_end_dragging.as_ref(),
-            "ONFOCUSOFF" => self.on_focus_off.as_ref(),
-            "ONFOCUSON" => self.on_focus_on.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONPAUSED" => self.on_paused.as_ref(),
-            "ONRELEASED" => self.on_released.as_ref(),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
-            "ONSTARTDRAGGING" => self.on_start_dragging.as_ref(),
+            "ONACTION" => get_matching_handler(&self.on_action, argument),
+            "ONCLICKED" => get_matching_handler(&self.on_clicked, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONDRAGGING" => get_matching_handler(&self.on_dragging, argument),
+            "ONENDDRAGGING" => get_matching_handler(&self.on_end_dragging, argument),
+            "ONFOCUSOFF" => get_matching_handler(&self.on_focus_off, argument),
+            "ONFOCUSON" => get_matching_handler(&self.on_focus_on, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONPAUSED" => get_matching_handler(&self.on_paused, argument),
+            "ONRELEASED" => get_matching_handler(&self.on_released, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONSTARTDRAGGING" => get_matching_handler(&self.on_start_dragging, argument),
             _ => None,
         }
     }
@@ -119,11 +131,15 @@ pub struct Button {
 
 impl Button {
     pub fn from_initial_properties(parent: Arc<CnvObject>, props: ButtonProperties) -> Self {
-        let is_enabled = props.enable.unwrap_or(true);
+        let enable_state = if props.enable.unwrap_or(true) {
+            EnableState::Enabled
+        } else {
+            EnableState::Disabled
+        };
         Self {
             parent,
             state: RefCell::new(ButtonState {
-                is_enabled,
+                enable_state,
                 is_accented: props.accent.unwrap_or_default(),
                 is_draggable: props.draggable.unwrap_or_default(),
                 graphics_normal: props.gfx_standard,
@@ -153,11 +169,18 @@ impl Button {
             sound_on_click: props.snd_on_click,
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl GeneralButton for Button {
     fn is_enabled(&self) -> anyhow::Result<bool> {
-        Ok(self.state.borrow().is_enabled)
+        Ok(self.state.borrow().enable_state.is_enabled())
     }
 
     fn get_rect(&self) -> anyhow::Result<Option<Rect>> {
@@ -284,33 +307,48 @@ impl CnvType for Button {
                 .borrow()
                 .get_std()
                 .map(|v| v.map(CnvValue::String).unwrap_or_default()),
-            CallableIdentifier::Method("SETONCLICK") => self
-                .state
-                .borrow_mut()
-                .set_on_click(&arguments[0].to_string())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETONMOVE") => self
-                .state
-                .borrow_mut()
-                .set_on_move(&arguments[0].to_string())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_priority(arguments[0].to_int() as isize)
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETONCLICK") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_on_click(&arguments[0].to_string())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETONMOVE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_on_move(&arguments[0].to_string())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETRECT") => {
-                let rect = parse_rect(arguments[0].to_str())?;
+                check_argument_count(arguments, 1..)?;
+                let rect = if arguments.len() >= 4 {
+                    let [left, top, right, bottom] =
+                        [0, 1, 2, 3].map(|i| arguments[i].to_int() as isize);
+                    ReferenceRect::Literal((left, top, right, bottom).into())
+                } else {
+                    parse_rect(arguments[0].to_str())?
+                };
                 self.state
                     .borrow_mut()
                     .set_rect(rect)
                     .map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SETSTD") => self
-                .state
-                .borrow_mut()
-                .set_std(&arguments[0].to_string())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETSTD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_std(&arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SYN") => {
                 self.state.borrow_mut().syn().map(|_| CnvValue::Null)
             }
@@ -372,69 +410,18 @@ impl CnvType for Button {
         let snd_on_click = properties.remove("SNDONCLICK").and_then(discard_if_empty);
         let snd_on_move = properties.remove("SNDONMOVE").and_then(discard_if_empty);
         let snd_standard = properties.remove("SNDSTANDARD").and_then(discard_if_empty);
-        let on_action = properties
-            .remove("ONACTION")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_clicked = properties
-            .remove("ONCLICKED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_dragging = properties
-            .remove("ONDRAGGING")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_end_dragging = properties
-            .remove("ONENDDRAGGING")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_focus_off = properties
-            .remove("ONFOCUSOFF")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_focus_on = properties
-            .remove("ONFOCUSON")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_paused = properties
-            .remove("ONPAUSED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_released = properties
-            .remove("ONRELEASED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_start_dragging = properties
-            .remove("ONSTARTDRAGGING")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_action = parse_event_handlers(&properties, "ONACTION")?;
+        let on_clicked = parse_event_handlers(&properties, "ONCLICKED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_dragging = parse_event_handlers(&properties, "ONDRAGGING")?;
+        let on_end_dragging = parse_event_handlers(&properties, "ONENDDRAGGING")?;
+        let on_focus_off = parse_event_handlers(&properties, "ONFOCUSOFF")?;
+        let on_focus_on = parse_event_handlers(&properties, "ONFOCUSON")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_paused = parse_event_handlers(&properties, "ONPAUSED")?;
+        let on_released = parse_event_handlers(&properties, "ONRELEASED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_start_dragging = parse_event_handlers(&properties, "ONSTARTDRAGGING")?;
         Ok(CnvContent::Button(Button::from_initial_properties(
             parent,
             ButtonProperties {
@@ -473,7 +460,7 @@ impl Initable for Button {
             .borrow_mut()
             .use_and_drop_mut(|state| -> anyhow::Result<()> {
                 state.set_interaction(context.clone(), Interaction::Hidden)?;
-                if state.is_enabled {
+                if state.enable_state != EnableState::Disabled {
                     state.set_interaction(context.clone(), Interaction::None)?;
                 }
                 Ok(())
@@ -500,13 +487,13 @@ impl ButtonState {
 
     pub fn disable(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // DISABLE
-        self.is_enabled = false;
+        self.enable_state = EnableState::Disabled;
         self.set_interaction(context, Interaction::Hidden)
     }
 
     pub fn disable_but_visible(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // DISABLEBUTVISIBLE
-        self.is_enabled = false;
+        self.enable_state = EnableState::DisabledButVisible;
         self.set_interaction(context, Interaction::None)
     }
 
@@ -517,10 +504,10 @@ impl ButtonState {
 
     pub fn enable(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // ENABLE
-        if self.is_enabled {
+        if self.enable_state.is_enabled() {
             return Ok(());
         }
-        self.is_enabled = true;
+        self.enable_state = EnableState::Enabled;
         self.set_interaction(context, Interaction::None)
     }
 
@@ -782,10 +769,10 @@ impl ButtonState {
                 .use_and_drop_mut(|events| {
                     events.push_back(InternalEvent {
                         context: context.clone().with_arguments(Vec::new()),
-                        callable: CallableIdentifier::Event("RELEASED").to_owned(),
+                        callable: CallableIdentifier::Event("ONRELEASED").to_owned(),
                     })
                 });
-        } else if interaction == Interaction::Pressing {
+        } else if self.current_interaction == Interaction::Pressing {
             context
                 .runner
                 .internal_events
@@ -793,7 +780,7 @@ impl ButtonState {
                 .use_and_drop_mut(|events| {
                     events.push_back(InternalEvent {
                         context: context.clone().with_arguments(Vec::new()),
-                        callable: CallableIdentifier::Event("CLICKED").to_owned(),
+                        callable: CallableIdentifier::Event("ONCLICKED").to_owned(),
                     })
                 });
         }
@@ -805,7 +792,7 @@ impl ButtonState {
         context: RunnerContext,
         interaction: Interaction,
     ) -> anyhow::Result<()> {
-        if !self.is_enabled {
+        if !self.enable_state.is_enabled() {
             return Ok(());
         }
         if self.current_interaction == Interaction::Hidden || interaction == Interaction::Hidden {
@@ -5,9 +5,9 @@ use std::any::Any;
 use std::sync::RwLock;
 use xxhash_rust::xxh3::xxh3_64;
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler};
+use super::super::parsers::parse_event_handlers;
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -18,15 +18,15 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct CanvasObserverProperties {
     // CANVAS_OBSERVER
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
-    pub on_initial_update: Option<Arc<ParsedScript>>, // ONINITIALUPDATE signal
-    pub on_initial_updated: Option<Arc<ParsedScript>>, // ONINITIALUPDATED signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
-    pub on_update: Option<Arc<ParsedScript>>, // ONUPDATE signal
-    pub on_updated: Option<Arc<ParsedScript>>, // ONUPDATED signal
-    pub on_window_focus_off: Option<Arc<ParsedScript>>, // ONWINDOWFOCUSOFF signal
-    pub on_window_focus_on: Option<Arc<ParsedScript>>, // ONWINDOWFOCUSON signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_initial_update: HashMap<String, Arc<ParsedScript>>, // ONINITIALUPDATE signal
+    pub on_initial_updated: HashMap<String, Arc<ParsedScript>>, // ONINITIALUPDATED signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_update: HashMap<String, Arc<ParsedScript>>, // ONUPDATE signal
+    pub on_updated: HashMap<String, Arc<ParsedScript>>, // ONUPDATED signal
+    pub on_window_focus_off: HashMap<String, Arc<ParsedScript>>, // ONWINDOWFOCUSOFF signal
+    pub on_window_focus_on: HashMap<String, Arc<ParsedScript>>, // ONWINDOWFOCUSON signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -39,29 +39,29 @@ struct CanvasObserverState {
 
 #[derive(Debug, Clone)]
 pub struct CanvasObserverEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
-    pub on_initial_update: Option<Arc<ParsedScript>>, // ONINITIALUPDATE signal
-    pub on_initial_updated: Option<Arc<ParsedScript>>, // ONINITIALUPDATED signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
-    pub on_update: Option<Arc<ParsedScript>>, // ONUPDATE signal
-    pub on_updated: Option<Arc<ParsedScript>>, // ONUPDATED signal
-    pub on_window_focus_off: Option<Arc<ParsedScript>>, // ONWINDOWFOCUSOFF signal
-    pub on_window_focus_on: Option<Arc<ParsedScript>>, // ONWINDOWFOCUSON signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_initial_update: HashMap<String, Arc<ParsedScript>>, // ONINITIALUPDATE signal
+    pub on_initial_updated: HashMap<String, Arc<ParsedScript>>, // ONINITIALUPDATED signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_update: HashMap<String, Arc<ParsedScript>>, // ONUPDATE signal
+    pub on_updated: HashMap<String, Arc<ParsedScript>>, // ONUPDATED signal
+    pub on_window_focus_off: HashMap<String, Arc<ParsedScript>>, // ONWINDOWFOCUSOFF signal
+    pub on_window_focus_on: HashMap<String, Arc<ParsedScript>>, // ONWINDOWFOCUSON signal
 }
 
 impl EventHandler for CanvasObserverEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONINITIALUPDATE" => self.on_initial_update.as_ref(),
-            "ONINITIALUPDATED" => self.on_initial_updated.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
-            "ONUPDATE" => self.on_update.as_ref(),
-            "ONUPDATED" => self.on_updated.as_ref(),
-            "ONWINDOWFOCUSOFF" => self.on_window_focus_off.as_ref(),
-            "ONWINDOWFOCUSON" => self.on_window_focus_on.as_ref(),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONINITIALUPDATE" => get_matching_handler(&self.on_initial_update, argument),
+            "ONINITIALUPDATED" => get_matching_handler(&self.on_initial_updated, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONUPDATE" => get_matching_handler(&self.on_update, argument),
+            "ONUPDATED" => get_matching_handler(&self.on_updated, argument),
+            "ONWINDOWFOCUSOFF" => get_matching_handler(&self.on_window_focus_off, argument),
+            "ONWINDOWFOCUSON" => get_matching_handler(&self.on_window_focus_on, argument),
             _ => None,
         }
     }
@@ -104,6 +104,20 @@ impl CanvasObserver {
         }
     }
 
+    /// Copies the object, attaching the copy to another object.
+    ///
+    /// The canvas observer state is global, so it stays shared between the copy and the original.
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    pub fn reset_state() {
+        *GLOBAL_CANVAS_OBSERVER_STATE.write().unwrap() = CanvasObserverState::default();
+    }
+
     pub fn set_background_data(&self, background_data: ImageFileData) -> anyhow::Result<()> {
         GLOBAL_CANVAS_OBSERVER_STATE
             .write()
@@ -131,6 +145,17 @@ impl CanvasObserver {
         );
         Ok(Some((rect, image.0.clone(), image.1.clone())))
     }
+
+    /// Composites graphics permanently onto the background.
+    ///
+    /// When no background is set, a transparent one covering the window is created first.
+    pub fn bake_graphics(&self, graphics_rect: Rect, pixel_data: &[u8]) -> anyhow::Result<()> {
+        let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
+        let window_rect = *context.runner.window_rect.borrow();
+        let mut state = GLOBAL_CANVAS_OBSERVER_STATE.write().unwrap();
+        state.load_background_if_needed(context)?;
+        state.bake_graphics(window_rect, graphics_rect, pixel_data)
+    }
 }
 
 impl CnvType for CanvasObserver {
@@ -190,30 +215,37 @@ impl CnvType for CanvasObserver {
             CallableIdentifier::Method("PASTE") => {
                 self.state.write().unwrap().paste().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("REDRAW") => {
-                self.state.write().unwrap().redraw().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("REFRESH") => self
-                .state
-                .write()
-                .unwrap()
-                .refresh()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("REMOVE") => {
-                self.state.write().unwrap().remove().map(|_| CnvValue::Null)
-            }
-            CallableIdentifier::Method("SAVE") => self
+            CallableIdentifier::Method("REDRAW") => self
                 .state
                 .write()
                 .unwrap()
-                .save(context, &arguments[0].to_str())
+                .redraw(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETBACKGROUND") => self
+            CallableIdentifier::Method("REFRESH") => self
                 .state
                 .write()
                 .unwrap()
-                .set_background(context, &arguments[0].to_str())
+                .refresh(context)
                 .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("REMOVE") => {
+                self.state.write().unwrap().remove().map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SAVE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .write()
+                    .unwrap()
+                    .save(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETBACKGROUND") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_background(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETBKGPOS") => self
                 .state
                 .write()
@@ -240,53 +272,17 @@ impl CnvType for CanvasObserver {
 
     fn new_content(
         parent: Arc<CnvObject>,
-        mut properties: HashMap<String, String>,
+        properties: HashMap<String, String>,
     ) -> Result<CnvContent, TypeParsingError> {
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_initial_update = properties
-            .remove("ONINITIALUPDATE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_initial_updated = properties
-            .remove("ONINITIALUPDATED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_update = properties
-            .remove("ONUPDATE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_updated = properties
-            .remove("ONUPDATED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_window_focus_off = properties
-            .remove("ONWINDOWFOCUSOFF")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_window_focus_on = properties
-            .remove("ONWINDOWFOCUSON")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_initial_update = parse_event_handlers(&properties, "ONINITIALUPDATE")?;
+        let on_initial_updated = parse_event_handlers(&properties, "ONINITIALUPDATED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_update = parse_event_handlers(&properties, "ONUPDATE")?;
+        let on_updated = parse_event_handlers(&properties, "ONUPDATED")?;
+        let on_window_focus_off = parse_event_handlers(&properties, "ONWINDOWFOCUSOFF")?;
+        let on_window_focus_on = parse_event_handlers(&properties, "ONWINDOWFOCUSON")?;
         Ok(CnvContent::CanvasObserver(Self::from_initial_properties(
             parent,
             CanvasObserverProperties {
@@ -356,14 +352,15 @@ impl CanvasObserverState {
         todo!()
     }
 
-    pub fn redraw(&mut self) -> anyhow::Result<()> {
+    pub fn redraw(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // REDRAW
-        todo!()
+        self.request_redraw(context);
+        Ok(())
     }
 
-    pub fn refresh(&mut self) -> anyhow::Result<()> {
+    pub fn refresh(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // REFRESH
-        warn!("Skipping CANVAS_OBSERVER^REFRESH() call");
+        self.request_redraw(context);
         Ok(())
     }
 
@@ -412,6 +409,8 @@ impl CanvasObserverState {
         let CnvContent::Image(image) = &object.content else {
             return Err(RunnerError::ExpectedGraphicsObject.into());
         };
+        // make sure the data is decoded, so later compositing does not depend on the current scene
+        image.get_image_to_show()?;
         self.background_data = image.get_file_data()?;
         Ok(())
     }
@@ -423,6 +422,15 @@ impl CanvasObserverState {
 
     // custom
 
+    fn request_redraw(&self, context: RunnerContext) {
+        context
+            .runner
+            .events_out
+            .graphics
+            .borrow_mut()
+            .use_and_drop_mut(|events| events.push_back(GraphicsEvent::CanvasRedrawRequested));
+    }
+
     pub fn load_background(
         &mut self,
         runner: &Arc<CnvRunner>,
@@ -458,6 +466,90 @@ impl CanvasObserverState {
         Ok(())
     }
 
+    fn bake_graphics(
+        &mut self,
+        window_rect: Rect,
+        graphics_rect: Rect,
+        pixel_data: &[u8],
+    ) -> anyhow::Result<()> {
+        let Some(graphics): Option<ImageBuffer<Rgba<u8>, Vec<u8>>> = ImageBuffer::from_raw(
+            graphics_rect.get_width() as u32,
+            graphics_rect.get_height() as u32,
+            pixel_data.to_owned(),
+        ) else {
+            warn!(
+                "Skipping baking graphics with mismatched pixel data for rect {:?}",
+                graphics_rect
+            );
+            return Ok(());
+        };
+        let (filename, background_rect, background) = match &self.background_data {
+            ImageFileData::Loaded(loaded_background) => {
+                let image = &loaded_background.image;
+                (
+                    loaded_background.filename.clone(),
+                    Rect::from(
+                        (image.0.offset_px.0 as isize, image.0.offset_px.1 as isize),
+                        (image.0.size_px.0 as usize, image.0.size_px.1 as usize),
+                    ),
+                    ImageBuffer::from_raw(
+                        image.0.size_px.0,
+                        image.0.size_px.1,
+                        (*image.1.data).clone(),
+                    ),
+                )
+            }
+            _ => (
+                None,
+                window_rect,
+                Some(ImageBuffer::new(
+                    window_rect.get_width() as u32,
+                    window_rect.get_height() as u32,
+                )),
+            ),
+        };
+        let Some(mut background): Option<ImageBuffer<Rgba<u8>, Vec<u8>>> = background else {
+            warn!("Skipping baking graphics onto background with mismatched pixel data");
+            return Ok(());
+        };
+        let Some(overlap) = graphics_rect.intersect(&background_rect) else {
+            return Ok(());
+        };
+        for y in overlap.top_left_y..overlap.bottom_right_y {
+            for x in overlap.top_left_x..overlap.bottom_right_x {
+                let pixel = graphics.get_pixel(
+                    (x - graphics_rect.top_left_x) as u32,
+                    (y - graphics_rect.top_left_y) as u32,
+                );
+                background
+                    .get_pixel_mut(
+                        (x - background_rect.top_left_x) as u32,
+                        (y - background_rect.top_left_y) as u32,
+                    )
+                    .blend(pixel);
+            }
+        }
+        let (width, height) = background.dimensions();
+        let data = background.into_raw();
+        self.background_data = ImageFileData::Loaded(LoadedImage {
+            filename,
+            image: (
+                ImageDefinition {
+                    size_px: (width, height),
+                    offset_px: (
+                        background_rect.top_left_x as i32,
+                        background_rect.top_left_y as i32,
+                    ),
+                },
+                ImageData {
+                    hash: xxh3_64(&data),
+                    data: Arc::new(data),
+                },
+            ),
+        });
+        Ok(())
+    }
+
     fn load_background_if_needed(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         if let ImageFileData::NotLoaded(filename) = &self.background_data {
             let Some(current_scene) = context.runner.get_current_scene() else {
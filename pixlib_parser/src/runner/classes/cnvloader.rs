@@ -46,6 +46,13 @@ impl CnvLoader {
             cnv_loader: props.cnv_loader.unwrap_or_default(),
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl CnvType for CnvLoader {
@@ -1,7 +1,7 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
-use super::super::parsers::{discard_if_empty, parse_event_handler, ComplexConditionOperator};
+use super::super::content::{get_matching_handler, EventHandler};
+use super::super::parsers::{discard_if_empty, parse_event_handlers, ComplexConditionOperator};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -16,8 +16,8 @@ pub struct ComplexConditionProperties {
     pub operand2: ConditionName,            // OPERAND2
     pub operator: ComplexConditionOperator, // OPERATOR
 
-    pub on_runtime_failed: Option<Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
-    pub on_runtime_success: Option<Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
+    pub on_runtime_failed: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
+    pub on_runtime_success: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -25,15 +25,15 @@ pub struct ComplexConditionState {}
 
 #[derive(Debug, Clone)]
 pub struct ComplexConditionEventHandlers {
-    pub on_runtime_failed: Option<Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
-    pub on_runtime_success: Option<Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
+    pub on_runtime_failed: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
+    pub on_runtime_success: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
 }
 
 impl EventHandler for ComplexConditionEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONRUNTIMEFAILED" => self.on_runtime_failed.as_ref(),
-            "ONRUNTIMESUCCESS" => self.on_runtime_success.as_ref(),
+            "ONRUNTIMEFAILED" => get_matching_handler(&self.on_runtime_failed, argument),
+            "ONRUNTIMESUCCESS" => get_matching_handler(&self.on_runtime_success, argument),
             _ => None,
         }
     }
@@ -68,6 +68,13 @@ impl ComplexCondition {
             right: props.operand2,
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl GeneralCondition for ComplexCondition {
@@ -148,16 +155,8 @@ impl CnvType for ComplexCondition {
             .map(ComplexConditionOperator::parse)
             .transpose()?
             .ok_or(TypeParsingError::MissingOperator)?;
-        let on_runtime_failed = properties
-            .remove("ONRUNTIMEFAILED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_runtime_success = properties
-            .remove("ONRUNTIMESUCCESS")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_runtime_failed = parse_event_handlers(&properties, "ONRUNTIMEFAILED")?;
+        let on_runtime_success = parse_event_handlers(&properties, "ONRUNTIMESUCCESS")?;
         Ok(CnvContent::ComplexCondition(Self::from_initial_properties(
             parent,
             ComplexConditionProperties {
@@ -182,13 +181,21 @@ impl ComplexConditionState {
         else {
             panic!();
         };
-        let left_object = context.runner.get_object(&complex_condition.left).unwrap();
+        let left_object = context.runner.get_object(&complex_condition.left).ok_or(
+            RunnerError::ObjectNotFound {
+                name: complex_condition.left.clone(),
+            },
+        )?;
         let left: &dyn GeneralCondition = match &left_object.content {
             CnvContent::Condition(c) => c,
             CnvContent::ComplexCondition(c) => c,
             _ => return Err(RunnerError::ExpectedConditionObject.into()),
         };
-        let right_object = context.runner.get_object(&complex_condition.right).unwrap();
+        let right_object = context.runner.get_object(&complex_condition.right).ok_or(
+            RunnerError::ObjectNotFound {
+                name: complex_condition.right.clone(),
+            },
+        )?;
         let right: &dyn GeneralCondition = match &right_object.content {
             CnvContent::Condition(c) => c,
             CnvContent::ComplexCondition(c) => c,
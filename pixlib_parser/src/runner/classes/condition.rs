@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::parsers::{
-    discard_if_empty, parse_event_handler, parse_program, ConditionOperator,
+    discard_if_empty, parse_event_handlers, parse_program, ConditionOperator,
 };
 
 use crate::{
@@ -22,23 +22,23 @@ pub struct ConditionProperties {
     pub operand2: Arc<ParsedScript>, // OPERAND2
     pub operator: ConditionOperator, // OPERATOR
 
-    pub on_runtime_failed: Option<Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
-    pub on_runtime_success: Option<Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
+    pub on_runtime_failed: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
+    pub on_runtime_success: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
 }
 #[derive(Debug, Clone, Default)]
 pub struct ConditionState {}
 
 #[derive(Debug, Clone)]
 pub struct ConditionEventHandlers {
-    pub on_runtime_failed: Option<Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
-    pub on_runtime_success: Option<Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
+    pub on_runtime_failed: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMEFAILED signal
+    pub on_runtime_success: HashMap<String, Arc<ParsedScript>>, // ONRUNTIMESUCCESS signal
 }
 
 impl EventHandler for ConditionEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONRUNTIMEFAILED" => self.on_runtime_failed.as_ref(),
-            "ONRUNTIMESUCCESS" => self.on_runtime_success.as_ref(),
+            "ONRUNTIMEFAILED" => get_matching_handler(&self.on_runtime_failed, argument),
+            "ONRUNTIMESUCCESS" => get_matching_handler(&self.on_runtime_success, argument),
             _ => None,
         }
     }
@@ -71,6 +71,13 @@ impl Condition {
             right: props.operand2,
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl GeneralCondition for Condition {
@@ -109,19 +116,23 @@ impl CnvType for Condition {
         //     name, self.parent.name
         // );
         match name {
-            CallableIdentifier::Method("BREAK") => self
-                .state
-                .borrow()
-                .break_run(context, arguments[0].to_bool())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("BREAK") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .break_run(context, arguments[0].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("CHECK") => {
                 self.state.borrow().check(context).map(CnvValue::Bool)
             }
-            CallableIdentifier::Method("ONE_BREAK") => self
-                .state
-                .borrow()
-                .one_break(context, arguments[0].to_bool())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("ONE_BREAK") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .one_break(context, arguments[0].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -162,16 +173,8 @@ impl CnvType for Condition {
             .map(ConditionOperator::parse)
             .transpose()?
             .ok_or(TypeParsingError::MissingOperator)?;
-        let on_runtime_failed = properties
-            .remove("ONRUNTIMEFAILED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_runtime_success = properties
-            .remove("ONRUNTIMESUCCESS")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_runtime_failed = parse_event_handlers(&properties, "ONRUNTIMEFAILED")?;
+        let on_runtime_success = parse_event_handlers(&properties, "ONRUNTIMESUCCESS")?;
         Ok(CnvContent::Condition(Condition::from_initial_properties(
             parent,
             ConditionProperties {
@@ -200,53 +203,34 @@ impl ConditionState {
         };
         let left = condition.left.calculate(context.clone())?;
         let left = if let ast::Expression::Identifier(_) = &condition.left.value {
-            left.resolve(context.clone())
+            left.resolve(context.clone())?
         } else {
             left
         };
         let right = condition.right.calculate(context.clone())?;
         let right = if let ast::Expression::Identifier(_) = &condition.right.value {
-            right.resolve(context.clone())
+            right.resolve(context.clone())?
         } else {
             right
         };
-        let result = match condition.operator {
-            ConditionOperator::Equal => Ok(left == right),
-            ConditionOperator::NotEqual => Ok(left != right),
-            ConditionOperator::Less => Ok(left.to_dbl() < right.to_dbl()), // TODO: handle integers
-            ConditionOperator::LessEqual => Ok(left.to_dbl() <= right.to_dbl()),
-            ConditionOperator::Greater => Ok(left.to_dbl() > right.to_dbl()),
-            ConditionOperator::GreaterEqual => Ok(left.to_dbl() >= right.to_dbl()),
-        };
+        let result = left.compare(&right, &condition.operator);
         let evt_context = context.clone().with_arguments(Vec::new());
-        match result {
-            Ok(false) => {
-                context
-                    .runner
-                    .internal_events
-                    .borrow_mut()
-                    .use_and_drop_mut(move |events| {
-                        events.push_back(InternalEvent {
-                            context: evt_context,
-                            callable: CallableIdentifier::Event("ONRUNTIMEFAILED").to_owned(),
-                        });
-                    });
-            }
-            Ok(true) => {
-                context
-                    .runner
-                    .internal_events
-                    .borrow_mut()
-                    .use_and_drop_mut(move |events| {
-                        events.push_back(InternalEvent {
-                            context: evt_context,
-                            callable: CallableIdentifier::Event("ONRUNTIMESUCCESS").to_owned(),
-                        });
-                    });
-            }
-            _ => {}
-        }
-        result
+        let event_name = if result {
+            "ONRUNTIMESUCCESS"
+        } else {
+            "ONRUNTIMEFAILED"
+        };
+        context
+            .runner
+            .internal_events
+            .borrow_mut()
+            .use_and_drop_mut(move |events| {
+                events.push_back(InternalEvent {
+                    context: evt_context,
+                    callable: CallableIdentifier::Event(event_name).to_owned(),
+                });
+            });
+        Ok(result)
     }
 
     pub fn one_break(&self, context: RunnerContext, _: bool) -> anyhow::Result<()> {
@@ -1,9 +1,9 @@
 use core::f64;
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler, parse_f64};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers, parse_f64};
 
 use crate::{
     common::DroppableRefMut,
@@ -25,8 +25,8 @@ pub struct DoubleVarProperties {
 
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -42,8 +42,8 @@ struct DoubleVarState {
 pub struct DoubleVarEventHandlers {
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -51,20 +51,12 @@ pub struct DoubleVarEventHandlers {
 impl EventHandler for DoubleVarEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONBRUTALCHANGED" => argument
-                .and_then(|a| self.on_brutal_changed.get(a))
-                .or(self.on_brutal_changed.get("")),
-            "ONCHANGED" => argument
-                .and_then(|a| self.on_changed.get(a))
-                .or(self.on_changed.get("")),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONNETCHANGED" => argument
-                .and_then(|a| self.on_net_changed.get(a))
-                .or(self.on_net_changed.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONBRUTALCHANGED" => get_matching_handler(&self.on_brutal_changed, argument),
+            "ONCHANGED" => get_matching_handler(&self.on_changed, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONNETCHANGED" => get_matching_handler(&self.on_net_changed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -103,6 +95,13 @@ impl DoubleVar {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn get(&self) -> anyhow::Result<f64> {
         self.state.borrow().get()
     }
@@ -128,31 +127,39 @@ impl CnvType for DoubleVar {
         context: RunnerContext,
     ) -> anyhow::Result<CnvValue> {
         match name {
-            CallableIdentifier::Method("ADD") => self
-                .state
-                .borrow_mut()
-                .add(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
-            CallableIdentifier::Method("ARCTAN") => self
-                .state
-                .borrow_mut()
-                .arc_tan(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
-            CallableIdentifier::Method("ARCTANEX") => self
-                .state
-                .borrow_mut()
-                .arc_tan_ex(
-                    context,
-                    arguments[0].to_dbl(),
-                    arguments[1].to_dbl(),
-                    arguments.get(2).map(|v| v.to_int()),
-                )
-                .map(CnvValue::Double),
-            CallableIdentifier::Method("CLAMP") => self
-                .state
-                .borrow_mut()
-                .clamp(context, arguments[0].to_dbl(), arguments[1].to_dbl())
-                .map(CnvValue::Double),
+            CallableIdentifier::Method("ADD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .add(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
+            CallableIdentifier::Method("ARCTAN") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .arc_tan(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
+            CallableIdentifier::Method("ARCTANEX") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .arc_tan_ex(
+                        context,
+                        arguments[0].to_dbl(),
+                        arguments[1].to_dbl(),
+                        arguments.get(2).map(|v| v.to_int()),
+                    )
+                    .map(CnvValue::Double)
+            }
+            CallableIdentifier::Method("CLAMP") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .clamp(context, arguments[0].to_dbl(), arguments[1].to_dbl())
+                    .map(CnvValue::Double)
+            }
             CallableIdentifier::Method("CLEAR") => self
                 .state
                 .borrow_mut()
@@ -163,74 +170,76 @@ impl CnvType for DoubleVar {
                 .borrow_mut()
                 .copy_file(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("COSINUS") => self
-                .state
-                .borrow_mut()
-                .cosinus(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
+            CallableIdentifier::Method("COSINUS") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .cosinus(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
             CallableIdentifier::Method("DEC") => {
                 self.state.borrow_mut().dec(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("DIV") => self
-                .state
-                .borrow_mut()
-                .div(context, arguments[0].to_dbl())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("DIV") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .div(context, arguments[0].to_dbl())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("GET") => self.state.borrow().get().map(CnvValue::Double),
             CallableIdentifier::Method("INC") => {
                 self.state.borrow_mut().inc(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("LENGTH") => self
-                .state
-                .borrow_mut()
-                .length(context, arguments[0].to_dbl(), arguments[1].to_dbl())
-                .map(CnvValue::Double),
-            CallableIdentifier::Method("LOG") => self
-                .state
-                .borrow_mut()
-                .log(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
+            CallableIdentifier::Method("LENGTH") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .length(context, arguments[0].to_dbl(), arguments[1].to_dbl())
+                    .map(CnvValue::Double)
+            }
+            CallableIdentifier::Method("LOG") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .log(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
             CallableIdentifier::Method("MAXA") => {
-                if arguments.is_empty() {
-                    return Err(RunnerError::TooFewArguments {
-                        expected_min: 1,
-                        actual: 0,
-                    }
-                    .into());
-                }
+                check_argument_count(arguments, 1..)?;
                 self.state
                     .borrow_mut()
                     .max_a(context, arguments.iter().map(|v| v.to_dbl()))
                     .map(CnvValue::Double)
             }
             CallableIdentifier::Method("MINA") => {
-                if arguments.is_empty() {
-                    return Err(RunnerError::TooFewArguments {
-                        expected_min: 1,
-                        actual: 0,
-                    }
-                    .into());
-                }
+                check_argument_count(arguments, 1..)?;
                 self.state
                     .borrow_mut()
                     .min_a(context, arguments.iter().map(|v| v.to_dbl()))
                     .map(CnvValue::Double)
             }
-            CallableIdentifier::Method("MOD") => self
-                .state
-                .borrow_mut()
-                .modulus(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("MUL") => self
-                .state
-                .borrow_mut()
-                .mul(context, arguments[0].to_dbl())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("POWER") => self
-                .state
-                .borrow_mut()
-                .power(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
+            CallableIdentifier::Method("MOD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .modulus(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("MUL") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .mul(context, arguments[0].to_dbl())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("POWER") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .power(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
             CallableIdentifier::Method("RANDOM") => self
                 .state
                 .borrow_mut()
@@ -246,35 +255,45 @@ impl CnvType for DoubleVar {
                 .borrow_mut()
                 .round(context)
                 .map(CnvValue::Integer),
-            CallableIdentifier::Method("SET") => self
-                .state
-                .borrow_mut()
-                .set(context, arguments[0].to_dbl())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETDEFAULT") => self
-                .state
-                .borrow_mut()
-                .set_default(context, arguments[0].to_dbl())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SET") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(context, arguments[0].to_dbl())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETDEFAULT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_default(context, arguments[0].to_dbl())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SGN") => self.state.borrow().sgn().map(CnvValue::Integer),
-            CallableIdentifier::Method("SINUS") => self
-                .state
-                .borrow_mut()
-                .sinus(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
+            CallableIdentifier::Method("SINUS") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .sinus(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
             CallableIdentifier::Method("SQRT") => {
                 self.state.borrow_mut().sqrt(context).map(CnvValue::Double)
             }
-            CallableIdentifier::Method("SUB") => self
-                .state
-                .borrow_mut()
-                .sub(context, arguments[0].to_dbl())
-                .map(CnvValue::Double),
-            CallableIdentifier::Method("SWITCH") => self
-                .state
-                .borrow_mut()
-                .switch(context, arguments[0].to_dbl(), arguments[1].to_dbl())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SUB") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .sub(context, arguments[0].to_dbl())
+                    .map(CnvValue::Double)
+            }
+            CallableIdentifier::Method("SWITCH") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .switch(context, arguments[0].to_dbl(), arguments[1].to_dbl())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -317,49 +336,12 @@ impl CnvType for DoubleVar {
             .and_then(discard_if_empty)
             .map(parse_f64)
             .transpose()?;
-        let mut on_brutal_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONBRUTALCHANGED" {
-                on_brutal_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONBRUTALCHANGED^") {
-                on_brutal_changed
-                    .insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCHANGED" {
-                on_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCHANGED^") {
-                on_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_net_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONNETCHANGED" {
-                on_net_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONNETCHANGED^") {
-                on_net_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_brutal_changed = parse_event_handlers(&properties, "ONBRUTALCHANGED")?;
+        let on_changed = parse_event_handlers(&properties, "ONCHANGED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_net_changed = parse_event_handlers(&properties, "ONNETCHANGED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Double(DoubleVar::from_initial_properties(
             parent,
             DoubleVarProperties {
@@ -428,6 +410,7 @@ impl DoubleVarState {
 
     pub fn clamp(&mut self, context: RunnerContext, min: f64, max: f64) -> anyhow::Result<f64> {
         // CLAMP
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
         self.change_value(context, self.value.clamp(min, max));
         Ok(self.value)
     }
@@ -457,6 +440,12 @@ impl DoubleVarState {
 
     pub fn div(&mut self, context: RunnerContext, divisor: f64) -> anyhow::Result<()> {
         // DIV
+        if divisor == 0.0 {
+            return Err(RunnerError::DivisionByZero {
+                object_name: context.current_object.name.clone(),
+            }
+            .into());
+        }
         self.change_value(context, self.value / divisor);
         Ok(())
     }
@@ -506,7 +495,14 @@ impl DoubleVarState {
 
     pub fn modulus(&mut self, context: RunnerContext, divisor: i32) -> anyhow::Result<()> {
         // MOD
-        self.change_value(context, (self.value as i32 % divisor) as f64);
+        if divisor == 0 {
+            return Err(RunnerError::DivisionByZero {
+                object_name: context.current_object.name.clone(),
+            }
+            .into());
+        }
+        // the cast saturates out-of-range values, while wrapping_rem covers i32::MIN % -1
+        self.change_value(context, (self.value as i32).wrapping_rem(divisor) as f64);
         Ok(())
     }
 
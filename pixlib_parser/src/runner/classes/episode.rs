@@ -76,6 +76,13 @@ impl Episode {
         episode
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
     pub fn get_script_path(&self) -> Option<String> {
@@ -127,14 +134,23 @@ impl CnvType for Episode {
                 .borrow()
                 .get_latest_scene()
                 .map(|v| v.map(CnvValue::String).unwrap_or_default()),
-            CallableIdentifier::Method("GOTO") => self
-                .state
-                .borrow_mut()
-                .go_to(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("GOTO") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .go_to(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("NEXT") => {
                 self.state.borrow_mut().next().map(|_| CnvValue::Null)
             }
+            CallableIdentifier::Method("PRELOADSCENE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .preload_scene(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("PREV") => {
                 self.state.borrow_mut().prev().map(|_| CnvValue::Null)
             }
@@ -233,6 +249,11 @@ impl EpisodeState {
         todo!()
     }
 
+    pub fn preload_scene(&self, context: RunnerContext, scene_name: &str) -> anyhow::Result<()> {
+        // PRELOADSCENE (STRING scene)
+        context.runner.cache_scene_script(scene_name)
+    }
+
     pub fn prev(&mut self) -> anyhow::Result<()> {
         // PREV
         todo!()
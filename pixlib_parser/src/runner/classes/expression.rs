@@ -56,19 +56,26 @@ impl Expression {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
     pub fn calculate(&self) -> anyhow::Result<CnvValue> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
         let left = self.left.calculate(context.clone())?;
         let left = if let ast::Expression::Identifier(_) = &self.left.value {
-            left.resolve(context.clone())
+            left.resolve(context.clone())?
         } else {
             left
         };
         let right = self.right.calculate(context.clone())?;
         let right = if let ast::Expression::Identifier(_) = &self.right.value {
-            right.resolve(context.clone())
+            right.resolve(context.clone())?
         } else {
             right
         };
@@ -1,10 +1,16 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler, FontDef};
+use super::super::parsers::{parse_event_handlers, FontDef};
+use pixlib_formats::file_formats::ann::parse_ann;
+use xxhash_rust::xxh3::xxh3_64;
 
-use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
+use crate::{
+    common::DroppableRefMut,
+    parser::ast::ParsedScript,
+    runner::{InternalEvent, RunnerError},
+};
 
 use super::super::common::*;
 use super::super::*;
@@ -15,9 +21,9 @@ pub struct FontProperties {
     // FONT
     pub defs: HashMap<FontDef, Option<String>>,
 
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -27,21 +33,24 @@ struct FontState {
     pub family: String,
     pub size: usize,
     pub style: String,
+
+    // more temporary
+    pub loaded_font: Option<(FontDef, Arc<LoadedFont>)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FontEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for FontEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -72,6 +81,85 @@ impl Font {
             font_definitions: props.defs,
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.font_definitions
+            .iter()
+            .sorted_by_key(|(d, _)| *d)
+            .filter_map(|(_, f)| f.clone())
+            .collect()
+    }
+
+    /// Drops the loaded glyphs if they come from the given file, so they are loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(referenced) = state
+            .loaded_font
+            .as_ref()
+            .and_then(|(d, _)| self.font_definitions.get(d).cloned().flatten())
+        else {
+            return false;
+        };
+        let script = self.parent.parent.as_ref();
+        let resolved_paths = script
+            .runner
+            .game_paths
+            .get_scene_asset_paths(&script.path.with_file_path(&referenced));
+        if !is_same_file(&resolved_paths, filename) {
+            return false;
+        }
+        state.loaded_font = None;
+        true
+    }
+
+    /// Returns the width and height of the text block in pixels.
+    pub fn measure(&self, text: &str) -> anyhow::Result<(usize, usize)> {
+        let lines = self.measure_lines(text)?;
+        Ok((
+            lines.iter().map(|(w, _)| *w).max().unwrap_or_default(),
+            lines.iter().map(|(_, h)| *h).sum(),
+        ))
+    }
+
+    /// Returns the width and height of every line of the text in pixels.
+    pub fn measure_lines(&self, text: &str) -> anyhow::Result<Vec<(usize, usize)>> {
+        self.with_loaded_font(|font| font.measure_lines(text))
+    }
+
+    pub fn get_line_height(&self) -> anyhow::Result<usize> {
+        self.with_loaded_font(|font| font.get_line_height())
+    }
+
+    /// Breaks the text into lines fitting the given width, see [`LoadedFont::wrap_lines`].
+    pub fn wrap_lines(&self, text: &str, max_width: usize) -> anyhow::Result<Vec<String>> {
+        self.with_loaded_font(|font| font.wrap_lines(text, max_width))
+    }
+
+    /// Draws the text with the currently loaded font, see [`LoadedFont::render`].
+    pub fn render(&self, text: &str, color: Option<(u8, u8, u8)>) -> anyhow::Result<RenderedText> {
+        self.with_loaded_font(|font| font.render(text, color))
+    }
+
+    fn with_loaded_font<T>(&self, f: impl FnOnce(&LoadedFont) -> T) -> anyhow::Result<T> {
+        let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
+        self.state
+            .borrow_mut()
+            .use_and_drop_mut(|s| s.load_if_needed(context, &self.font_definitions))?;
+        let state = self.state.borrow();
+        let Some((_, ref font)) = state.loaded_font else {
+            return Err(RunnerError::NoFontDataLoaded(self.parent.name.clone()).into());
+        };
+        Ok(f(font))
+    }
 }
 
 lazy_static! {
@@ -135,23 +223,11 @@ impl CnvType for Font {
 
     fn new_content(
         parent: Arc<CnvObject>,
-        mut properties: HashMap<String, String>,
+        properties: HashMap<String, String>,
     ) -> Result<CnvContent, TypeParsingError> {
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         let defs: HashMap<FontDef, Option<String>> = properties
             .into_iter()
             .filter_map(|(k, v)| {
@@ -196,6 +272,89 @@ impl Initable for Font {
 }
 
 impl FontState {
+    fn load_if_needed(
+        &mut self,
+        context: RunnerContext,
+        definitions: &HashMap<FontDef, Option<String>>,
+    ) -> anyhow::Result<()> {
+        let Some((definition, filename)) = definitions
+            .iter()
+            .filter(|(d, _)| {
+                (self.family.is_empty() || d.family.eq_ignore_ascii_case(&self.family))
+                    && (self.style.is_empty() || d.style.eq_ignore_ascii_case(&self.style))
+                    && (self.size == 0 || d.size == self.size)
+            })
+            .filter_map(|(d, f)| f.as_ref().map(|f| (d, f)))
+            .min_by_key(|(d, _)| *d)
+        else {
+            return Ok(());
+        };
+        if self
+            .loaded_font
+            .as_ref()
+            .is_some_and(|(d, _)| d == definition)
+        {
+            return Ok(());
+        }
+        let script = context.current_object.parent.as_ref();
+        let filesystem = Arc::clone(&script.runner.filesystem);
+        let data = filesystem
+            .write()
+            .unwrap()
+            .read_scene_asset(
+                Arc::clone(&script.runner.game_paths),
+                &script.path.with_file_path(filename),
+            )
+            .map_err(|e| RunnerError::IoError { source: e })?;
+        let data = parse_ann(&data);
+        let mut glyphs = HashMap::new();
+        for sequence in data.sequences.iter() {
+            let mut characters = sequence.header.name.0.chars();
+            let (Some(character), None) = (characters.next(), characters.next()) else {
+                continue;
+            };
+            let Some(sprite) = sequence
+                .header
+                .frame_to_sprite_mapping
+                .first()
+                .and_then(|i| data.sprites.get(usize::from(*i)))
+            else {
+                continue;
+            };
+            let converted_data = sprite
+                .image_data
+                .to_rgba8888(data.header.color_format, sprite.header.compression_type);
+            glyphs.insert(
+                character,
+                (
+                    SpriteDefinition {
+                        name: sprite.header.name.0.clone(),
+                        size_px: (
+                            sprite.header.width_px.into(),
+                            sprite.header.height_px.into(),
+                        ),
+                        offset_px: (
+                            sprite.header.x_position_px.into(),
+                            sprite.header.y_position_px.into(),
+                        ),
+                    },
+                    SpriteData {
+                        hash: xxh3_64(&converted_data),
+                        data: converted_data,
+                    },
+                ),
+            );
+        }
+        self.loaded_font = Some((
+            definition.clone(),
+            Arc::new(LoadedFont {
+                filename: Some(filename.clone()),
+                glyphs,
+            }),
+        ));
+        Ok(())
+    }
+
     pub fn get_height(&self) -> anyhow::Result<usize> {
         // GETHEIGHT
         todo!()
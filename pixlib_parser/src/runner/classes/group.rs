@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler};
+use super::super::parsers::parse_event_handlers;
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -13,9 +13,9 @@ use super::*;
 #[derive(Debug, Clone)]
 pub struct GroupInit {
     // GROUP
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -23,21 +23,22 @@ struct GroupState {
     // deduced from methods
     pub objects: Vec<Arc<CnvObject>>,
     pub cursor_index: usize,
+    pub is_visible: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct GroupEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for GroupEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -56,6 +57,7 @@ impl Group {
         Self {
             parent,
             state: RefCell::new(GroupState {
+                is_visible: true,
                 ..Default::default()
             }),
             event_handlers: GroupEventHandlers {
@@ -65,6 +67,23 @@ impl Group {
             },
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    pub fn is_visible(&self) -> anyhow::Result<bool> {
+        self.state.borrow().is_visible()
+    }
+
+    pub fn get_objects(&self) -> Vec<Arc<CnvObject>> {
+        self.state.borrow().objects.clone()
+    }
 }
 
 impl CnvType for Group {
@@ -88,6 +107,7 @@ impl CnvType for Group {
     ) -> anyhow::Result<CnvValue> {
         match name {
             CallableIdentifier::Method("ADD") => {
+                check_argument_count(arguments, 1..)?;
                 let name = arguments[0].to_str();
                 let added_object = context
                     .runner
@@ -132,17 +152,25 @@ impl CnvType for Group {
                 .borrow()
                 .get_size()
                 .map(|v| CnvValue::Integer(v as i32)),
+            CallableIdentifier::Method("HIDE") => {
+                self.state.borrow_mut().hide().map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("ISVISIBLE") => {
+                self.state.borrow().is_visible().map(CnvValue::Bool)
+            }
             CallableIdentifier::Method("NEXT") => {
                 self.state.borrow_mut().next().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("PREV") => {
                 self.state.borrow_mut().prev().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("REMOVE") => self
-                .state
-                .borrow_mut()
-                .remove(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("REMOVE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .remove(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("REMOVEALL") => {
                 self.state.borrow_mut().remove_all().map(|_| CnvValue::Null)
             }
@@ -156,6 +184,9 @@ impl CnvType for Group {
                 .borrow_mut()
                 .set_marker_pos()
                 .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SHOW") => {
+                self.state.borrow_mut().show().map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -179,23 +210,11 @@ impl CnvType for Group {
 
     fn new_content(
         parent: Arc<CnvObject>,
-        mut properties: HashMap<String, String>,
+        properties: HashMap<String, String>,
     ) -> Result<CnvContent, TypeParsingError> {
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Group(Self::from_initial_properties(
             parent,
             GroupInit {
@@ -270,6 +289,17 @@ impl GroupState {
         todo!()
     }
 
+    pub fn hide(&mut self) -> anyhow::Result<()> {
+        // HIDE
+        self.is_visible = false;
+        Ok(())
+    }
+
+    pub fn is_visible(&self) -> anyhow::Result<bool> {
+        // ISVISIBLE
+        Ok(self.is_visible)
+    }
+
     pub fn next(&mut self) -> anyhow::Result<()> {
         // NEXT
         todo!()
@@ -316,6 +346,12 @@ impl GroupState {
         todo!()
     }
 
+    pub fn show(&mut self) -> anyhow::Result<()> {
+        // SHOW
+        self.is_visible = true;
+        Ok(())
+    }
+
     // custom
 
     pub fn call_method_on_objects(
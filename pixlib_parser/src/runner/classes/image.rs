@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler, parse_i32};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers, parse_i32};
 use pixlib_formats::file_formats::img::parse_img;
 use xxhash_rust::xxh3::xxh3_64;
 
@@ -30,15 +30,15 @@ pub struct ImageProperties {
     pub to_canvas: Option<bool>,               // TOCANVAS
     pub visible: Option<bool>,                 // VISIBLE
 
-    pub on_click: Option<Arc<ParsedScript>>, // ONCLICK signal
-    pub on_collision: Option<Arc<ParsedScript>>, // ONCOLLISION signal
-    pub on_collision_finished: Option<Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,  // ONDONE signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>, // ONFOCUSON signal
-    pub on_init: Option<Arc<ParsedScript>>,  // ONINIT signal
-    pub on_release: Option<Arc<ParsedScript>>, // ONRELEASE signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
+    pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
+    pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
+    pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -65,29 +65,29 @@ struct ImageState {
 
 #[derive(Debug, Clone)]
 pub struct ImageEventHandlers {
-    pub on_click: Option<Arc<ParsedScript>>,     // ONCLICK signal
-    pub on_collision: Option<Arc<ParsedScript>>, // ONCOLLISION signal
-    pub on_collision_finished: Option<Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,      // ONDONE signal
-    pub on_focus_off: Option<Arc<ParsedScript>>, // ONFOCUSOFF signal
-    pub on_focus_on: Option<Arc<ParsedScript>>,  // ONFOCUSON signal
-    pub on_init: Option<Arc<ParsedScript>>,      // ONINIT signal
-    pub on_release: Option<Arc<ParsedScript>>,   // ONRELEASE signal
-    pub on_signal: Option<Arc<ParsedScript>>,    // ONSIGNAL signal
+    pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
+    pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
+    pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
+    pub on_focus_off: HashMap<String, Arc<ParsedScript>>, // ONFOCUSOFF signal
+    pub on_focus_on: HashMap<String, Arc<ParsedScript>>, // ONFOCUSON signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
+    pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for ImageEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCLICK" => self.on_click.as_ref(),
-            "ONCOLLISION" => self.on_collision.as_ref(),
-            "ONCOLLISIONFINISHED" => self.on_collision_finished.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONFOCUSOFF" => self.on_focus_off.as_ref(),
-            "ONFOCUSON" => self.on_focus_on.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONRELEASE" => self.on_release.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONCLICK" => get_matching_handler(&self.on_click, argument),
+            "ONCOLLISION" => get_matching_handler(&self.on_collision, argument),
+            "ONCOLLISIONFINISHED" => get_matching_handler(&self.on_collision_finished, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONFOCUSOFF" => get_matching_handler(&self.on_focus_off, argument),
+            "ONFOCUSON" => get_matching_handler(&self.on_focus_on, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONRELEASE" => get_matching_handler(&self.on_release, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -117,6 +117,7 @@ impl Image {
                 does_monitor_collision: props.monitor_collision.unwrap_or_default(),
                 priority: props.priority.unwrap_or_default() as isize,
                 is_visible: props.visible.unwrap_or(true),
+                opacity: 255,
                 ..ImageState::default()
             }),
             event_handlers: ImageEventHandlers {
@@ -143,6 +144,13 @@ impl Image {
         image
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn is_visible(&self) -> anyhow::Result<bool> {
         self.state.borrow().is_visible()
     }
@@ -153,6 +161,38 @@ impl Image {
 
     // custom
 
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.state
+            .borrow()
+            .file_data
+            .get_filename()
+            .map(|f| f.to_owned())
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops the decoded file data if it comes from the given file, so it is loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(referenced) = state.file_data.get_filename().map(|f| f.to_owned()) else {
+            return false;
+        };
+        let script = self.parent.parent.as_ref();
+        let resolved_paths = script
+            .runner
+            .game_paths
+            .get_scene_asset_paths(&script.path.with_file_path(&referenced));
+        if !is_same_file(&resolved_paths, filename) {
+            return false;
+        }
+        state.file_data = ImageFileData::NotLoaded(referenced);
+        true
+    }
+
+    pub fn get_base_position(&self) -> anyhow::Result<(isize, isize)> {
+        self.state.borrow().get_position()
+    }
+
     pub fn get_position(&self) -> anyhow::Result<(isize, isize)> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
         self.state
@@ -232,8 +272,9 @@ impl GeneralGraphics for Image {
     }
 
     fn get_pixel_data(&self) -> anyhow::Result<Arc<Vec<u8>>> {
+        let opacity = self.state.borrow().opacity;
         self.get_image_to_show()?
-            .map(|r| r.1.data)
+            .map(|r| apply_opacity(r.1.data, opacity))
             .ok_or(RunnerError::NoImageDataLoaded(self.parent.name.clone()).into())
     }
 }
@@ -390,9 +431,11 @@ impl CnvType for Image {
             CallableIdentifier::Method("FLIPV") => {
                 self.state.borrow_mut().flip_v().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("GETALPHA") => {
-                self.state.borrow_mut().get_alpha().map(|_| CnvValue::Null)
-            }
+            CallableIdentifier::Method("GETALPHA") => self
+                .state
+                .borrow()
+                .get_alpha()
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETCENTERX") => self
                 .state
                 .borrow_mut()
@@ -428,9 +471,9 @@ impl CnvType for Image {
             }
             CallableIdentifier::Method("GETOPACITY") => self
                 .state
-                .borrow_mut()
+                .borrow()
                 .get_opacity()
-                .map(|_| CnvValue::Null),
+                .map(|v| CnvValue::Integer(v as i32)),
             CallableIdentifier::Method("GETPIXEL") => {
                 self.state.borrow_mut().get_pixel().map(|_| CnvValue::Null)
             }
@@ -470,6 +513,7 @@ impl CnvType for Image {
                 self.state.borrow_mut().is_inside().map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("ISNEAR") => {
+                check_argument_count(arguments, 2..)?;
                 let name = arguments[0].to_str();
                 let other = context
                     .runner
@@ -486,11 +530,13 @@ impl CnvType for Image {
             CallableIdentifier::Method("LINK") => {
                 self.state.borrow_mut().link().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("LOAD") => self
-                .state
-                .borrow_mut()
-                .load(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("LOAD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .load(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("MERGEALPHA") => self
                 .state
                 .borrow_mut()
@@ -506,15 +552,17 @@ impl CnvType for Image {
                 .borrow_mut()
                 .monitor_collision()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("MOVE") => self
-                .state
-                .borrow_mut()
-                .move_by(
-                    context,
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("MOVE") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .move_by(
+                        context,
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("REMOVEMONITORCOLLISION") => self
                 .state
                 .borrow_mut()
@@ -551,32 +599,40 @@ impl CnvType for Image {
                 .borrow_mut()
                 .set_clipping()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETOPACITY") => self
-                .state
-                .borrow_mut()
-                .set_opacity()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPOSITION") => self
-                .state
-                .borrow_mut()
-                .set_position(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_priority()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETRESETPOSITION") => self
-                .state
-                .borrow_mut()
-                .set_reset_position(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETOPACITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_opacity(arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETPOSITION") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .set_position(
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETRESETPOSITION") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .set_reset_position(
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETSCALEFACTOR") => self
                 .state
                 .borrow_mut()
@@ -653,51 +709,15 @@ impl CnvType for Image {
             .and_then(discard_if_empty)
             .map(parse_bool)
             .transpose()?;
-        let on_click = properties
-            .remove("ONCLICK")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_collision = properties
-            .remove("ONCOLLISION")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_collision_finished = properties
-            .remove("ONCOLLISIONFINISHED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_focus_off = properties
-            .remove("ONFOCUSOFF")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_focus_on = properties
-            .remove("ONFOCUSON")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_release = properties
-            .remove("ONRELEASE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_click = parse_event_handlers(&properties, "ONCLICK")?;
+        let on_collision = parse_event_handlers(&properties, "ONCOLLISION")?;
+        let on_collision_finished = parse_event_handlers(&properties, "ONCOLLISIONFINISHED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_focus_off = parse_event_handlers(&properties, "ONFOCUSOFF")?;
+        let on_focus_on = parse_event_handlers(&properties, "ONFOCUSON")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_release = parse_event_handlers(&properties, "ONRELEASE")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Image(Image::from_initial_properties(
             parent,
             ImageProperties {
@@ -768,9 +788,9 @@ impl ImageState {
         Ok(())
     }
 
-    pub fn get_alpha(&mut self) -> anyhow::Result<()> {
+    pub fn get_alpha(&self) -> anyhow::Result<usize> {
         // GETALPHA
-        todo!()
+        Ok(self.opacity)
     }
 
     pub fn get_center_x(&mut self) -> anyhow::Result<()> {
@@ -808,9 +828,9 @@ impl ImageState {
         todo!()
     }
 
-    pub fn get_opacity(&mut self) -> anyhow::Result<()> {
+    pub fn get_opacity(&self) -> anyhow::Result<usize> {
         // GETOPACITY
-        todo!()
+        Ok(self.opacity)
     }
 
     pub fn get_pixel(&mut self) -> anyhow::Result<()> {
@@ -1035,9 +1055,10 @@ impl ImageState {
         todo!()
     }
 
-    pub fn set_opacity(&mut self) -> anyhow::Result<()> {
-        // SETOPACITY
-        todo!()
+    pub fn set_opacity(&mut self, opacity: i32) -> anyhow::Result<()> {
+        // SETOPACITY (INTEGER)
+        self.opacity = opacity.clamp(0, 255) as usize;
+        Ok(())
     }
 
     pub fn set_position(&mut self, x: isize, y: isize) -> anyhow::Result<()> {
@@ -1046,9 +1067,10 @@ impl ImageState {
         Ok(())
     }
 
-    pub fn set_priority(&mut self) -> anyhow::Result<()> {
+    pub fn set_priority(&mut self, priority: isize) -> anyhow::Result<()> {
         // SETPRIORITY
-        todo!()
+        self.priority = priority;
+        Ok(())
     }
 
     pub fn set_reset_position(&mut self, x: isize, y: isize) -> anyhow::Result<()> {
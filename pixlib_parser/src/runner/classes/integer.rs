@@ -2,9 +2,9 @@ use std::{any::Any, cell::RefCell};
 
 use log::info;
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler, parse_i32};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers, parse_i32};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -22,8 +22,8 @@ pub struct IntegerVarProperties {
 
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -39,8 +39,8 @@ struct IntegerVarState {
 pub struct IntegerVarEventHandlers {
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -48,20 +48,12 @@ pub struct IntegerVarEventHandlers {
 impl EventHandler for IntegerVarEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONBRUTALCHANGED" => argument
-                .and_then(|a| self.on_brutal_changed.get(a))
-                .or(self.on_brutal_changed.get("")),
-            "ONCHANGED" => argument
-                .and_then(|a| self.on_changed.get(a))
-                .or(self.on_changed.get("")),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONNETCHANGED" => argument
-                .and_then(|a| self.on_net_changed.get(a))
-                .or(self.on_net_changed.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONBRUTALCHANGED" => get_matching_handler(&self.on_brutal_changed, argument),
+            "ONCHANGED" => get_matching_handler(&self.on_changed, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONNETCHANGED" => get_matching_handler(&self.on_net_changed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -100,6 +92,13 @@ impl IntegerVar {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn get(&self) -> anyhow::Result<i32> {
         self.state.borrow().get(RunnerContext::new_minimal(
             &self.parent.parent.runner,
@@ -131,21 +130,27 @@ impl CnvType for IntegerVar {
             CallableIdentifier::Method("ABS") => {
                 self.state.borrow_mut().abs(context).map(CnvValue::Integer)
             }
-            CallableIdentifier::Method("ADD") => self
-                .state
-                .borrow_mut()
-                .add(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
-            CallableIdentifier::Method("AND") => self
-                .state
-                .borrow_mut()
-                .and(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
-            CallableIdentifier::Method("CLAMP") => self
-                .state
-                .borrow_mut()
-                .clamp(context, arguments[0].to_int(), arguments[1].to_int())
-                .map(CnvValue::Integer),
+            CallableIdentifier::Method("ADD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .add(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
+            CallableIdentifier::Method("AND") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .and(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
+            CallableIdentifier::Method("CLAMP") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .clamp(context, arguments[0].to_int(), arguments[1].to_int())
+                    .map(CnvValue::Integer)
+            }
             CallableIdentifier::Method("CLEAR") => self
                 .state
                 .borrow_mut()
@@ -159,40 +164,50 @@ impl CnvType for IntegerVar {
             CallableIdentifier::Method("DEC") => {
                 self.state.borrow_mut().dec(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("DIV") => self
-                .state
-                .borrow_mut()
-                .div(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("DIV") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .div(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("GET") => {
                 self.state.borrow().get(context).map(CnvValue::Integer)
             }
             CallableIdentifier::Method("INC") => {
                 self.state.borrow_mut().inc(context).map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("MOD") => self
-                .state
-                .borrow_mut()
-                .modulus(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("MUL") => self
-                .state
-                .borrow_mut()
-                .mul(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("MOD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .modulus(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("MUL") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .mul(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("NOT") => {
                 self.state.borrow_mut().not(context).map(CnvValue::Integer)
             }
-            CallableIdentifier::Method("OR") => self
-                .state
-                .borrow_mut()
-                .or(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
-            CallableIdentifier::Method("POWER") => self
-                .state
-                .borrow_mut()
-                .power(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
+            CallableIdentifier::Method("OR") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .or(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
+            CallableIdentifier::Method("POWER") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .power(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
             CallableIdentifier::Method("RANDOM") => self
                 .state
                 .borrow_mut()
@@ -203,31 +218,41 @@ impl CnvType for IntegerVar {
                 .borrow_mut()
                 .reset_ini(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SET") => self
-                .state
-                .borrow_mut()
-                .set(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETDEFAULT") => self
-                .state
-                .borrow_mut()
-                .set_default(context, arguments[0].to_int())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SUB") => self
-                .state
-                .borrow_mut()
-                .sub(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
-            CallableIdentifier::Method("SWITCH") => self
-                .state
-                .borrow_mut()
-                .switch(context, arguments[0].to_int(), arguments[1].to_int())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("XOR") => self
-                .state
-                .borrow_mut()
-                .xor(context, arguments[0].to_int())
-                .map(CnvValue::Integer),
+            CallableIdentifier::Method("SET") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETDEFAULT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_default(context, arguments[0].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SUB") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .sub(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
+            CallableIdentifier::Method("SWITCH") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .switch(context, arguments[0].to_int(), arguments[1].to_int())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("XOR") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .xor(context, arguments[0].to_int())
+                    .map(CnvValue::Integer)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -270,49 +295,12 @@ impl CnvType for IntegerVar {
             .and_then(discard_if_empty)
             .map(parse_i32)
             .transpose()?;
-        let mut on_brutal_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONBRUTALCHANGED" {
-                on_brutal_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONBRUTALCHANGED^") {
-                on_brutal_changed
-                    .insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCHANGED" {
-                on_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCHANGED^") {
-                on_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_net_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONNETCHANGED" {
-                on_net_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONNETCHANGED^") {
-                on_net_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_brutal_changed = parse_event_handlers(&properties, "ONBRUTALCHANGED")?;
+        let on_changed = parse_event_handlers(&properties, "ONCHANGED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_net_changed = parse_event_handlers(&properties, "ONNETCHANGED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Integer(Self::from_initial_properties(
             parent,
             IntegerVarProperties {
@@ -385,7 +373,8 @@ impl IntegerVarState {
 
     pub fn dec(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // DEC
-        self.change_value(context, self.value - 1);
+        // clamped to the integer range instead of wrapping around
+        self.change_value(context, self.value.saturating_sub(1));
         Ok(())
     }
 
@@ -402,7 +391,8 @@ impl IntegerVarState {
 
     pub fn inc(&mut self, context: RunnerContext) -> anyhow::Result<()> {
         // INC
-        self.change_value(context, self.value + 1);
+        // clamped to the integer range instead of wrapping around
+        self.change_value(context, self.value.saturating_add(1));
         Ok(())
     }
 
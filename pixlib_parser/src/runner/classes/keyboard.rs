@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler};
+use super::super::parsers::{discard_if_empty, parse_event_handlers};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -15,12 +15,12 @@ pub struct KeyboardProperties {
     // KEYBOARD
     pub keyboard: Option<String>, // KEYBOARD
 
-    pub on_char: Option<Arc<ParsedScript>>, // ONCHAR signal
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
-    pub on_key_down: Option<Arc<ParsedScript>>, // ONKEYDOWN signal
-    pub on_key_up: Option<Arc<ParsedScript>>, // ONKEYUP signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_char: HashMap<String, Arc<ParsedScript>>, // ONCHAR signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_key_down: HashMap<String, Arc<ParsedScript>>, // ONKEYDOWN signal
+    pub on_key_up: HashMap<String, Arc<ParsedScript>>, // ONKEYUP signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -32,23 +32,23 @@ struct KeyboardState {
 
 #[derive(Debug, Clone)]
 pub struct KeyboardEventHandlers {
-    pub on_char: Option<Arc<ParsedScript>>,     // ONCHAR signal
-    pub on_done: Option<Arc<ParsedScript>>,     // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,     // ONINIT signal
-    pub on_key_down: Option<Arc<ParsedScript>>, // ONKEYDOWN signal
-    pub on_key_up: Option<Arc<ParsedScript>>,   // ONKEYUP signal
-    pub on_signal: Option<Arc<ParsedScript>>,   // ONSIGNAL signal
+    pub on_char: HashMap<String, Arc<ParsedScript>>, // ONCHAR signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_key_down: HashMap<String, Arc<ParsedScript>>, // ONKEYDOWN signal
+    pub on_key_up: HashMap<String, Arc<ParsedScript>>, // ONKEYUP signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for KeyboardEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCHAR" => self.on_char.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONKEYDOWN" => self.on_key_down.as_ref(),
-            "ONKEYUP" => self.on_key_up.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONCHAR" => get_matching_handler(&self.on_char, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONKEYDOWN" => get_matching_handler(&self.on_key_down, argument),
+            "ONKEYUP" => get_matching_handler(&self.on_key_up, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -83,6 +83,19 @@ impl Keyboard {
             keyboard: props.keyboard.unwrap_or_default(),
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    pub fn is_enabled(&self) -> anyhow::Result<bool> {
+        self.state.borrow().is_enabled()
+    }
 }
 
 impl CnvType for Keyboard {
@@ -129,17 +142,20 @@ impl CnvType for Keyboard {
                 .borrow_mut()
                 .is_key_down()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETAUTOREPEAT") => self
-                .state
-                .borrow_mut()
-                .set_auto_repeat(arguments[0].to_bool())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETAUTOREPEAT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_auto_repeat(arguments[0].to_bool())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
                     .get(event_name, arguments.first().map(|v| v.to_str()).as_deref())
                 {
-                    code.run(context).map(|_| CnvValue::Null)
+                    code.run(context.with_arguments(arguments.to_owned()))
+                        .map(|_| CnvValue::Null)
                 } else {
                     Ok(CnvValue::Null)
                 }
@@ -157,36 +173,12 @@ impl CnvType for Keyboard {
         mut properties: HashMap<String, String>,
     ) -> Result<CnvContent, TypeParsingError> {
         let keyboard = properties.remove("KEYBOARD").and_then(discard_if_empty);
-        let on_char = properties
-            .remove("ONCHAR")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_key_down = properties
-            .remove("ONKEYDOWN")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_key_up = properties
-            .remove("ONKEYUP")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_char = parse_event_handlers(&properties, "ONCHAR")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_key_down = parse_event_handlers(&properties, "ONKEYDOWN")?;
+        let on_key_up = parse_event_handlers(&properties, "ONKEYUP")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Keyboard(Self::from_initial_properties(
             parent,
             KeyboardProperties {
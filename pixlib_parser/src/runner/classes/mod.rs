@@ -1,11 +1,16 @@
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
 
 use chrono::{DateTime, Utc};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use super::{content::CnvContent, parsers::TypeParsingError, CallableIdentifier, CnvObject, Rect};
-use crate::runner::{CnvValue, RunnerContext};
+use crate::runner::{CnvValue, RunnerContext, RunnerError};
 
 pub trait CnvType: std::fmt::Debug {
     fn get_type_id(&self) -> &'static str;
@@ -105,7 +110,16 @@ impl CnvTypeFactory {
             "SYSTEM" => System::new_content(parent, properties),
             "TEXT" => Text::new_content(parent, properties),
             "TIMER" => Timer::new_content(parent, properties),
-            _ => Err(TypeParsingError::UnknownType(type_name)),
+            _ => {
+                log::warn!(
+                    "Unknown type {} of object {}, keeping it as a placeholder",
+                    type_name,
+                    parent.name
+                );
+                Ok(CnvContent::Unknown(Unknown::from_type_and_properties(
+                    parent, type_name, properties,
+                )))
+            }
         }
     }
 }
@@ -134,6 +148,38 @@ pub trait GeneralButton {
     fn makes_cursor_pointer(&self) -> anyhow::Result<bool>;
 }
 
+/// Checks the arity of a method call up front, so that malformed scripts
+/// result in an error instead of a panic on out-of-bounds indexing.
+pub(crate) fn check_argument_count(
+    arguments: &[CnvValue],
+    expected: impl RangeBounds<usize>,
+) -> Result<(), RunnerError> {
+    let actual = arguments.len();
+    let expected_min = match expected.start_bound() {
+        Bound::Included(&min) => min,
+        Bound::Excluded(&min) => min + 1,
+        Bound::Unbounded => 0,
+    };
+    let expected_max = match expected.end_bound() {
+        Bound::Included(&max) => Some(max),
+        Bound::Excluded(&max) => Some(max.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+    if actual < expected_min {
+        return Err(RunnerError::TooFewArguments {
+            expected_min,
+            actual,
+        });
+    }
+    if let Some(expected_max) = expected_max.filter(|max| actual > *max) {
+        return Err(RunnerError::TooManyArguments {
+            expected_max,
+            actual,
+        });
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 enum CursorInteraction {
     #[default]
@@ -142,6 +188,67 @@ enum CursorInteraction {
     Pressing,
 }
 
+/// Point of a graphics object that `SETPOSITION` places at the given coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Anchor {
+    Center,
+    LeftUpper,
+    RightUpper,
+    LeftLower,
+    RightLower,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Anchor {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim_matches('"').to_ascii_uppercase().as_ref() {
+            "CENTER" => Some(Self::Center),
+            "LEFTUPPER" => Some(Self::LeftUpper),
+            "RIGHTUPPER" => Some(Self::RightUpper),
+            "LEFTLOWER" => Some(Self::LeftLower),
+            "RIGHTLOWER" => Some(Self::RightLower),
+            "LEFT" => Some(Self::Left),
+            "RIGHT" => Some(Self::Right),
+            "TOP" => Some(Self::Top),
+            "BOTTOM" => Some(Self::Bottom),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Center => "CENTER",
+            Self::LeftUpper => "LEFTUPPER",
+            Self::RightUpper => "RIGHTUPPER",
+            Self::LeftLower => "LEFTLOWER",
+            Self::RightLower => "RIGHTLOWER",
+            Self::Left => "LEFT",
+            Self::Right => "RIGHT",
+            Self::Top => "TOP",
+            Self::Bottom => "BOTTOM",
+        }
+    }
+
+    /// Offset of the anchor point from the top-left corner of a rectangle of the given size.
+    pub fn get_offset(&self, size: (usize, usize)) -> (isize, isize) {
+        let (width, height) = (size.0 as isize, size.1 as isize);
+        match self {
+            Self::Center => (width / 2, height / 2),
+            Self::LeftUpper => (0, 0),
+            Self::RightUpper => (width, 0),
+            Self::LeftLower => (0, height),
+            Self::RightLower => (width, height),
+            Self::Left => (0, height / 2),
+            Self::Right => (width, height / 2),
+            Self::Top => (width / 2, 0),
+            Self::Bottom => (width / 2, height),
+        }
+    }
+}
+
 pub type EpisodeName = String;
 pub type SceneName = String;
 pub type ConditionName = String;
@@ -181,6 +288,7 @@ mod r#struct;
 mod system;
 mod text;
 mod timer;
+mod unknown;
 
 pub use animation::Animation;
 pub use application::Application;
@@ -213,3 +321,4 @@ pub use string::StringVar;
 pub use system::System;
 pub use text::Text;
 pub use timer::Timer;
+pub use unknown::Unknown;
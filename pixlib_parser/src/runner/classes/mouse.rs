@@ -1,8 +1,8 @@
 use std::{any::Any, collections::VecDeque, sync::RwLock};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler, parse_i32, ReferenceRect};
+use super::super::parsers::{discard_if_empty, parse_event_handlers, parse_i32, ReferenceRect};
 
 use crate::{
     common::DroppableRefMut,
@@ -23,10 +23,10 @@ pub struct MouseProperties {
     pub raw: Option<i32>,      // RAW
 
     pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
-    pub on_dbl_click: Option<Arc<ParsedScript>>,      // ONDBLCLICK signal
-    pub on_done: Option<Arc<ParsedScript>>,           // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,           // ONINIT signal
-    pub on_move: Option<Arc<ParsedScript>>,           // ONMOVE signal
+    pub on_dbl_click: HashMap<String, Arc<ParsedScript>>, // ONDBLCLICK signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
+    pub on_move: HashMap<String, Arc<ParsedScript>>,  // ONMOVE signal
     pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
@@ -46,6 +46,7 @@ struct MouseState {
     is_middle_button_down: bool,
     is_right_button_down: bool,
     is_locked: bool,
+    cursor_image: Option<String>,
 
     events_out: VecDeque<InternalMouseEvent>,
 }
@@ -53,10 +54,10 @@ struct MouseState {
 #[derive(Debug, Clone)]
 pub struct MouseEventHandlers {
     pub on_click: HashMap<String, Arc<ParsedScript>>, // ONCLICK signal
-    pub on_dbl_click: Option<Arc<ParsedScript>>,      // ONDBLCLICK signal
-    pub on_done: Option<Arc<ParsedScript>>,           // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,           // ONINIT signal
-    pub on_move: Option<Arc<ParsedScript>>,           // ONMOVE signal
+    pub on_dbl_click: HashMap<String, Arc<ParsedScript>>, // ONDBLCLICK signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,  // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,  // ONINIT signal
+    pub on_move: HashMap<String, Arc<ParsedScript>>,  // ONMOVE signal
     pub on_release: HashMap<String, Arc<ParsedScript>>, // ONRELEASE signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
@@ -64,19 +65,13 @@ pub struct MouseEventHandlers {
 impl EventHandler for MouseEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCLICK" => argument
-                .and_then(|a| self.on_click.get(a))
-                .or(self.on_click.get("")),
-            "ONDBLCLICK" => self.on_dbl_click.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONMOVE" => self.on_move.as_ref(),
-            "ONRELEASE" => argument
-                .and_then(|a| self.on_release.get(a))
-                .or(self.on_release.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONCLICK" => get_matching_handler(&self.on_click, argument),
+            "ONDBLCLICK" => get_matching_handler(&self.on_dbl_click, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONMOVE" => get_matching_handler(&self.on_move, argument),
+            "ONRELEASE" => get_matching_handler(&self.on_release, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -121,6 +116,16 @@ impl Mouse {
         }
     }
 
+    /// Copies the object, attaching the copy to another object.
+    ///
+    /// The mouse state is global, so it stays shared between the copy and the original.
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn handle_incoming_event(event: MouseEvent) -> anyhow::Result<()> {
         let mut mouse_state = GLOBAL_MOUSE_STATE.write().unwrap();
         match event {
@@ -144,6 +149,30 @@ impl Mouse {
         Ok(())
     }
 
+    /// Restores the script-controlled part of the global state to its defaults,
+    /// leaving the physical cursor position and buttons intact.
+    pub fn reset_state() {
+        let mut mouse_state = GLOBAL_MOUSE_STATE.write().unwrap();
+        mouse_state.is_enabled = true;
+        mouse_state.are_events_enabled = true;
+        mouse_state.is_visible = true;
+        mouse_state.clip_rect = None;
+        mouse_state.is_locked = false;
+        mouse_state.cursor_image = None;
+        mouse_state.events_out.clear();
+    }
+
+    pub fn is_visible() -> anyhow::Result<bool> {
+        let mouse_state = GLOBAL_MOUSE_STATE.read().unwrap();
+        Ok(mouse_state.is_visible)
+    }
+
+    /// Name of the graphics object set with `SET` to be drawn in place of the system cursor.
+    pub fn get_cursor_image() -> anyhow::Result<Option<String>> {
+        let mouse_state = GLOBAL_MOUSE_STATE.read().unwrap();
+        Ok(mouse_state.cursor_image.clone())
+    }
+
     pub fn get_position() -> anyhow::Result<(isize, isize)> {
         let mouse_state = GLOBAL_MOUSE_STATE.read().unwrap();
         Ok(mouse_state.position)
@@ -254,17 +283,24 @@ impl CnvType for Mouse {
                 .unwrap()
                 .release_left_button()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("MOVE") => self
-                .state
-                .write()
-                .unwrap()
-                .move_by(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("MOVE") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .write()
+                    .unwrap()
+                    .move_by(
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SET") => {
-                self.state.write().unwrap().set().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .write()
+                    .unwrap()
+                    .set(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SETACTIVERECT") => self
                 .state
@@ -278,15 +314,17 @@ impl CnvType for Mouse {
                 .unwrap()
                 .set_clip_rect()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPOSITION") => self
-                .state
-                .write()
-                .unwrap()
-                .set_position(
-                    arguments[0].to_int() as isize,
-                    arguments[1].to_int() as isize,
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETPOSITION") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .write()
+                    .unwrap()
+                    .set_position(
+                        arguments[0].to_int() as isize,
+                        arguments[1].to_int() as isize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SHOW") => {
                 self.state.write().unwrap().show().map(|_| CnvValue::Null)
             }
@@ -318,50 +356,13 @@ impl CnvType for Mouse {
             .and_then(discard_if_empty)
             .map(parse_i32)
             .transpose()?;
-        let mut on_click = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCLICK" {
-                on_click.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCLICK^") {
-                on_click.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_dbl_click = properties
-            .remove("ONDBLCLICK")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_move = properties
-            .remove("ONMOVE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_release = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONRELEASE" {
-                on_release.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONRELEASE^") {
-                on_release.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_click = parse_event_handlers(&properties, "ONCLICK")?;
+        let on_dbl_click = parse_event_handlers(&properties, "ONDBLCLICK")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_move = parse_event_handlers(&properties, "ONMOVE")?;
+        let on_release = parse_event_handlers(&properties, "ONRELEASE")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Mouse(Self::from_initial_properties(
             parent,
             MouseProperties {
@@ -481,9 +482,18 @@ impl MouseState {
         Ok(())
     }
 
-    pub fn set(&mut self) -> anyhow::Result<()> {
-        // SET
-        todo!()
+    pub fn set(&mut self, context: RunnerContext, cursor_image: &str) -> anyhow::Result<()> {
+        // SET (STRING)
+        self.cursor_image = Some(cursor_image.to_owned());
+        context
+            .runner
+            .events_out
+            .cursor
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(CursorEvent::CursorSetToImage(cursor_image.to_owned()))
+            });
+        Ok(())
     }
 
     pub fn set_active_rect(&mut self) -> anyhow::Result<()> {
@@ -51,6 +51,13 @@ impl MultiArray {
             dimension_count: props.dimensions as usize,
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl CnvType for MultiArray {
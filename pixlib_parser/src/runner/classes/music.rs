@@ -51,6 +51,43 @@ impl Music {
         }
         music
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.state
+            .borrow()
+            .file_data
+            .get_filename()
+            .map(|f| f.to_owned())
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops the decoded file data if it comes from the given file, so it is loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(referenced) = state.file_data.get_filename().map(|f| f.to_owned()) else {
+            return false;
+        };
+        let script = self.parent.parent.as_ref();
+        let resolved_paths = script
+            .runner
+            .game_paths
+            .get_sound_paths(&script.path.with_file_path(&referenced));
+        if !is_same_file(&resolved_paths, filename) {
+            return false;
+        }
+        state.file_data = SoundFileData::NotLoaded(referenced);
+        true
+    }
 }
 
 impl CnvType for Music {
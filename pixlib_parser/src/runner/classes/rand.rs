@@ -42,6 +42,13 @@ impl Rand {
             event_handlers: RandEventHandlers {},
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl CnvType for Rand {
@@ -1,12 +1,13 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
 use super::super::parsers::{
-    discard_if_empty, parse_bool, parse_comma_separated, parse_datetime, parse_event_handler,
+    discard_if_empty, parse_bool, parse_comma_separated, parse_datetime, parse_event_handlers,
+    parse_i32,
 };
-use events::SoundSource;
-use pixlib_formats::file_formats::img::parse_img;
+use events::{SceneTransition, SoundSource};
+use pixlib_formats::{file_formats::img::parse_img, FileFormat};
 use xxhash_rust::xxh3::xxh3_64;
 
 use crate::{
@@ -31,17 +32,20 @@ pub struct SceneProperties {
     pub dlls: Option<Vec<String>>,               // DLLS
     pub last_modify_time: Option<DateTime<Utc>>, // LASTMODIFYTIME
     pub music: Option<String>,                   // MUSIC
+    pub music_loop: Option<bool>,                // MUSICLOOP
     pub path: Option<String>,                    // PATH
+    pub transition: Option<String>,              // TRANSITION
+    pub transition_time: Option<i32>,            // TRANSITIONTIME
     pub version: Option<String>,                 // VERSION
 
-    pub on_activate: Option<Arc<ParsedScript>>, // ONACTIVATE signal
-    pub on_deactivate: Option<Arc<ParsedScript>>, // ONDEACTIVATE signal
-    pub on_do_modal: Option<Arc<ParsedScript>>, // ONDOMODAL signal
-    pub on_done: Option<Arc<ParsedScript>>,     // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,     // ONINIT signal
-    pub on_music_looped: Option<Arc<ParsedScript>>, // ONMUSICLOOPED signal
-    pub on_restart: Option<Arc<ParsedScript>>,  // ONRESTART signal
-    pub on_signal: Option<Arc<ParsedScript>>,   // ONSIGNAL signal
+    pub on_activate: HashMap<String, Arc<ParsedScript>>, // ONACTIVATE signal
+    pub on_deactivate: HashMap<String, Arc<ParsedScript>>, // ONDEACTIVATE signal
+    pub on_do_modal: HashMap<String, Arc<ParsedScript>>, // ONDOMODAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,     // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,     // ONINIT signal
+    pub on_music_looped: HashMap<String, Arc<ParsedScript>>, // ONMUSICLOOPED signal
+    pub on_restart: HashMap<String, Arc<ParsedScript>>,  // ONRESTART signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>,   // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -61,27 +65,27 @@ struct SceneState {
 
 #[derive(Debug, Clone)]
 pub struct SceneEventHandlers {
-    pub on_activate: Option<Arc<ParsedScript>>, // ONACTIVATE signal
-    pub on_deactivate: Option<Arc<ParsedScript>>, // ONDEACTIVATE signal
-    pub on_do_modal: Option<Arc<ParsedScript>>, // ONDOMODAL signal
-    pub on_done: Option<Arc<ParsedScript>>,     // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,     // ONINIT signal
-    pub on_music_looped: Option<Arc<ParsedScript>>, // ONMUSICLOOPED signal
-    pub on_restart: Option<Arc<ParsedScript>>,  // ONRESTART signal
-    pub on_signal: Option<Arc<ParsedScript>>,   // ONSIGNAL signal
+    pub on_activate: HashMap<String, Arc<ParsedScript>>, // ONACTIVATE signal
+    pub on_deactivate: HashMap<String, Arc<ParsedScript>>, // ONDEACTIVATE signal
+    pub on_do_modal: HashMap<String, Arc<ParsedScript>>, // ONDOMODAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,     // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,     // ONINIT signal
+    pub on_music_looped: HashMap<String, Arc<ParsedScript>>, // ONMUSICLOOPED signal
+    pub on_restart: HashMap<String, Arc<ParsedScript>>,  // ONRESTART signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>,   // ONSIGNAL signal
 }
 
 impl EventHandler for SceneEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONACTIVATE" => self.on_activate.as_ref(),
-            "ONDEACTIVATE" => self.on_deactivate.as_ref(),
-            "ONDOMODAL" => self.on_do_modal.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONMUSICLOOPED" => self.on_music_looped.as_ref(),
-            "ONRESTART" => self.on_restart.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONACTIVATE" => get_matching_handler(&self.on_activate, argument),
+            "ONDEACTIVATE" => get_matching_handler(&self.on_deactivate, argument),
+            "ONDOMODAL" => get_matching_handler(&self.on_do_modal, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONMUSICLOOPED" => get_matching_handler(&self.on_music_looped, argument),
+            "ONRESTART" => get_matching_handler(&self.on_restart, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -101,8 +105,10 @@ pub struct Scene {
     is_deamon: bool,
     description: String,
     dlls: Vec<String>,
+    is_music_looping: bool,
     last_modify_time: Option<DateTime<Utc>>,
     path: Option<String>,
+    transition: Option<SceneTransition>,
     version: String,
 }
 
@@ -113,6 +119,8 @@ impl Scene {
             state: RefCell::new(SceneState {
                 is_music_playing: true,
                 music_volume_permilles: 1000usize,
+                min_hs_priority: i32::MIN as isize,
+                max_hs_priority: i32::MAX as isize,
                 ..Default::default()
             }),
             event_handlers: SceneEventHandlers {
@@ -131,8 +139,13 @@ impl Scene {
             is_deamon: props.deamon.unwrap_or_default(),
             description: props.description.unwrap_or_default(),
             dlls: props.dlls.unwrap_or_default(),
+            is_music_looping: props.music_loop.unwrap_or(true),
             last_modify_time: props.last_modify_time,
             path: props.path,
+            transition: props.transition.map(|kind| SceneTransition {
+                kind,
+                duration_ms: props.transition_time.unwrap_or_default().max(0) as usize,
+            }),
             version: props.version.unwrap_or_default(),
         };
         if let Some(background_filename) = props.background {
@@ -145,12 +158,93 @@ impl Scene {
         scene
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
     pub fn get_script_path(&self) -> Option<String> {
         self.path.clone()
     }
 
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        let state = self.state.borrow();
+        [
+            state.background_data.get_filename(),
+            state.music_data.get_filename(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|f| f.to_owned())
+        .collect()
+    }
+
+    pub fn get_referenced_resources(&self) -> Vec<(String, FileFormat)> {
+        let state = self.state.borrow();
+        [
+            (state.background_data.get_filename(), FileFormat::Img),
+            (state.music_data.get_filename(), FileFormat::Wav),
+        ]
+        .into_iter()
+        .filter_map(|(f, format)| f.map(|f| (f.to_owned(), format)))
+        .collect()
+    }
+
+    /// Drops the background or music data if it comes from the given file,
+    /// so it is loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let game_paths = &self.parent.parent.runner.game_paths;
+        let scene_path = self.path.as_deref().unwrap_or_default();
+        let mut state = self.state.borrow_mut();
+        let mut invalidated = false;
+        if let Some(referenced) = state.background_data.get_filename().map(|f| f.to_owned()) {
+            let resolved_paths =
+                game_paths.get_scene_asset_paths(&ScenePath::new(scene_path, &referenced));
+            if is_same_file(&resolved_paths, filename) {
+                state.background_data = ImageFileData::NotLoaded(referenced);
+                invalidated = true;
+            }
+        }
+        if let Some(referenced) = state.music_data.get_filename().map(|f| f.to_owned()) {
+            let resolved_paths =
+                game_paths.get_sound_paths(&ScenePath::new(scene_path, &referenced));
+            if is_same_file(&resolved_paths, filename) {
+                state.music_data = SoundFileData::NotLoaded(referenced);
+                invalidated = true;
+            }
+        }
+        invalidated
+    }
+
+    /// Loads the background if needed and shows it on the canvas.
+    pub fn refresh_background(&self) -> anyhow::Result<()> {
+        let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
+        self.state
+            .borrow_mut()
+            .use_and_drop_mut(|s| s.load_background_if_not_loaded(context.clone()))?;
+        let canvas_observer = context
+            .runner
+            .find_object(|o| matches!(&o.content, CnvContent::CanvasObserver(_)))
+            .unwrap();
+        let CnvContent::CanvasObserver(canvas_observer) = &canvas_observer.content else {
+            unreachable!();
+        };
+        canvas_observer.set_background_data(self.state.borrow().background_data.clone())
+    }
+
+    pub fn get_hs_priority_range(&self) -> (isize, isize) {
+        let state = self.state.borrow();
+        (state.min_hs_priority, state.max_hs_priority)
+    }
+
+    pub fn get_transition(&self) -> Option<SceneTransition> {
+        self.transition.clone()
+    }
+
     pub fn has_background_image(&self) -> bool {
         !matches!(&self.state.borrow().background_data, ImageFileData::Empty)
     }
@@ -174,6 +268,18 @@ impl Scene {
         if !self.state.borrow().use_and_drop(|s| s.is_music_playing) {
             return Ok(());
         }
+        if !self.is_music_looping {
+            self.state.borrow_mut().is_music_playing = false;
+            context
+                .runner
+                .events_out
+                .sound
+                .borrow_mut()
+                .use_and_drop_mut(|events| {
+                    events.push_back(SoundEvent::SoundStopped(SoundSource::BackgroundMusic))
+                });
+            return Ok(());
+        }
         context
             .runner
             .events_out
@@ -197,18 +303,10 @@ impl Scene {
 
     pub fn handle_scene_loaded(&self) -> anyhow::Result<()> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
-        self.state.borrow_mut().use_and_drop_mut(|s| {
-            s.load_music_if_not_loaded(context.clone())?;
-            s.load_background_if_not_loaded(context.clone())
-        })?;
-        let canvas_observer = context
-            .runner
-            .find_object(|o| matches!(&o.content, CnvContent::CanvasObserver(_)))
-            .unwrap();
-        let CnvContent::CanvasObserver(canvas_observer) = &canvas_observer.content else {
-            unreachable!();
-        };
-        canvas_observer.set_background_data(self.state.borrow().background_data.clone())?;
+        self.state
+            .borrow_mut()
+            .use_and_drop_mut(|s| s.load_music_if_not_loaded(context.clone()))?;
+        self.refresh_background()?;
         if self.state.borrow().use_and_drop(|s| s.is_music_playing) {
             if let SoundFileData::Loaded(sound_data) =
                 self.state.borrow().use_and_drop(|s| s.music_data.clone())
@@ -326,29 +424,32 @@ impl CnvType for Scene {
                 .borrow_mut()
                 .resume_seq_only()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("RUN") => self
-                .state
-                .borrow_mut()
-                .run(
+            CallableIdentifier::Method("RUN") => {
+                check_argument_count(arguments, 2..)?;
+                self.state.borrow().run(
                     context,
                     arguments[0].to_str(),
                     arguments[1].to_str(),
                     arguments.iter().skip(2).map(|v| v.to_owned()).collect(),
                 )
-                .map(|_| CnvValue::Null), // TODO: return something
+            }
             CallableIdentifier::Method("RUNCLONES") => {
                 self.state.borrow_mut().run_clones().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SETMAXHSPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_max_hs_priority()
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETMINHSPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_min_hs_priority()
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETMAXHSPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_max_hs_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETMINHSPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_min_hs_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETMUSICFREQ") => self
                 .state
                 .borrow_mut()
@@ -359,11 +460,13 @@ impl CnvType for Scene {
                 .borrow_mut()
                 .set_music_pan()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETMUSICVOLUME") => self
-                .state
-                .borrow_mut()
-                .set_music_volume(arguments[0].to_int() as usize)
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETMUSICVOLUME") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_music_volume(arguments[0].to_int() as usize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("STARTMUSIC") => self
                 .state
                 .borrow_mut()
@@ -429,48 +532,27 @@ impl CnvType for Scene {
             .map(parse_datetime)
             .transpose()?;
         let music = properties.remove("MUSIC").and_then(discard_if_empty);
-        let path = properties.remove("PATH").and_then(discard_if_empty);
-        let version = properties.remove("VERSION").and_then(discard_if_empty);
-        let on_activate = properties
-            .remove("ONACTIVATE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_deactivate = properties
-            .remove("ONDEACTIVATE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_do_modal = properties
-            .remove("ONDOMODAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_music_looped = properties
-            .remove("ONMUSICLOOPED")
+        let music_loop = properties
+            .remove("MUSICLOOP")
             .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_restart = properties
-            .remove("ONRESTART")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
+            .map(parse_bool)
             .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
+        let path = properties.remove("PATH").and_then(discard_if_empty);
+        let transition = properties.remove("TRANSITION").and_then(discard_if_empty);
+        let transition_time = properties
+            .remove("TRANSITIONTIME")
             .and_then(discard_if_empty)
-            .map(parse_event_handler)
+            .map(parse_i32)
             .transpose()?;
+        let version = properties.remove("VERSION").and_then(discard_if_empty);
+        let on_activate = parse_event_handlers(&properties, "ONACTIVATE")?;
+        let on_deactivate = parse_event_handlers(&properties, "ONDEACTIVATE")?;
+        let on_do_modal = parse_event_handlers(&properties, "ONDOMODAL")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_music_looped = parse_event_handlers(&properties, "ONMUSICLOOPED")?;
+        let on_restart = parse_event_handlers(&properties, "ONRESTART")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Scene(Self::from_initial_properties(
             parent,
             SceneProperties {
@@ -483,7 +565,10 @@ impl CnvType for Scene {
                 dlls,
                 last_modify_time,
                 music,
+                music_loop,
                 path,
+                transition,
+                transition_time,
                 version,
                 on_activate,
                 on_deactivate,
@@ -537,12 +622,12 @@ impl SceneState {
 
     pub fn get_max_hs_priority(&self) -> anyhow::Result<isize> {
         // GETMAXHSPRIORITY
-        todo!()
+        Ok(self.max_hs_priority)
     }
 
     pub fn get_min_hs_priority(&self) -> anyhow::Result<isize> {
         // GETMINHSPRIORITY
-        todo!()
+        Ok(self.min_hs_priority)
     }
 
     pub fn get_music_volume(&self) -> anyhow::Result<usize> {
@@ -606,28 +691,34 @@ impl SceneState {
     }
 
     pub fn run(
-        &mut self,
+        &self,
         context: RunnerContext,
         object_name: String,
         method_name: String,
         arguments: Vec<CnvValue>,
-    ) -> anyhow::Result<()> {
-        // RUN
-        let Some(object) = context.runner.get_object(&object_name) else {
+    ) -> anyhow::Result<CnvValue> {
+        // RUN (STRING, STRING, [...])
+        let scene_object = context.current_object.clone();
+        let object = context
+            .runner
+            .scripts
+            .borrow()
+            .iter()
+            .filter(|s| {
+                s.parent_object
+                    .as_ref()
+                    .is_some_and(|p| Arc::ptr_eq(p, &scene_object))
+            })
+            .find_map(|s| s.get_object(&object_name))
+            .or_else(|| context.runner.get_object(&object_name));
+        let Some(object) = object else {
             return Err(RunnerError::ObjectNotFound { name: object_name }.into());
         };
-        let evt_context = RunnerContext::new(&context.runner, &object, &object, &arguments);
-        context
-            .runner
-            .internal_events
-            .borrow_mut()
-            .use_and_drop_mut(move |events| {
-                events.push_back(InternalEvent {
-                    context: evt_context,
-                    callable: CallableIdentifierOwned::Method(method_name),
-                })
-            });
-        Ok(())
+        object.call_method(
+            CallableIdentifier::Method(&method_name),
+            &arguments,
+            Some(context.with_arguments(arguments.clone())),
+        )
     }
 
     pub fn run_clones(&mut self) -> anyhow::Result<()> {
@@ -635,14 +726,16 @@ impl SceneState {
         todo!()
     }
 
-    pub fn set_max_hs_priority(&mut self) -> anyhow::Result<()> {
-        // SETMAXHSPRIORITY
-        todo!()
+    pub fn set_max_hs_priority(&mut self, priority: isize) -> anyhow::Result<()> {
+        // SETMAXHSPRIORITY (INTEGER)
+        self.max_hs_priority = priority;
+        Ok(())
     }
 
-    pub fn set_min_hs_priority(&mut self) -> anyhow::Result<()> {
-        // SETMINHSPRIORITY
-        todo!()
+    pub fn set_min_hs_priority(&mut self, priority: isize) -> anyhow::Result<()> {
+        // SETMINHSPRIORITY (INTEGER)
+        self.min_hs_priority = priority;
+        Ok(())
     }
 
     pub fn set_music_freq(&mut self) -> anyhow::Result<()> {
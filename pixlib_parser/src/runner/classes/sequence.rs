@@ -4,9 +4,9 @@ use std::{any::Any, cell::RefCell};
 use ::rand::{seq::SliceRandom, thread_rng};
 use xxhash_rust::xxh3::xxh3_64;
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler};
+use super::super::parsers::{discard_if_empty, parse_event_handlers};
 
 use crate::common::Position;
 use crate::parser::seq_parser::{SeqBuilder, SeqEntry, SeqMode, SeqParser, SeqType};
@@ -21,9 +21,9 @@ pub struct SequenceProperties {
     // SEQUENCE
     pub filename: Option<String>, // FILENAME
 
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
     pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
 }
@@ -55,9 +55,9 @@ struct SequenceQueue {
 
 #[derive(Debug, Clone)]
 pub struct SequenceEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
     pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
 }
@@ -65,17 +65,11 @@ pub struct SequenceEventHandlers {
 impl EventHandler for SequenceEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONFINISHED" => argument
-                .and_then(|a| self.on_finished.get(a))
-                .or(self.on_finished.get("")),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
-            "ONSTARTED" => argument
-                .and_then(|a| self.on_started.get(a))
-                .or(self.on_started.get("")),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONFINISHED" => get_matching_handler(&self.on_finished, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONSTARTED" => get_matching_handler(&self.on_started, argument),
             _ => None,
         }
     }
@@ -112,8 +106,51 @@ impl Sequence {
         sequence
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.state
+            .borrow()
+            .file_data
+            .get_filename()
+            .map(|f| f.to_owned())
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops the decoded sequence or the current sound if they come from the given file,
+    /// so they are loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let script = self.parent.parent.as_ref();
+        let game_paths = &script.runner.game_paths;
+        let mut state = self.state.borrow_mut();
+        let mut invalidated = false;
+        if let Some(referenced) = state.file_data.get_filename().map(|f| f.to_owned()) {
+            let resolved_paths =
+                game_paths.get_scene_asset_paths(&script.path.with_file_path(&referenced));
+            if is_same_file(&resolved_paths, filename) {
+                state.file_data = SequenceFileData::NotLoaded(referenced);
+                invalidated = true;
+            }
+        }
+        if let Some(referenced) = state.current_sound.get_filename().map(|f| f.to_owned()) {
+            let resolved_paths =
+                game_paths.get_sound_paths(&script.path.with_file_path(&referenced));
+            if is_same_file(&resolved_paths, filename) {
+                state.current_sound = SoundFileData::NotLoaded(referenced);
+                invalidated = true;
+            }
+        }
+        invalidated
+    }
+
     pub fn get_currently_played_animation(&self) -> anyhow::Result<Option<Arc<CnvObject>>> {
         self.state.borrow().get_currently_played_animation()
     }
@@ -169,11 +206,13 @@ impl CnvType for Sequence {
             CallableIdentifier::Method("PAUSE") => {
                 self.state.borrow_mut().pause().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("PLAY") => self
-                .state
-                .borrow_mut()
-                .play(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("PLAY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .play(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("RESUME") => {
                 self.state.borrow_mut().resume().map(|_| CnvValue::Null)
             }
@@ -220,40 +259,11 @@ impl CnvType for Sequence {
         mut properties: HashMap<String, String>,
     ) -> Result<CnvContent, TypeParsingError> {
         let filename = properties.remove("FILENAME").and_then(discard_if_empty);
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_finished = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONFINISHED" {
-                on_finished.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONFINISHED^") {
-                on_finished.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_started = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSTARTED" {
-                on_started.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSTARTED^") {
-                on_started.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_finished = parse_event_handlers(&properties, "ONFINISHED")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_started = parse_event_handlers(&properties, "ONSTARTED")?;
         Ok(CnvContent::Sequence(Self::from_initial_properties(
             parent,
             SequenceProperties {
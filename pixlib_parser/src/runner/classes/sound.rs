@@ -3,9 +3,9 @@ use std::{any::Any, cell::RefCell};
 use events::SoundSource;
 use xxhash_rust::xxh3::xxh3_64;
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers};
 
 use crate::{
     common::DroppableRefMut,
@@ -24,12 +24,12 @@ pub struct SoundProperties {
     pub flush_after_played: Option<bool>, // FLUSHAFTERPLAYED
     pub preload: Option<bool>,            // PRELOAD
 
-    pub on_done: Option<Arc<ParsedScript>>, // ONDONE signal
-    pub on_finished: Option<Arc<ParsedScript>>, // ONFINISHED signal
-    pub on_init: Option<Arc<ParsedScript>>, // ONINIT signal
-    pub on_resumed: Option<Arc<ParsedScript>>, // ONRESUMED signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
-    pub on_started: Option<Arc<ParsedScript>>, // ONSTARTED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_resumed: HashMap<String, Arc<ParsedScript>>, // ONRESUMED signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -41,6 +41,9 @@ struct SoundState {
 
     // deduced from methods
     pub is_playing: bool,
+    // the backend keeps one playback per object, so a new one replaces the previous
+    pub playing_instance: Option<usize>,
+    pub last_instance: usize,
     pub is_paused: bool,
     pub music_frequency: usize,
     pub music_volume: f32,
@@ -49,23 +52,23 @@ struct SoundState {
 
 #[derive(Debug, Clone)]
 pub struct SoundEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>,     // ONDONE signal
-    pub on_finished: Option<Arc<ParsedScript>>, // ONFINISHED signal
-    pub on_init: Option<Arc<ParsedScript>>,     // ONINIT signal
-    pub on_resumed: Option<Arc<ParsedScript>>,  // ONRESUMED signal
-    pub on_signal: Option<Arc<ParsedScript>>,   // ONSIGNAL signal
-    pub on_started: Option<Arc<ParsedScript>>,  // ONSTARTED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_finished: HashMap<String, Arc<ParsedScript>>, // ONFINISHED signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_resumed: HashMap<String, Arc<ParsedScript>>, // ONRESUMED signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_started: HashMap<String, Arc<ParsedScript>>, // ONSTARTED signal
 }
 
 impl EventHandler for SoundEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONFINISHED" => self.on_finished.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONRESUMED" => self.on_resumed.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
-            "ONSTARTED" => self.on_started.as_ref(),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONFINISHED" => get_matching_handler(&self.on_finished, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONRESUMED" => get_matching_handler(&self.on_resumed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONSTARTED" => get_matching_handler(&self.on_started, argument),
             _ => None,
         }
     }
@@ -108,8 +111,43 @@ impl Sound {
         sound
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        self.state
+            .borrow()
+            .file_data
+            .get_filename()
+            .map(|f| f.to_owned())
+            .into_iter()
+            .collect()
+    }
+
+    /// Drops the decoded file data if it comes from the given file, so it is loaded again on next use.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        let mut state = self.state.borrow_mut();
+        let Some(referenced) = state.file_data.get_filename().map(|f| f.to_owned()) else {
+            return false;
+        };
+        let script = self.parent.parent.as_ref();
+        let resolved_paths = script
+            .runner
+            .game_paths
+            .get_sound_paths(&script.path.with_file_path(&referenced));
+        if !is_same_file(&resolved_paths, filename) {
+            return false;
+        }
+        state.file_data = SoundFileData::NotLoaded(referenced);
+        true
+    }
+
     pub fn get_sound_to_play(&self) -> anyhow::Result<Option<SoundData>> {
         let state = self.state.borrow();
         if !state.is_playing {
@@ -121,12 +159,23 @@ impl Sound {
         Ok(Some(loaded_data.sound.clone()))
     }
 
-    pub fn handle_finished(&self) -> anyhow::Result<()> {
+    pub fn handle_finished(&self, instance: usize) -> anyhow::Result<()> {
         let context = RunnerContext::new_minimal(&self.parent.parent.runner, &self.parent);
-        self.state.borrow_mut().use_and_drop_mut(|s| {
+        let was_playing = self.state.borrow_mut().use_and_drop_mut(|s| {
+            let Some(playing_instance) = s.playing_instance else {
+                return false;
+            };
+            if instance != 0 && instance != playing_instance {
+                return false;
+            }
+            s.playing_instance = None;
             s.is_playing = false;
             s.is_paused = false;
+            true
         });
+        if !was_playing {
+            return Ok(());
+        }
         context
             .runner
             .internal_events
@@ -179,11 +228,13 @@ impl CnvType for Sound {
             CallableIdentifier::Method("ISPLAYING") => {
                 self.state.borrow().is_playing().map(CnvValue::Bool)
             }
-            CallableIdentifier::Method("LOAD") => self
-                .state
-                .borrow_mut()
-                .load(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("LOAD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .load(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("PAUSE") => self
                 .state
                 .borrow_mut()
@@ -246,36 +297,12 @@ impl CnvType for Sound {
             .and_then(discard_if_empty)
             .map(parse_bool)
             .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_finished = properties
-            .remove("ONFINISHED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_resumed = properties
-            .remove("ONRESUMED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_started = properties
-            .remove("ONSTARTED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_finished = parse_event_handlers(&properties, "ONFINISHED")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_resumed = parse_event_handlers(&properties, "ONRESUMED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_started = parse_event_handlers(&properties, "ONSTARTED")?;
         Ok(CnvContent::Sound(Self::from_initial_properties(
             parent,
             SoundProperties {
@@ -323,7 +350,7 @@ impl Initable for Sound {
 impl SoundState {
     pub fn is_playing(&self) -> anyhow::Result<bool> {
         // ISPLAYING
-        todo!()
+        Ok(self.is_playing)
     }
 
     pub fn load(&mut self, context: RunnerContext, filename: &str) -> anyhow::Result<()> {
@@ -358,6 +385,7 @@ impl SoundState {
                     source: SoundSource::Sound {
                         script_path: context.current_object.parent.path.clone(),
                         object_name: context.current_object.name.clone(),
+                        instance: 0,
                     },
                     sound_data,
                 })
@@ -377,6 +405,7 @@ impl SoundState {
                 events.push_back(SoundEvent::SoundPaused(SoundSource::Sound {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
+                    instance: 0,
                 }))
             });
         context
@@ -402,6 +431,9 @@ impl SoundState {
             return Err(RunnerError::NoSoundDataLoaded(context.current_object.name.clone()).into());
         };
         self.is_playing = true;
+        self.last_instance += 1;
+        let instance = self.last_instance;
+        self.playing_instance = Some(instance);
         context
             .runner
             .events_out
@@ -411,6 +443,7 @@ impl SoundState {
                 events.push_back(SoundEvent::SoundStarted(SoundSource::Sound {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
+                    instance,
                 }))
             });
         context
@@ -438,6 +471,7 @@ impl SoundState {
                 events.push_back(SoundEvent::SoundResumed(SoundSource::Sound {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
+                    instance: 0,
                 }))
             });
         context
@@ -472,6 +506,7 @@ impl SoundState {
         // STOP
         self.is_playing = false;
         self.is_paused = false;
+        self.playing_instance = None;
         context
             .runner
             .events_out
@@ -481,6 +516,7 @@ impl SoundState {
                 events.push_back(SoundEvent::SoundStopped(SoundSource::Sound {
                     script_path: context.current_object.parent.path.clone(),
                     object_name: context.current_object.name.clone(),
+                    instance: 0,
                 }))
             });
         context
@@ -2,9 +2,9 @@ use std::{any::Any, cell::RefCell};
 
 use log::info;
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -22,8 +22,8 @@ pub struct StringVarProperties {
 
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -39,8 +39,8 @@ struct StringVarState {
 pub struct StringVarEventHandlers {
     pub on_brutal_changed: HashMap<String, Arc<ParsedScript>>, // ONBRUTALCHANGED signal
     pub on_changed: HashMap<String, Arc<ParsedScript>>,        // ONCHANGED signal
-    pub on_done: Option<Arc<ParsedScript>>,                    // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,                    // ONINIT signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,           // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,           // ONINIT signal
     pub on_net_changed: HashMap<String, Arc<ParsedScript>>,    // ONNETCHANGED signal
     pub on_signal: HashMap<String, Arc<ParsedScript>>,         // ONSIGNAL signal
 }
@@ -48,20 +48,12 @@ pub struct StringVarEventHandlers {
 impl EventHandler for StringVarEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONBRUTALCHANGED" => argument
-                .and_then(|a| self.on_brutal_changed.get(a))
-                .or(self.on_brutal_changed.get("")),
-            "ONCHANGED" => argument
-                .and_then(|a| self.on_changed.get(a))
-                .or(self.on_changed.get("")),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONNETCHANGED" => argument
-                .and_then(|a| self.on_net_changed.get(a))
-                .or(self.on_net_changed.get("")),
-            "ONSIGNAL" => argument
-                .and_then(|a| self.on_signal.get(a))
-                .or(self.on_signal.get("")),
+            "ONBRUTALCHANGED" => get_matching_handler(&self.on_brutal_changed, argument),
+            "ONCHANGED" => get_matching_handler(&self.on_changed, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONNETCHANGED" => get_matching_handler(&self.on_net_changed, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -100,6 +92,13 @@ impl StringVar {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     pub fn get(&self) -> anyhow::Result<String> {
         self.state.borrow().get(None, None)
     }
@@ -130,11 +129,13 @@ impl CnvType for StringVar {
         //     arguments.iter().join(", ")
         // );
         match name {
-            CallableIdentifier::Method("ADD") => self
-                .state
-                .borrow_mut()
-                .add(context, &arguments[0].to_str())
-                .map(CnvValue::String),
+            CallableIdentifier::Method("ADD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .add(context, &arguments[0].to_str())
+                    .map(CnvValue::String)
+            }
             CallableIdentifier::Method("CLEAR") => self
                 .state
                 .borrow_mut()
@@ -145,24 +146,28 @@ impl CnvType for StringVar {
                 .borrow_mut()
                 .copy_file(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("CUT") => self
-                .state
-                .borrow_mut()
-                .cut(
-                    context,
-                    arguments[0].to_int() as usize,
-                    arguments[1].to_int() as usize,
-                )
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("FIND") => self
-                .state
-                .borrow()
-                .find(
-                    &arguments[0].to_str(),
-                    arguments.get(1).map(|v| v.to_int() as usize),
-                )
-                .map(|v| v.map(|u| u as i32).unwrap_or(-1))
-                .map(CnvValue::Integer),
+            CallableIdentifier::Method("CUT") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .cut(
+                        context,
+                        arguments[0].to_int() as usize,
+                        arguments[1].to_int() as usize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("FIND") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .find(
+                        &arguments[0].to_str(),
+                        arguments.get(1).map(|v| v.to_int() as usize),
+                    )
+                    .map(|v| v.map(|u| u as i32).unwrap_or(-1))
+                    .map(CnvValue::Integer)
+            }
             CallableIdentifier::Method("GET") => self
                 .state
                 .borrow()
@@ -171,21 +176,25 @@ impl CnvType for StringVar {
                     arguments.get(1).map(|v| v.to_int() as usize),
                 )
                 .map(CnvValue::String),
-            CallableIdentifier::Method("INSERTAT") => self
-                .state
-                .borrow_mut()
-                .insert_at(
-                    context,
-                    arguments[0].to_int() as usize,
-                    &arguments[1].to_str(),
-                    arguments.get(2).map(|v| v.to_int() as usize).unwrap_or(1),
-                )
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("ISUPPERLETTER") => self
-                .state
-                .borrow()
-                .is_upper_letter(arguments[0].to_int() as usize)
-                .map(CnvValue::Bool),
+            CallableIdentifier::Method("INSERTAT") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .insert_at(
+                        context,
+                        arguments[0].to_int() as usize,
+                        &arguments[1].to_str(),
+                        arguments.get(2).map(|v| v.to_int() as usize).unwrap_or(1),
+                    )
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("ISUPPERLETTER") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .is_upper_letter(arguments[0].to_int() as usize)
+                    .map(CnvValue::Bool)
+            }
             CallableIdentifier::Method("LENGTH") => self
                 .state
                 .borrow_mut()
@@ -204,49 +213,61 @@ impl CnvType for StringVar {
                 .borrow_mut()
                 .random(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("REPLACE") => self
-                .state
-                .borrow_mut()
-                .replace(context, &arguments[0].to_str(), &arguments[1].to_str())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("REPLACEAT") => self
-                .state
-                .borrow_mut()
-                .replace_at(
-                    context,
-                    arguments[0].to_int() as usize,
-                    &arguments[1].to_str(),
-                )
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("REPLACE") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .replace(context, &arguments[0].to_str(), &arguments[1].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("REPLACEAT") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .replace_at(
+                        context,
+                        arguments[0].to_int() as usize,
+                        &arguments[1].to_str(),
+                    )
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("RESETINI") => self
                 .state
                 .borrow_mut()
                 .reset_ini(context)
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SET") => self
-                .state
-                .borrow_mut()
-                .set(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETDEFAULT") => self
-                .state
-                .borrow_mut()
-                .set_default(context, &arguments[0].to_str())
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SUB") => self
-                .state
-                .borrow_mut()
-                .sub(
-                    context,
-                    arguments[0].to_int() as usize,
-                    arguments[1].to_int() as usize,
-                )
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SWITCH") => self
-                .state
-                .borrow_mut()
-                .switch(context, &arguments[0].to_str(), &arguments[1].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SET") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETDEFAULT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_default(context, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SUB") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .sub(
+                        context,
+                        arguments[0].to_int() as usize,
+                        arguments[1].to_int() as usize,
+                    )
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SWITCH") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .switch(context, &arguments[0].to_str(), &arguments[1].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("UPPER") => self
                 .state
                 .borrow_mut()
@@ -286,49 +307,12 @@ impl CnvType for StringVar {
             .map(parse_bool)
             .transpose()?;
         let value = properties.remove("VALUE");
-        let mut on_brutal_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONBRUTALCHANGED" {
-                on_brutal_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONBRUTALCHANGED^") {
-                on_brutal_changed
-                    .insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONCHANGED" {
-                on_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONCHANGED^") {
-                on_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_net_changed = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONNETCHANGED" {
-                on_net_changed.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONNETCHANGED^") {
-                on_net_changed.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
-        let mut on_signal = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONSIGNAL" {
-                on_signal.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONSIGNAL^") {
-                on_signal.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_brutal_changed = parse_event_handlers(&properties, "ONBRUTALCHANGED")?;
+        let on_changed = parse_event_handlers(&properties, "ONCHANGED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_net_changed = parse_event_handlers(&properties, "ONNETCHANGED")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::String(StringVar::from_initial_properties(
             parent,
             StringVarProperties {
@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_event_handler, STRUCT_FIELDS_REGEX};
+use super::super::parsers::{discard_if_empty, parse_event_handlers, STRUCT_FIELDS_REGEX};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -15,9 +15,9 @@ pub struct StructProperties {
     // STRUCT
     pub fields: Option<Vec<(String, TypeName)>>,
 
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -28,17 +28,17 @@ struct StructState {
 
 #[derive(Debug, Clone)]
 pub struct StructEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
 }
 
 impl EventHandler for StructEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -69,6 +69,31 @@ impl Struct {
             fields: props.fields.unwrap_or_default(),
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    /// Lists the declared fields in declaration order, paired with their current values.
+    pub fn fields(&self) -> Vec<(String, CnvValue)> {
+        let state = self.state.borrow();
+        self.fields
+            .iter()
+            .map(|(field_name, type_name)| {
+                let value = state
+                    .fields
+                    .get(field_name)
+                    .cloned()
+                    .unwrap_or_else(|| get_default_value(type_name));
+                (field_name.clone(), value)
+            })
+            .collect()
+    }
 }
 
 impl CnvType for Struct {
@@ -91,19 +116,31 @@ impl CnvType for Struct {
         context: RunnerContext,
     ) -> anyhow::Result<CnvValue> {
         match name {
-            CallableIdentifier::Method("GETFIELD") => self
-                .state
-                .borrow_mut()
-                .get_field(&arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("GETFIELD") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow()
+                    .get_field(context, &self.fields, &arguments[0].to_str())
+            }
             CallableIdentifier::Method("SET") => {
-                self.state.borrow_mut().set().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(context, &self.fields, &arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETFIELD") => {
+                check_argument_count(arguments, 2..)?;
+                self.state
+                    .borrow_mut()
+                    .set_field(
+                        context,
+                        &self.fields,
+                        &arguments[0].to_str(),
+                        arguments[1].clone(),
+                    )
+                    .map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SETFIELD") => self
-                .state
-                .borrow_mut()
-                .set_field(&arguments[0].to_str(), arguments[1].clone())
-                .map(|_| CnvValue::Null),
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -137,21 +174,9 @@ impl CnvType for Struct {
                     })
                     .collect()
             });
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Struct(Self::from_initial_properties(
             parent,
             StructProperties {
@@ -181,18 +206,140 @@ impl Initable for Struct {
 }
 
 impl StructState {
-    pub fn get_field(&self, _name: &str) -> anyhow::Result<CnvValue> {
-        // GETFIELD
-        todo!()
+    pub fn get_field(
+        &self,
+        context: RunnerContext,
+        fields: &[(String, TypeName)],
+        name: &str,
+    ) -> anyhow::Result<CnvValue> {
+        // GETFIELD (STRING)
+        let (field_name, type_name) = find_field(&context, fields, name)?;
+        Ok(self
+            .fields
+            .get(field_name)
+            .cloned()
+            .unwrap_or_else(|| get_default_value(type_name)))
     }
 
-    pub fn set(&mut self) -> anyhow::Result<()> {
-        // SET
-        todo!()
+    pub fn set(
+        &mut self,
+        context: RunnerContext,
+        fields: &[(String, TypeName)],
+        source_name: &str,
+    ) -> anyhow::Result<()> {
+        // SET (STRING)
+        let source_object =
+            context
+                .runner
+                .get_object(source_name)
+                .ok_or(RunnerError::ObjectNotFound {
+                    name: source_name.to_owned(),
+                })?;
+        if Arc::ptr_eq(&source_object, &context.current_object) {
+            return Ok(());
+        }
+        let CnvContent::Struct(ref source) = &source_object.content else {
+            return Err(RunnerError::UnexpectedType {
+                object_name: source_object.name.clone(),
+                expected: "STRUCT".to_owned(),
+                actual: source_object.content.get_type_id().to_owned(),
+            }
+            .into());
+        };
+        let source_state = source.state.borrow();
+        let mut values = Vec::new();
+        for (field_name, type_name) in fields.iter() {
+            let Some((source_field_name, source_type_name)) = source
+                .fields
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(field_name))
+            else {
+                continue;
+            };
+            let value = source_state
+                .fields
+                .get(source_field_name)
+                .cloned()
+                .unwrap_or_else(|| get_default_value(source_type_name));
+            values.push((
+                field_name.clone(),
+                coerce_value(&context, field_name, type_name, value)?,
+            ));
+        }
+        self.fields.extend(values);
+        Ok(())
+    }
+
+    pub fn set_field(
+        &mut self,
+        context: RunnerContext,
+        fields: &[(String, TypeName)],
+        name: &str,
+        value: CnvValue,
+    ) -> anyhow::Result<()> {
+        // SETFIELD (STRING, ANY)
+        let (field_name, type_name) = find_field(&context, fields, name)?;
+        let value = coerce_value(&context, field_name, type_name, value)?;
+        self.fields.insert(field_name.clone(), value);
+        Ok(())
     }
+}
+
+fn find_field<'a>(
+    context: &RunnerContext,
+    fields: &'a [(String, TypeName)],
+    name: &str,
+) -> anyhow::Result<&'a (String, TypeName)> {
+    fields
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .ok_or(
+            RunnerError::FieldNotFound {
+                object_name: context.current_object.name.clone(),
+                field_name: name.to_owned(),
+            }
+            .into(),
+        )
+}
 
-    pub fn set_field(&mut self, _name: &str, _value: CnvValue) -> anyhow::Result<()> {
-        // SETFIELD
-        todo!()
+fn get_default_value(type_name: &str) -> CnvValue {
+    match type_name.to_ascii_uppercase().as_ref() {
+        "INTEGER" => CnvValue::Integer(0),
+        "DOUBLE" => CnvValue::Double(0.0),
+        "BOOL" => CnvValue::Bool(false),
+        "STRING" => CnvValue::String(String::new()),
+        _ => CnvValue::Null,
     }
 }
+
+/// Converts the value to the field type, failing for strings not representing that type.
+fn coerce_value(
+    context: &RunnerContext,
+    field_name: &str,
+    type_name: &str,
+    value: CnvValue,
+) -> anyhow::Result<CnvValue> {
+    let coerced = match (type_name.to_ascii_uppercase().as_ref(), &value) {
+        ("INTEGER", CnvValue::String(s)) => s.trim().parse().ok().map(CnvValue::Integer),
+        ("INTEGER", _) => Some(CnvValue::Integer(value.to_int())),
+        ("DOUBLE", CnvValue::String(s)) => s.trim().parse().ok().map(CnvValue::Double),
+        ("DOUBLE", _) => Some(CnvValue::Double(value.to_dbl())),
+        ("BOOL", CnvValue::String(s)) => match s.to_ascii_uppercase().as_ref() {
+            "TRUE" => Some(CnvValue::Bool(true)),
+            "FALSE" => Some(CnvValue::Bool(false)),
+            _ => None,
+        },
+        ("BOOL", _) => Some(CnvValue::Bool(value.to_bool())),
+        ("STRING", _) => Some(CnvValue::String(value.to_str())),
+        _ => Some(value.clone()),
+    };
+    coerced.ok_or(
+        RunnerError::InvalidFieldValue {
+            object_name: context.current_object.name.clone(),
+            field_name: field_name.to_owned(),
+            type_name: type_name.to_owned(),
+            value,
+        }
+        .into(),
+    )
+}
@@ -48,6 +48,13 @@ impl System {
             system: props.system.unwrap_or_default(),
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
 }
 
 impl CnvType for System {
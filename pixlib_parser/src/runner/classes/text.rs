@@ -1,9 +1,10 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
 use super::super::parsers::{
-    discard_if_empty, parse_bool, parse_event_handler, parse_i32, parse_rect, ReferenceRect,
+    discard_if_empty, parse_bool, parse_color, parse_event_handlers, parse_i32, parse_rect,
+    ReferenceRect,
 };
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
@@ -27,11 +28,11 @@ pub struct TextProperties {
     pub visible: Option<bool>,                 // VISIBLE
     pub vertical_justify: Option<bool>,        // VJUSTIFY
 
-    pub on_collision: Option<Arc<ParsedScript>>, // ONCOLLISION signal
-    pub on_collision_finished: Option<Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,      // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,      // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>,    // ONSIGNAL signal
+    pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
+    pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,      // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,      // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>,    // ONSIGNAL signal
 }
 
 #[derive(Debug, Clone, Default)]
@@ -48,27 +49,27 @@ struct TextState {
 
     // deduced from methods
     pub opacity: usize,
-    pub color: Option<String>,
+    pub color: Option<(u8, u8, u8)>,
     pub clipping: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct TextEventHandlers {
-    pub on_collision: Option<Arc<ParsedScript>>, // ONCOLLISION signal
-    pub on_collision_finished: Option<Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
-    pub on_done: Option<Arc<ParsedScript>>,      // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,      // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>,    // ONSIGNAL signal
+    pub on_collision: HashMap<String, Arc<ParsedScript>>, // ONCOLLISION signal
+    pub on_collision_finished: HashMap<String, Arc<ParsedScript>>, // ONCOLLISIONFINISHED signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>,      // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>,      // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>,    // ONSIGNAL signal
 }
 
 impl EventHandler for TextEventHandlers {
-    fn get(&self, name: &str, _argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
+    fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONCOLLISION" => self.on_collision.as_ref(),
-            "ONCOLLISIONFINISHED" => self.on_collision_finished.as_ref(),
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
+            "ONCOLLISION" => get_matching_handler(&self.on_collision, argument),
+            "ONCOLLISIONFINISHED" => get_matching_handler(&self.on_collision_finished, argument),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
             _ => None,
         }
     }
@@ -111,6 +112,135 @@ impl Text {
             should_draw_to_canvas: props.to_canvas.unwrap_or(true),
         }
     }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    // custom
+
+    pub fn get_color(&self) -> Option<(u8, u8, u8)> {
+        self.state.borrow().color
+    }
+
+    /// Draws the current text with the assigned font and color.
+    ///
+    /// Texts with a rect are drawn onto a buffer of the rect size, see [`Text::layout_lines`].
+    pub fn render(&self) -> anyhow::Result<Option<RenderedText>> {
+        let Some(rect) = self.get_rect()? else {
+            let state = self.state.borrow();
+            return self.with_font(|font| font.render(&state.text, state.color));
+        };
+        let Some(lines) = self.layout_lines()? else {
+            return Ok(None);
+        };
+        let color = self.state.borrow().color;
+        let (width, height) = (rect.get_width(), rect.get_height());
+        let mut data = vec![0u8; width * height * 4];
+        self.with_font(|font| {
+            for (line, offset_y) in lines.iter() {
+                let ((line_width, line_height), line_data) = font.render(line, color)?;
+                let copied_width = line_width.min(width) * 4;
+                for y in 0..line_height.min(height.saturating_sub(*offset_y)) {
+                    let source = y * line_width * 4;
+                    let target = (offset_y + y) * width * 4;
+                    data[target..(target + copied_width)]
+                        .copy_from_slice(&line_data[source..(source + copied_width)]);
+                }
+            }
+            Ok(((width, height), data))
+        })
+    }
+
+    /// Breaks the text into lines and places them vertically.
+    ///
+    /// With a rect set, the text is word-wrapped to its width, centered in it when justified
+    /// vertically and clipped to the lines fitting within its height. Returns the lines along
+    /// with their offsets from the top.
+    pub fn layout_lines(&self) -> anyhow::Result<Option<Vec<(String, usize)>>> {
+        let rect = self.get_rect()?;
+        let (text, is_centered) = {
+            let state = self.state.borrow();
+            (state.text.clone(), state.is_justified_vertically)
+        };
+        self.with_font(|font| {
+            let line_height = font.get_line_height()?;
+            let Some(rect) = rect else {
+                return Ok(text
+                    .lines()
+                    .enumerate()
+                    .map(|(i, line)| (line.to_owned(), i * line_height))
+                    .collect());
+            };
+            let mut lines = font.wrap_lines(&text, rect.get_width())?;
+            let top = if is_centered {
+                rect.get_height().saturating_sub(lines.len() * line_height) / 2
+            } else {
+                0
+            };
+            if let Some(visible_count) = rect
+                .get_height()
+                .saturating_sub(top)
+                .checked_div(line_height)
+            {
+                lines.truncate(visible_count);
+            }
+            Ok(lines
+                .into_iter()
+                .enumerate()
+                .map(|(i, line)| (line, top + i * line_height))
+                .collect())
+        })
+    }
+
+    pub fn get_rect(&self) -> anyhow::Result<Option<Rect>> {
+        let Some(reference_rect) = self.state.borrow().rect.clone() else {
+            return Ok(None);
+        };
+        match reference_rect {
+            ReferenceRect::Literal(rect) => Ok(Some(rect)),
+            ReferenceRect::Reference(reference) => {
+                let object = self
+                    .parent
+                    .parent
+                    .runner
+                    .get_object(&reference)
+                    .ok_or(RunnerError::ObjectNotFound { name: reference })?;
+                let graphics: &dyn GeneralGraphics = match &object.content {
+                    CnvContent::Animation(a) => a,
+                    CnvContent::Image(i) => i,
+                    _ => return Err(RunnerError::ExpectedGraphicsObject.into()),
+                };
+                graphics.get_rect()
+            }
+        }
+    }
+
+    fn with_font<T>(
+        &self,
+        f: impl FnOnce(&Font) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        let Some(font_name) = self.state.borrow().font.clone() else {
+            return Ok(None);
+        };
+        let font_object = self.parent.parent.runner.get_object(&font_name).ok_or(
+            RunnerError::ObjectNotFound {
+                name: font_name.clone(),
+            },
+        )?;
+        let CnvContent::Font(font) = &font_object.content else {
+            return Err(RunnerError::UnexpectedType {
+                object_name: font_name,
+                expected: "FONT".to_owned(),
+                actual: font_object.content.get_type_id().to_owned(),
+            }
+            .into());
+        };
+        f(font).map(Some)
+    }
 }
 
 impl CnvType for Text {
@@ -211,7 +341,22 @@ impl CnvType for Text {
                 .set_clipping()
                 .map(|_| CnvValue::Null),
             CallableIdentifier::Method("SETCOLOR") => {
-                self.state.borrow_mut().set_color().map(|_| CnvValue::Null)
+                check_argument_count(arguments, 1..)?;
+                let color = if arguments.len() >= 3 {
+                    parse_color(
+                        arguments[..3]
+                            .iter()
+                            .map(|v| v.to_str())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    )
+                } else {
+                    parse_color(arguments[0].to_str())
+                }?;
+                self.state
+                    .borrow_mut()
+                    .set_color(color)
+                    .map(|_| CnvValue::Null)
             }
             CallableIdentifier::Method("SETFONT") => {
                 self.state.borrow_mut().set_font().map(|_| CnvValue::Null)
@@ -231,19 +376,23 @@ impl CnvType for Text {
                 .borrow_mut()
                 .set_position()
                 .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETPRIORITY") => self
-                .state
-                .borrow_mut()
-                .set_priority(arguments[0].to_int() as isize)
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETPRIORITY") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_priority(arguments[0].to_int() as isize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETRECT") => {
                 self.state.borrow_mut().set_rect().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SETTEXT") => self
-                .state
-                .borrow_mut()
-                .set_text(arguments[0].to_str())
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SETTEXT") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_text(arguments[0].to_str())
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Method("SETTEXTDOUBLE") => self
                 .state
                 .borrow_mut()
@@ -326,31 +475,11 @@ impl CnvType for Text {
             .and_then(discard_if_empty)
             .map(parse_bool)
             .transpose()?;
-        let on_collision = properties
-            .remove("ONCOLLISION")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_collision_finished = properties
-            .remove("ONCOLLISIONFINISHED")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
+        let on_collision = parse_event_handlers(&properties, "ONCOLLISION")?;
+        let on_collision_finished = parse_event_handlers(&properties, "ONCOLLISIONFINISHED")?;
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
         Ok(CnvContent::Text(Self::from_initial_properties(
             parent,
             TextProperties {
@@ -487,9 +616,10 @@ impl TextState {
         todo!()
     }
 
-    pub fn set_color(&mut self) -> anyhow::Result<()> {
+    pub fn set_color(&mut self, color: (u8, u8, u8)) -> anyhow::Result<()> {
         // SETCOLOR
-        todo!()
+        self.color = Some(color);
+        Ok(())
     }
 
     pub fn set_font(&mut self) -> anyhow::Result<()> {
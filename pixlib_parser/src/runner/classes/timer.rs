@@ -1,8 +1,8 @@
 use std::{any::Any, cell::RefCell};
 
-use super::super::content::EventHandler;
+use super::super::content::{get_matching_handler, EventHandler};
 use super::super::initable::Initable;
-use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handler, parse_i32};
+use super::super::parsers::{discard_if_empty, parse_bool, parse_event_handlers, parse_i32};
 
 use crate::{common::DroppableRefMut, parser::ast::ParsedScript, runner::InternalEvent};
 
@@ -17,9 +17,9 @@ pub struct TimerProperties {
     pub enabled: Option<bool>, // ENABLED
     pub ticks: Option<i32>,    // TICKS
 
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_tick: HashMap<String, Arc<ParsedScript>>, // ONTICK signal
 }
 
@@ -37,21 +37,19 @@ struct TimerState {
 
 #[derive(Debug, Clone)]
 pub struct TimerEventHandlers {
-    pub on_done: Option<Arc<ParsedScript>>,   // ONDONE signal
-    pub on_init: Option<Arc<ParsedScript>>,   // ONINIT signal
-    pub on_signal: Option<Arc<ParsedScript>>, // ONSIGNAL signal
+    pub on_done: HashMap<String, Arc<ParsedScript>>, // ONDONE signal
+    pub on_init: HashMap<String, Arc<ParsedScript>>, // ONINIT signal
+    pub on_signal: HashMap<String, Arc<ParsedScript>>, // ONSIGNAL signal
     pub on_tick: HashMap<String, Arc<ParsedScript>>, // ONTICK signal
 }
 
 impl EventHandler for TimerEventHandlers {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>> {
         match name {
-            "ONDONE" => self.on_done.as_ref(),
-            "ONINIT" => self.on_init.as_ref(),
-            "ONSIGNAL" => self.on_signal.as_ref(),
-            "ONTICK" => argument
-                .and_then(|a| self.on_tick.get(a))
-                .or(self.on_tick.get("")),
+            "ONDONE" => get_matching_handler(&self.on_done, argument),
+            "ONINIT" => get_matching_handler(&self.on_init, argument),
+            "ONSIGNAL" => get_matching_handler(&self.on_signal, argument),
+            "ONTICK" => get_matching_handler(&self.on_tick, argument),
             _ => None,
         }
     }
@@ -92,6 +90,13 @@ impl Timer {
         }
     }
 
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
     // custom
 
     pub fn step(&self, seconds: f64) -> anyhow::Result<()> {
@@ -141,16 +146,20 @@ impl CnvType for Timer {
             CallableIdentifier::Method("RESUME") => {
                 self.state.borrow_mut().resume().map(|_| CnvValue::Null)
             }
-            CallableIdentifier::Method("SET") => self
-                .state
-                .borrow_mut()
-                .set(arguments[0].to_int() as f64)
-                .map(|_| CnvValue::Null),
-            CallableIdentifier::Method("SETELAPSE") => self
-                .state
-                .borrow_mut()
-                .set_elapse(arguments[0].to_int() as usize)
-                .map(|_| CnvValue::Null),
+            CallableIdentifier::Method("SET") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set(arguments[0].to_int() as f64)
+                    .map(|_| CnvValue::Null)
+            }
+            CallableIdentifier::Method("SETELAPSE") => {
+                check_argument_count(arguments, 1..)?;
+                self.state
+                    .borrow_mut()
+                    .set_elapse(arguments[0].to_int() as usize)
+                    .map(|_| CnvValue::Null)
+            }
             CallableIdentifier::Event(event_name) => {
                 if let Some(code) = self
                     .event_handlers
@@ -188,29 +197,10 @@ impl CnvType for Timer {
             .and_then(discard_if_empty)
             .map(parse_i32)
             .transpose()?;
-        let on_done = properties
-            .remove("ONDONE")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_init = properties
-            .remove("ONINIT")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let on_signal = properties
-            .remove("ONSIGNAL")
-            .and_then(discard_if_empty)
-            .map(parse_event_handler)
-            .transpose()?;
-        let mut on_tick = HashMap::new();
-        for (k, v) in properties.iter() {
-            if k == "ONTICK" {
-                on_tick.insert(String::from(""), parse_event_handler(v.to_owned())?);
-            } else if let Some(argument) = k.strip_prefix("ONTICK^") {
-                on_tick.insert(String::from(argument), parse_event_handler(v.to_owned())?);
-            }
-        }
+        let on_done = parse_event_handlers(&properties, "ONDONE")?;
+        let on_init = parse_event_handlers(&properties, "ONINIT")?;
+        let on_signal = parse_event_handlers(&properties, "ONSIGNAL")?;
+        let on_tick = parse_event_handlers(&properties, "ONTICK")?;
         Ok(CnvContent::Timer(Self::from_initial_properties(
             parent,
             TimerProperties {
@@ -312,7 +302,8 @@ impl TimerState {
             return Ok(());
         }
         self.current_ms -= duration_ms;
-        while self.current_ms < 0.0
+        // an interval ending exactly at this step counts as elapsed
+        while self.current_ms <= 0.0
             && timer
                 .max_ticks
                 .map(|max| self.current_ticks < max)
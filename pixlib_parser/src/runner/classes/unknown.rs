@@ -0,0 +1,86 @@
+use std::any::Any;
+
+use super::super::*;
+use super::*;
+
+/// Placeholder for objects of types not supported by the runner yet.
+///
+/// It keeps the declared type and properties so that the rest of the script stays loadable.
+#[derive(Debug, Clone)]
+pub struct Unknown {
+    parent: Arc<CnvObject>,
+
+    type_name: String,
+    properties: HashMap<String, String>,
+}
+
+impl Unknown {
+    pub fn from_type_and_properties(
+        parent: Arc<CnvObject>,
+        type_name: String,
+        properties: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            parent,
+            type_name,
+            properties,
+        }
+    }
+
+    pub fn with_parent(&self, parent: Arc<CnvObject>) -> Self {
+        Self {
+            parent,
+            ..self.clone()
+        }
+    }
+
+    pub fn get_type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn get_properties(&self) -> &HashMap<String, String> {
+        &self.properties
+    }
+}
+
+impl CnvType for Unknown {
+    fn get_type_id(&self) -> &'static str {
+        "UNKNOWN"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self as &dyn Any
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self as &mut dyn Any
+    }
+
+    fn call_method(
+        &self,
+        name: CallableIdentifier,
+        _arguments: &[CnvValue],
+        _context: RunnerContext,
+    ) -> anyhow::Result<CnvValue> {
+        match name {
+            CallableIdentifier::Event(_) => Ok(CnvValue::Null),
+            CallableIdentifier::Method(method) => Err(RunnerError::Unimplemented {
+                object_name: self.parent.name.clone(),
+                type_name: self.type_name.clone(),
+                method: method.to_owned(),
+            }
+            .into()),
+        }
+    }
+
+    fn new_content(
+        parent: Arc<CnvObject>,
+        properties: HashMap<String, String>,
+    ) -> Result<CnvContent, TypeParsingError> {
+        Ok(CnvContent::Unknown(Self::from_type_and_properties(
+            parent,
+            String::new(),
+            properties,
+        )))
+    }
+}
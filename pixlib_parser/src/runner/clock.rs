@@ -0,0 +1,36 @@
+/// Engine clock accumulating the real time reported by the host application.
+///
+/// The host may tick at any rate. Objects keep their own frame rates and derive how many frames
+/// are due from the time they accumulated, using [`Clock::take_frames`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clock {
+    elapsed_seconds: f64,
+}
+
+impl Clock {
+    /// Registers time elapsed since the previous tick and returns it sanitized:
+    /// negative and non-finite deltas are treated as no time passing.
+    pub fn advance(&mut self, seconds: f64) -> f64 {
+        let seconds = if seconds.is_finite() {
+            seconds.max(0.0)
+        } else {
+            0.0
+        };
+        self.elapsed_seconds += seconds;
+        seconds
+    }
+
+    pub fn get_elapsed_seconds(&self) -> f64 {
+        self.elapsed_seconds
+    }
+
+    /// Consumes whole frames from the accumulated time, leaving the remainder in place.
+    pub fn take_frames(accumulated_seconds: &mut f64, frame_duration: f64) -> usize {
+        if !frame_duration.is_finite() || frame_duration <= 0.0 {
+            return 0;
+        }
+        let frames = (*accumulated_seconds / frame_duration).floor().max(0.0);
+        *accumulated_seconds -= frames * frame_duration;
+        frames as usize
+    }
+}
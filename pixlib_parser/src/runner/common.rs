@@ -1,9 +1,11 @@
-use std::{fmt::Display, sync::Arc};
+use std::{collections::HashMap, fmt::Display, sync::Arc};
 
 use pixlib_formats::file_formats::ann::LoopingSettings;
 
 use crate::parser::seq_parser::SeqEntry;
 
+use super::path::Path;
+
 #[derive(Debug, Clone)]
 pub enum CallableIdentifier<'a> {
     Method(&'a str),
@@ -19,7 +21,7 @@ impl<'a> CallableIdentifier<'a> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CallableIdentifierOwned {
     Method(String),
     Event(String),
@@ -44,6 +46,24 @@ impl Display for CallableIdentifierOwned {
     }
 }
 
+/// Checks whether a file, named as it was read from the file system,
+/// is one of the paths an asset reference resolves to.
+pub fn is_same_file(resolved_paths: &[Path], filename: &str) -> bool {
+    resolved_paths.contains(&Path::from(filename))
+}
+
+/// Scales the alpha channel of RGBA8888 pixel data by the given opacity (0-255).
+pub fn apply_opacity(pixel_data: Arc<Vec<u8>>, opacity: usize) -> Arc<Vec<u8>> {
+    if opacity >= 255 {
+        return pixel_data;
+    }
+    let mut pixel_data = (*pixel_data).clone();
+    for alpha in pixel_data.iter_mut().skip(3).step_by(4) {
+        *alpha = (*alpha as usize * opacity / 255) as u8;
+    }
+    Arc::new(pixel_data)
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum SoundFileData {
     #[default]
@@ -52,6 +72,16 @@ pub enum SoundFileData {
     Loaded(LoadedSound),
 }
 
+impl SoundFileData {
+    pub fn get_filename(&self) -> Option<&str> {
+        match self {
+            SoundFileData::Empty => None,
+            SoundFileData::NotLoaded(filename) => Some(filename),
+            SoundFileData::Loaded(loaded) => loaded.filename.as_deref(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadedSound {
     pub filename: Option<String>,
@@ -64,6 +94,23 @@ pub struct SoundData {
     pub data: Arc<Vec<u8>>, // RGBA8888
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundParameters {
+    pub volume: f32,              // 0.0 (muted) to 1.0 (original)
+    pub pan: f32,                 // -1.0 (left) to 1.0 (right)
+    pub frequency: Option<usize>, // sample rate override in Hz
+}
+
+impl Default for SoundParameters {
+    fn default() -> Self {
+        Self {
+            volume: 1f32,
+            pan: 0f32,
+            frequency: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub enum SequenceFileData {
     #[default]
@@ -72,6 +119,16 @@ pub enum SequenceFileData {
     Loaded(LoadedSequence),
 }
 
+impl SequenceFileData {
+    pub fn get_filename(&self) -> Option<&str> {
+        match self {
+            SequenceFileData::Empty => None,
+            SequenceFileData::NotLoaded(filename) => Some(filename),
+            SequenceFileData::Loaded(loaded) => loaded.filename.as_deref(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadedSequence {
     pub filename: Option<String>,
@@ -104,7 +161,18 @@ pub enum ImageFileData {
     Loaded(LoadedImage),
 }
 
+impl ImageFileData {
+    pub fn get_filename(&self) -> Option<&str> {
+        match self {
+            ImageFileData::Empty => None,
+            ImageFileData::NotLoaded(filename) => Some(filename),
+            ImageFileData::Loaded(loaded) => loaded.filename.as_deref(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SequenceDefinition {
     pub name: String,
     pub opacity: u8,
@@ -113,6 +181,7 @@ pub struct SequenceDefinition {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FrameDefinition {
     pub name: String,
     pub offset_px: (i32, i32),
@@ -122,6 +191,7 @@ pub struct FrameDefinition {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpriteDefinition {
     pub name: String,
     pub size_px: (u32, u32),
@@ -148,6 +218,16 @@ pub enum AnimationFileData {
     Loaded(LoadedAnimation),
 }
 
+impl AnimationFileData {
+    pub fn get_filename(&self) -> Option<&str> {
+        match self {
+            AnimationFileData::Empty => None,
+            AnimationFileData::NotLoaded(filename) => Some(filename),
+            AnimationFileData::Loaded(loaded) => loaded.filename.as_deref(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LoadedAnimation {
     pub filename: Option<String>,
@@ -155,6 +235,109 @@ pub struct LoadedAnimation {
     pub sprites: Vec<(SpriteDefinition, SpriteData)>,
 }
 
+/// Size in pixels and RGBA8888 pixel data of a rendered text block.
+pub type RenderedText = ((usize, usize), Vec<u8>);
+
+#[derive(Debug, Clone)]
+pub struct LoadedFont {
+    pub filename: Option<String>,
+    pub glyphs: HashMap<char, (SpriteDefinition, SpriteData)>,
+}
+
+impl LoadedFont {
+    pub fn get_line_height(&self) -> usize {
+        self.glyphs
+            .values()
+            .map(|(d, _)| d.size_px.1 as usize)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Advance used for characters missing from the font.
+    pub fn get_fallback_advance(&self) -> usize {
+        self.glyphs
+            .get(&' ')
+            .or(self.glyphs.get(&'?'))
+            .map(|(d, _)| d.size_px.0 as usize)
+            .unwrap_or(self.get_line_height() / 2)
+    }
+
+    pub fn get_advance(&self, character: char) -> usize {
+        self.glyphs
+            .get(&character)
+            .map(|(d, _)| d.size_px.0 as usize)
+            .unwrap_or_else(|| self.get_fallback_advance())
+    }
+
+    pub fn measure_lines(&self, text: &str) -> Vec<(usize, usize)> {
+        let line_height = self.get_line_height();
+        text.lines()
+            .map(|line| (line.chars().map(|c| self.get_advance(c)).sum(), line_height))
+            .collect()
+    }
+
+    /// Splits the text into lines no wider than `max_width`, breaking at spaces and newlines.
+    ///
+    /// Words wider than the limit are kept whole on lines of their own.
+    pub fn wrap_lines(&self, text: &str, max_width: usize) -> Vec<String> {
+        let measure = |line: &str| line.chars().map(|c| self.get_advance(c)).sum::<usize>();
+        let mut lines = Vec::new();
+        for paragraph in text.lines() {
+            let mut current_line = String::new();
+            for word in paragraph.split(' ') {
+                if current_line.is_empty() {
+                    current_line.push_str(word);
+                    continue;
+                }
+                let candidate = format!("{} {}", current_line, word);
+                if measure(&candidate) <= max_width {
+                    current_line = candidate;
+                } else {
+                    lines.push(std::mem::replace(&mut current_line, word.to_owned()));
+                }
+            }
+            lines.push(current_line);
+        }
+        lines
+    }
+
+    /// Draws the text into an RGBA8888 buffer, returning its size along with the pixel data.
+    ///
+    /// When a color is given, it replaces the color of glyph pixels while keeping their alpha.
+    pub fn render(&self, text: &str, color: Option<(u8, u8, u8)>) -> RenderedText {
+        let lines = self.measure_lines(text);
+        let width = lines.iter().map(|(w, _)| *w).max().unwrap_or_default();
+        let height = lines.iter().map(|(_, h)| *h).sum();
+        let mut data = vec![0u8; width * height * 4];
+        let mut line_y = 0;
+        for (line, (_, line_height)) in text.lines().zip(lines) {
+            let mut glyph_x = 0;
+            for character in line.chars() {
+                if let Some((definition, sprite)) = self.glyphs.get(&character) {
+                    let glyph_width = definition.size_px.0 as usize;
+                    let glyph_height = (definition.size_px.1 as usize).min(line_height);
+                    for y in 0..glyph_height {
+                        for x in 0..glyph_width {
+                            let source = (y * glyph_width + x) * 4;
+                            let target = ((line_y + y) * width + glyph_x + x) * 4;
+                            let Some(pixel) = sprite.data.get(source..source + 4) else {
+                                continue;
+                            };
+                            data[target..target + 4].copy_from_slice(pixel);
+                            if let Some((r, g, b)) = color {
+                                data[target..target + 3].copy_from_slice(&[r, g, b]);
+                            }
+                        }
+                    }
+                }
+                glyph_x += self.get_advance(character);
+            }
+            line_y += line_height;
+        }
+        ((width, height), data)
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Copy)]
 pub struct FrameIdentifier {
     pub sequence_idx: usize,
@@ -1,8 +1,10 @@
-use std::{ops::Deref, sync::Arc};
+use std::{collections::HashMap, ops::Deref, sync::Arc};
+
+use pixlib_formats::FileFormat;
 
 use crate::parser::ast::ParsedScript;
 
-use super::classes::*;
+use super::{classes::*, CnvObject, CnvValue};
 
 #[derive(Debug)]
 pub enum CnvContent {
@@ -36,6 +38,7 @@ pub enum CnvContent {
     System(System),
     Text(Text),
     Timer(Timer),
+    Unknown(Unknown),
     Custom(Box<dyn CnvType>), // TODO: allow for ONINIT here
     None(DummyCnvType),
 }
@@ -44,6 +47,180 @@ pub trait EventHandler {
     fn get(&self, name: &str, argument: Option<&str>) -> Option<&Arc<ParsedScript>>;
 }
 
+/// Resolves a handler registered for an event argument (e.g. `ONSIGNAL^ATTACK*`).
+///
+/// Precedence: exact key > wildcard pattern (the longest matching one, then the lexically
+/// first one) > default (empty key).
+pub fn get_matching_handler<'a>(
+    handlers: &'a HashMap<String, Arc<ParsedScript>>,
+    argument: Option<&str>,
+) -> Option<&'a Arc<ParsedScript>> {
+    let Some(argument) = argument else {
+        return handlers.get("");
+    };
+    handlers
+        .get(argument)
+        .or_else(|| {
+            handlers
+                .iter()
+                .filter(|(k, _)| k.contains('*') && matches_pattern(k, argument))
+                .max_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| b.cmp(a)))
+                .map(|(_, v)| v)
+        })
+        .or(handlers.get(""))
+}
+
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            return rest.ends_with(part);
+        }
+        let Some(index) = rest.find(part) else {
+            return false;
+        };
+        rest = &rest[(index + part.len())..];
+    }
+    rest.is_empty()
+}
+
+impl CnvContent {
+    /// Lists external files declared by the object, without loading them.
+    pub fn get_referenced_files(&self) -> Vec<String> {
+        match self {
+            CnvContent::Animation(content) => content.get_referenced_files(),
+            CnvContent::Font(content) => content.get_referenced_files(),
+            CnvContent::Image(content) => content.get_referenced_files(),
+            CnvContent::Music(content) => content.get_referenced_files(),
+            CnvContent::Scene(content) => content.get_referenced_files(),
+            CnvContent::Sequence(content) => content.get_referenced_files(),
+            CnvContent::Sound(content) => content.get_referenced_files(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Lists external files declared by the object together with the format they should have.
+    pub fn get_referenced_resources(&self) -> Vec<(String, FileFormat)> {
+        let format = match self {
+            CnvContent::Animation(_) => FileFormat::Ann,
+            CnvContent::Font(_) => FileFormat::Font,
+            CnvContent::Image(_) => FileFormat::Img,
+            CnvContent::Music(_) | CnvContent::Sound(_) => FileFormat::Wav,
+            CnvContent::Sequence(_) => FileFormat::Seq,
+            CnvContent::Scene(content) => return content.get_referenced_resources(),
+            _ => return Vec::new(),
+        };
+        self.get_referenced_files()
+            .into_iter()
+            .map(|f| (f, format))
+            .collect()
+    }
+
+    /// Describes the key state of the object (position and visibility for graphics,
+    /// value for variables) without loading any of its files.
+    pub fn describe_state(&self) -> anyhow::Result<Option<String>> {
+        let value = match self {
+            CnvContent::Integer(content) => CnvValue::Integer(content.get()?),
+            CnvContent::Double(content) => CnvValue::Double(content.get()?),
+            CnvContent::Bool(content) => CnvValue::Bool(content.get()?),
+            CnvContent::String(content) => CnvValue::String(content.get()?),
+            CnvContent::Animation(content) => {
+                let position = content.get_base_position()?;
+                let is_visible = GeneralGraphics::is_visible(content)?;
+                return Ok(Some(format!(
+                    "position=({}, {}) visible={}",
+                    position.0, position.1, is_visible
+                )));
+            }
+            CnvContent::Image(content) => {
+                let position = content.get_base_position()?;
+                let is_visible = GeneralGraphics::is_visible(content)?;
+                return Ok(Some(format!(
+                    "position=({}, {}) visible={}",
+                    position.0, position.1, is_visible
+                )));
+            }
+            CnvContent::Struct(content) => {
+                return Ok(Some(
+                    content
+                        .fields()
+                        .into_iter()
+                        .map(|(name, value)| format!("{}={}", name, value.to_cnv_string()))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ));
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(format!("value={}", value.to_cnv_string())))
+    }
+
+    /// Marks the object's data loaded from the given file as not loaded yet.
+    pub fn invalidate_file(&self, filename: &str) -> bool {
+        match self {
+            CnvContent::Animation(content) => content.invalidate_file(filename),
+            CnvContent::Font(content) => content.invalidate_file(filename),
+            CnvContent::Image(content) => content.invalidate_file(filename),
+            CnvContent::Music(content) => content.invalidate_file(filename),
+            CnvContent::Scene(content) => content.invalidate_file(filename),
+            CnvContent::Sequence(content) => content.invalidate_file(filename),
+            CnvContent::Sound(content) => content.invalidate_file(filename),
+            _ => false,
+        }
+    }
+
+    /// Copies the content together with its current state, attaching the copy to another object.
+    ///
+    /// Custom contents cannot be copied and are replaced with a dummy one.
+    pub fn clone_with_parent(&self, parent: Arc<CnvObject>) -> CnvContent {
+        match self {
+            CnvContent::Animation(content) => CnvContent::Animation(content.with_parent(parent)),
+            CnvContent::Application(content) => {
+                CnvContent::Application(content.with_parent(parent))
+            }
+            CnvContent::Array(content) => CnvContent::Array(content.with_parent(parent)),
+            CnvContent::Behavior(content) => CnvContent::Behavior(content.with_parent(parent)),
+            CnvContent::Bool(content) => CnvContent::Bool(content.with_parent(parent)),
+            CnvContent::Button(content) => CnvContent::Button(content.with_parent(parent)),
+            CnvContent::CanvasObserver(content) => {
+                CnvContent::CanvasObserver(content.with_parent(parent))
+            }
+            CnvContent::CnvLoader(content) => CnvContent::CnvLoader(content.with_parent(parent)),
+            CnvContent::Condition(content) => CnvContent::Condition(content.with_parent(parent)),
+            CnvContent::ComplexCondition(content) => {
+                CnvContent::ComplexCondition(content.with_parent(parent))
+            }
+            CnvContent::Double(content) => CnvContent::Double(content.with_parent(parent)),
+            CnvContent::Episode(content) => CnvContent::Episode(content.with_parent(parent)),
+            CnvContent::Expression(content) => CnvContent::Expression(content.with_parent(parent)),
+            CnvContent::Font(content) => CnvContent::Font(content.with_parent(parent)),
+            CnvContent::Group(content) => CnvContent::Group(content.with_parent(parent)),
+            CnvContent::Image(content) => CnvContent::Image(content.with_parent(parent)),
+            CnvContent::Integer(content) => CnvContent::Integer(content.with_parent(parent)),
+            CnvContent::Keyboard(content) => CnvContent::Keyboard(content.with_parent(parent)),
+            CnvContent::Mouse(content) => CnvContent::Mouse(content.with_parent(parent)),
+            CnvContent::MultiArray(content) => CnvContent::MultiArray(content.with_parent(parent)),
+            CnvContent::Music(content) => CnvContent::Music(content.with_parent(parent)),
+            CnvContent::Rand(content) => CnvContent::Rand(content.with_parent(parent)),
+            CnvContent::Scene(content) => CnvContent::Scene(content.with_parent(parent)),
+            CnvContent::Sequence(content) => CnvContent::Sequence(content.with_parent(parent)),
+            CnvContent::Sound(content) => CnvContent::Sound(content.with_parent(parent)),
+            CnvContent::String(content) => CnvContent::String(content.with_parent(parent)),
+            CnvContent::Struct(content) => CnvContent::Struct(content.with_parent(parent)),
+            CnvContent::System(content) => CnvContent::System(content.with_parent(parent)),
+            CnvContent::Text(content) => CnvContent::Text(content.with_parent(parent)),
+            CnvContent::Timer(content) => CnvContent::Timer(content.with_parent(parent)),
+            CnvContent::Unknown(content) => CnvContent::Unknown(content.with_parent(parent)),
+            CnvContent::Custom(_) | CnvContent::None(_) => CnvContent::None(DummyCnvType {}),
+        }
+    }
+}
+
 impl AsRef<dyn CnvType> for CnvContent {
     fn as_ref(&self) -> &(dyn CnvType + 'static) {
         match self {
@@ -77,6 +254,7 @@ impl AsRef<dyn CnvType> for CnvContent {
             CnvContent::System(content) => content,
             CnvContent::Text(content) => content,
             CnvContent::Timer(content) => content,
+            CnvContent::Unknown(content) => content,
             CnvContent::Custom(content) => &**content,
             CnvContent::None(content) => content,
         }
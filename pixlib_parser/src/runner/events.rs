@@ -2,7 +2,11 @@ use std::{cell::RefCell, collections::VecDeque, fmt::Display};
 
 pub use keyboard_types::Code as KeyboardKey;
 
-use super::{common::SoundData, path::ScenePath, CallableIdentifierOwned, RunnerContext};
+use super::{
+    common::{SoundData, SoundParameters},
+    path::ScenePath,
+    CallableIdentifierOwned, RunnerContext,
+};
 
 #[derive(Debug, Clone)]
 pub struct InternalEvent {
@@ -37,6 +41,7 @@ pub enum MouseEvent {
 #[derive(Debug, Clone, PartialEq)]
 pub enum KeyboardEvent {
     KeyPressed { key_code: keyboard_types::Code },
+    KeyReleased { key_code: keyboard_types::Code },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -57,8 +62,18 @@ pub struct OutgoingEvents {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScriptEvent {
-    ScriptLoaded { path: ScenePath },
-    ScriptUnloaded { path: ScenePath },
+    ScriptLoaded {
+        path: ScenePath,
+    },
+    ScriptUnloaded {
+        path: ScenePath,
+    },
+    /// Emitted after each object referencing files has been initialized.
+    ScriptResourcesLoading {
+        path: ScenePath,
+        loaded: usize,
+        total: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -83,6 +98,8 @@ pub enum SoundSource {
     Sound {
         script_path: ScenePath,
         object_name: String,
+        /// Playback started by a single `PLAY` (replacing the previous one), or 0 for the object.
+        instance: usize,
     },
     AnimationSfx {
         script_path: ScenePath,
@@ -94,12 +111,37 @@ pub enum SoundSource {
     },
 }
 
+impl SoundSource {
+    /// Checks whether both sources come from the same object, regardless of the playback instance.
+    pub fn is_same_object(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                SoundSource::Sound {
+                    script_path,
+                    object_name,
+                    ..
+                },
+                SoundSource::Sound {
+                    script_path: other_script_path,
+                    object_name: other_object_name,
+                    ..
+                },
+            ) => script_path == other_script_path && object_name == other_object_name,
+            _ => self == other,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SoundEvent {
     SoundLoaded {
         source: SoundSource,
         sound_data: SoundData,
     },
+    SoundParametersChanged {
+        source: SoundSource,
+        parameters: SoundParameters,
+    },
     SoundStarted(SoundSource),
     SoundPaused(SoundSource),
     SoundResumed(SoundSource),
@@ -110,6 +152,7 @@ impl SoundEvent {
     pub fn get_source(&self) -> &SoundSource {
         match self {
             SoundEvent::SoundLoaded { source, .. } => source,
+            SoundEvent::SoundParametersChanged { source, .. } => source,
             SoundEvent::SoundStarted(source) => source,
             SoundEvent::SoundPaused(source) => source,
             SoundEvent::SoundResumed(source) => source,
@@ -125,6 +168,7 @@ impl Display for SoundEvent {
             "SoundEvent::{}({:?})",
             match self {
                 SoundEvent::SoundLoaded { .. } => "SoundLoaded",
+                SoundEvent::SoundParametersChanged { .. } => "SoundParametersChanged",
                 SoundEvent::SoundStarted(_) => "SoundStarted",
                 SoundEvent::SoundPaused(_) => "SoundPaused",
                 SoundEvent::SoundResumed(_) => "SoundResumed",
@@ -142,6 +186,22 @@ pub enum GraphicsEvent {
     GraphicsLoaded,
     GraphicsFlipped,
     FrameChanged,
+    /// The whole canvas has to be recomposited, not only the graphics that changed.
+    CanvasRedrawRequested,
+    SceneUnloading {
+        name: String,
+        transition: Option<SceneTransition>,
+    },
+    SceneLoaded {
+        name: String,
+        transition: Option<SceneTransition>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneTransition {
+    pub kind: String,
+    pub duration_ms: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -152,4 +212,6 @@ pub enum CursorEvent {
     CursorShown,
     CursorSetToPointer,
     CursorSetToDefault,
+    /// Replaces the cursor graphic with the named one.
+    CursorSetToImage(String),
 }
@@ -1,5 +1,6 @@
 use std::{io::ErrorKind, sync::Arc};
 
+use itertools::Itertools;
 use log::{info, trace};
 
 use super::path::{Path, ScenePath};
@@ -7,6 +8,11 @@ use super::path::{Path, ScenePath};
 pub trait FileSystem: std::fmt::Debug + Send + Sync {
     fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>>;
     fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()>;
+
+    /// Lists files which have been modified since they were last read.
+    fn poll_changed_files(&mut self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 impl dyn FileSystem {
@@ -19,26 +25,13 @@ impl dyn FileSystem {
             "read_scene_file({:?}, {:?})",
             game_paths.data_directory, scene_path,
         );
-        let mut path = scene_path.file_path.clone();
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
-        }
-        path.prepend(&scene_path.dir_path);
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
-        }
-        path.prepend(&game_paths.data_directory);
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
+        for path in game_paths.get_scene_asset_paths(scene_path) {
+            trace!("Trying path: {:?}", path);
+            match self.read_file(&path) {
+                Ok(vec) => return Ok(vec),
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
         }
         Err(std::io::Error::from(std::io::ErrorKind::NotFound))
     }
@@ -52,33 +45,13 @@ impl dyn FileSystem {
             "read_sound_file(({:?}, {:?}), {:?})",
             game_paths.dialogues_directory, game_paths.data_directory, scene_path,
         );
-        let mut path = scene_path.file_path.clone();
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
-        }
-        path.prepend(&game_paths.dialogues_directory);
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
-        }
-        let mut path = scene_path.file_path.with_prepended(&scene_path.dir_path);
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
-        }
-        path.prepend(&game_paths.data_directory);
-        trace!("Trying path: {:?}", path);
-        match self.read_file(&path) {
-            Ok(vec) => return Ok(vec),
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
-            Err(e) => return Err(e),
+        for path in game_paths.get_sound_paths(scene_path) {
+            trace!("Trying path: {:?}", path);
+            match self.read_file(&path) {
+                Ok(vec) => return Ok(vec),
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
         }
         Err(std::io::Error::from(std::io::ErrorKind::NotFound))
     }
@@ -101,6 +74,120 @@ impl dyn FileSystem {
     }
 }
 
+/// Maps asset references as written in scripts onto the names present in a file system.
+///
+/// Names are compared case-insensitively after canonicalization (see [`Path`]).
+/// References without an extension additionally match any of the known extensions,
+/// the earlier ones in the list being preferred.
+/// Logical directories (e.g. scene `PATH`s) can be mapped onto the directories
+/// they are actually stored in.
+#[derive(Debug, Clone)]
+pub struct ResolveStrategy {
+    pub known_extensions: Vec<String>,
+    pub directory_mappings: Vec<(Path, Path)>,
+}
+
+impl Default for ResolveStrategy {
+    fn default() -> Self {
+        Self {
+            known_extensions: [
+                "ANN", "ARR", "CNV", "DEF", "FNT", "IMG", "INI", "SEQ", "WAV",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            directory_mappings: Vec::new(),
+        }
+    }
+}
+
+impl ResolveStrategy {
+    pub fn with_directory_mapping(mut self, logical: &str, on_disk: &str) -> Self {
+        self.directory_mappings
+            .push((Path::from(logical), Path::from(on_disk)));
+        self
+    }
+
+    /// Replaces the first run of segments matching a mapped logical directory
+    /// with the on-disk one, leaving unmapped references intact.
+    pub fn map_directories(&self, requested: &str) -> String {
+        let canonical = Path::from(requested);
+        let segments: Vec<&str> = canonical.split('/').collect();
+        for (logical, on_disk) in self.directory_mappings.iter() {
+            if logical.is_empty() || &**logical == "." {
+                continue;
+            }
+            let logical_segments: Vec<&str> = logical.split('/').collect();
+            let Some(start) = segments
+                .windows(logical_segments.len())
+                .position(|w| w == logical_segments.as_slice())
+            else {
+                continue;
+            };
+            return segments[..start]
+                .iter()
+                .copied()
+                .chain([&**on_disk])
+                .chain(segments[(start + logical_segments.len())..].iter().copied())
+                .join("/");
+        }
+        requested.to_owned()
+    }
+
+    pub fn resolve<'a>(
+        &self,
+        requested: &str,
+        available: impl IntoIterator<Item = &'a str>,
+    ) -> Option<&'a str> {
+        let requested = Path::from(&self.map_directories(requested));
+        let mut extension_match: Option<(usize, &'a str)> = None;
+        for name in available {
+            let name_path = Path::from(name);
+            if *name_path == *requested {
+                return Some(name);
+            }
+            if let Some(priority) = self.get_extension_priority(&requested, &name_path) {
+                if !extension_match.is_some_and(|(p, _)| p <= priority) {
+                    extension_match = Some((priority, name));
+                }
+            }
+        }
+        extension_match.map(|(_, name)| name)
+    }
+
+    pub fn candidates(&self, requested: &str) -> Vec<String> {
+        let requested = self.map_directories(requested);
+        let mut candidates = vec![requested.clone()];
+        if !has_extension(&requested) {
+            candidates.extend(
+                self.known_extensions
+                    .iter()
+                    .map(|e| format!("{}.{}", requested, e)),
+            );
+        }
+        candidates
+    }
+
+    fn get_extension_priority(&self, requested: &Path, name: &Path) -> Option<usize> {
+        if has_extension(requested) {
+            return None;
+        }
+        let (stem, extension) = name.rsplit_once('.')?;
+        if stem != &**requested {
+            return None;
+        }
+        self.known_extensions
+            .iter()
+            .position(|e| e.eq_ignore_ascii_case(extension))
+    }
+}
+
+fn has_extension(path: &str) -> bool {
+    path.rsplit(['/', '\\'])
+        .next()
+        .is_some_and(|n| n.contains('.') && n != "." && n != "..")
+}
+
 #[derive(Debug, Clone)]
 pub struct GamePaths {
     pub data_directory: Path,
@@ -112,6 +199,31 @@ pub struct GamePaths {
     pub classes_directory: Path,
 }
 
+impl GamePaths {
+    /// Lists the paths a scene asset is looked up at, in the order they are tried.
+    pub fn get_scene_asset_paths(&self, scene_path: &ScenePath) -> Vec<Path> {
+        let mut path = scene_path.file_path.clone();
+        let mut paths = vec![path.clone()];
+        path.prepend(&scene_path.dir_path);
+        paths.push(path.clone());
+        path.prepend(&self.data_directory);
+        paths.push(path);
+        paths
+    }
+
+    /// Lists the paths a sound is looked up at, in the order they are tried.
+    pub fn get_sound_paths(&self, scene_path: &ScenePath) -> Vec<Path> {
+        let mut paths = vec![
+            scene_path.file_path.clone(),
+            scene_path
+                .file_path
+                .with_prepended(&self.dialogues_directory),
+        ];
+        paths.extend(self.get_scene_asset_paths(scene_path).into_iter().skip(1));
+        paths
+    }
+}
+
 impl Default for GamePaths {
     fn default() -> Self {
         Self {
@@ -125,3 +237,82 @@ impl Default for GamePaths {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("uppercase", "TEST.IMG", Some("test.img"))]
+    #[test_case("mixed case", "Test.Img", Some("test.img"))]
+    #[test_case("lowercase without extension", "test", Some("test.img"))]
+    #[test_case("mixed case without extension", "TeSt", Some("test.img"))]
+    #[test_case("relative", "./DIR/../test", Some("test.img"))]
+    #[test_case("different extension", "test.ann", None)]
+    #[test_case("prefix", "tes", None)]
+    fn references_should_be_resolved_to_available_names(
+        _description: &str,
+        requested: &str,
+        expected: Option<&str>,
+    ) {
+        let available = ["other.img", "test.img", "test.img.bak"];
+        assert_eq!(
+            ResolveStrategy::default().resolve(requested, available),
+            expected
+        );
+    }
+
+    #[test]
+    fn exact_matches_should_take_precedence_over_extension_matches() {
+        let available = ["TEST.WAV", "TEST"];
+        assert_eq!(
+            ResolveStrategy::default().resolve("test", available),
+            Some("TEST")
+        );
+    }
+
+    #[test]
+    fn earlier_known_extensions_should_be_preferred() {
+        let available = ["test.wav", "test.img", "test.ann"];
+        assert_eq!(
+            ResolveStrategy::default().resolve("test", available),
+            Some("test.ann")
+        );
+    }
+
+    #[test_case(
+        "mapped directory",
+        "DANE/SCENES/INTRO/INTRO.CNV",
+        Some("dane/scenes/intro_v2/intro.cnv")
+    )]
+    #[test_case(
+        "mapped directory without extension",
+        "dane\\scenes\\intro\\intro",
+        Some("dane/scenes/intro_v2/intro.cnv")
+    )]
+    #[test_case(
+        "on-disk directory",
+        "DANE/SCENES/INTRO_V2/INTRO.CNV",
+        Some("dane/scenes/intro_v2/intro.cnv")
+    )]
+    #[test_case("partially matching directory", "DANE/SCENES/INTRO2/INTRO.CNV", None)]
+    fn logical_directories_should_be_mapped_to_on_disk_ones(
+        _description: &str,
+        requested: &str,
+        expected: Option<&str>,
+    ) {
+        let available = ["dane/scenes/intro_v2/intro.cnv"];
+        assert_eq!(
+            ResolveStrategy::default()
+                .with_directory_mapping("SCENES/INTRO", "SCENES/INTRO_V2")
+                .resolve(requested, available),
+            expected
+        );
+    }
+
+    #[test]
+    fn unknown_extensions_should_not_be_tried() {
+        let available = ["test.bak"];
+        assert_eq!(ResolveStrategy::default().resolve("test", available), None);
+    }
+}
@@ -0,0 +1,141 @@
+use keyboard_types::{Code, Modifiers};
+
+use crate::scanner::CP1250_LUT;
+
+/// Returns the modifier flag tracked for the given key, if it is a modifier key.
+pub fn get_modifier(key_code: Code) -> Option<Modifiers> {
+    match key_code {
+        Code::ShiftLeft | Code::ShiftRight => Some(Modifiers::SHIFT),
+        Code::ControlLeft | Code::ControlRight => Some(Modifiers::CONTROL),
+        Code::AltLeft => Some(Modifiers::ALT),
+        Code::AltRight => Some(Modifiers::ALT_GRAPH),
+        Code::MetaLeft | Code::MetaRight => Some(Modifiers::META),
+        _ => None,
+    }
+}
+
+/// Translates a key press into the character typed on the Polish (programmer's) layout.
+///
+/// Modifier keys, non-printable keys and shortcuts (Ctrl/Meta combinations other than
+/// AltGr) produce no character. Characters outside of CP1250 are never returned.
+pub fn translate_key(key_code: Code, modifiers: Modifiers) -> Option<char> {
+    let is_alt_graph = modifiers.contains(Modifiers::ALT_GRAPH)
+        || modifiers.contains(Modifiers::CONTROL | Modifiers::ALT);
+    if !is_alt_graph && modifiers.intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+    {
+        return None;
+    }
+    let is_shifted = modifiers.contains(Modifiers::SHIFT);
+    let character = if let Some(letter) = get_letter(key_code) {
+        let letter = if is_alt_graph {
+            get_polish_diacritic(letter)?
+        } else {
+            letter
+        };
+        if is_shifted {
+            letter.to_uppercase().next()?
+        } else {
+            letter
+        }
+    } else if is_alt_graph {
+        return None;
+    } else {
+        let (plain, shifted) = get_symbol(key_code)?;
+        if is_shifted {
+            shifted
+        } else {
+            plain
+        }
+    };
+    (character.is_ascii() || CP1250_LUT.contains(&character)).then_some(character)
+}
+
+fn get_letter(key_code: Code) -> Option<char> {
+    Some(match key_code {
+        Code::KeyA => 'a',
+        Code::KeyB => 'b',
+        Code::KeyC => 'c',
+        Code::KeyD => 'd',
+        Code::KeyE => 'e',
+        Code::KeyF => 'f',
+        Code::KeyG => 'g',
+        Code::KeyH => 'h',
+        Code::KeyI => 'i',
+        Code::KeyJ => 'j',
+        Code::KeyK => 'k',
+        Code::KeyL => 'l',
+        Code::KeyM => 'm',
+        Code::KeyN => 'n',
+        Code::KeyO => 'o',
+        Code::KeyP => 'p',
+        Code::KeyQ => 'q',
+        Code::KeyR => 'r',
+        Code::KeyS => 's',
+        Code::KeyT => 't',
+        Code::KeyU => 'u',
+        Code::KeyV => 'v',
+        Code::KeyW => 'w',
+        Code::KeyX => 'x',
+        Code::KeyY => 'y',
+        Code::KeyZ => 'z',
+        _ => return None,
+    })
+}
+
+fn get_polish_diacritic(letter: char) -> Option<char> {
+    Some(match letter {
+        'a' => 'ą',
+        'c' => 'ć',
+        'e' => 'ę',
+        'l' => 'ł',
+        'n' => 'ń',
+        'o' => 'ó',
+        's' => 'ś',
+        'x' => 'ź',
+        'z' => 'ż',
+        _ => return None,
+    })
+}
+
+fn get_symbol(key_code: Code) -> Option<(char, char)> {
+    Some(match key_code {
+        Code::Digit1 => ('1', '!'),
+        Code::Digit2 => ('2', '@'),
+        Code::Digit3 => ('3', '#'),
+        Code::Digit4 => ('4', '$'),
+        Code::Digit5 => ('5', '%'),
+        Code::Digit6 => ('6', '^'),
+        Code::Digit7 => ('7', '&'),
+        Code::Digit8 => ('8', '*'),
+        Code::Digit9 => ('9', '('),
+        Code::Digit0 => ('0', ')'),
+        Code::Minus => ('-', '_'),
+        Code::Equal => ('=', '+'),
+        Code::BracketLeft => ('[', '{'),
+        Code::BracketRight => (']', '}'),
+        Code::Backslash => ('\\', '|'),
+        Code::Semicolon => (';', ':'),
+        Code::Quote => ('\'', '"'),
+        Code::Backquote => ('`', '~'),
+        Code::Comma => (',', '<'),
+        Code::Period => ('.', '>'),
+        Code::Slash => ('/', '?'),
+        Code::Space => (' ', ' '),
+        Code::Numpad0 => ('0', '0'),
+        Code::Numpad1 => ('1', '1'),
+        Code::Numpad2 => ('2', '2'),
+        Code::Numpad3 => ('3', '3'),
+        Code::Numpad4 => ('4', '4'),
+        Code::Numpad5 => ('5', '5'),
+        Code::Numpad6 => ('6', '6'),
+        Code::Numpad7 => ('7', '7'),
+        Code::Numpad8 => ('8', '8'),
+        Code::Numpad9 => ('9', '9'),
+        Code::NumpadAdd => ('+', '+'),
+        Code::NumpadSubtract => ('-', '-'),
+        Code::NumpadMultiply => ('*', '*'),
+        Code::NumpadDivide => ('/', '/'),
+        Code::NumpadDecimal => ('.', '.'),
+        _ => return None,
+    })
+}
@@ -1,40 +1,49 @@
 #[allow(dead_code)]
 pub mod classes;
+mod clock;
 pub mod common;
 mod containers;
 mod content;
 mod events;
 mod filesystem;
 mod initable;
+mod keymap;
 pub mod object;
 mod parsers;
 mod path;
 mod script;
+mod sound_backend;
 #[cfg(test)]
 #[allow(clippy::arc_with_non_send_sync)]
 mod tests;
+mod trace;
 mod tree_walking;
 mod value;
 
+pub use clock::Clock;
 pub use common::{CallableIdentifier, CallableIdentifierOwned};
 use containers::{ObjectContainer, ScriptContainer};
 pub use content::CnvContent;
 pub use events::{
     ApplicationEvent, CursorEvent, FileEvent, GraphicsEvent, InternalEvent, KeyboardEvent,
-    KeyboardKey, MouseEvent, MultimediaEvents, ObjectEvent, ScriptEvent, SoundEvent, SoundSource,
-    TimerEvent,
+    KeyboardKey, MouseEvent, MultimediaEvents, ObjectEvent, SceneTransition, ScriptEvent,
+    SoundEvent, SoundSource, TimerEvent,
 };
-pub use filesystem::{FileSystem, GamePaths};
+pub use filesystem::{FileSystem, GamePaths, ResolveStrategy};
 use image::{ImageBuffer, Pixel, Rgba};
 use itertools::Itertools;
+use keyboard_types::Modifiers;
 use log::{error, warn};
 pub use object::{CnvObject, ObjectBuildErrorKind, ObjectBuilderError};
 pub use path::{Path, ScenePath};
-use pixlib_formats::Rect;
+use pixlib_formats::{detect_format, FileFormat, Rect};
 pub use script::{CnvScript, ScriptSource};
+pub use sound_backend::SoundBackend;
 use thiserror::Error;
-pub use tree_walking::{CnvExpression, CnvStatement};
+pub use trace::{EventTrace, TraceEntry};
+pub use tree_walking::{calculate_script, CnvExpression, CnvStatement};
 pub use value::CnvValue;
+use xxhash_rust::xxh3::xxh3_64;
 
 use std::collections::{HashSet, VecDeque};
 use std::fmt::Display;
@@ -46,11 +55,11 @@ use events::{IncomingEvents, OutgoingEvents};
 use crate::common::LoggableToOption;
 use crate::parser::seq_parser::SeqParserError;
 use crate::{
-    common::{DroppableRefMut, Issue, IssueKind},
+    common::{DroppableRefMut, Issue, IssueKind, IssueLogger, IssueManager},
     parser::declarative_parser::{self, CnvDeclaration, DeclarativeParser, ParserFatal},
-    scanner::parse_cnv,
+    scanner::{parse_cnv, CnvFile},
 };
-use classes::{GeneralButton, GeneralGraphics, InternalMouseEvent, Mouse};
+use classes::{CanvasObserver, GeneralButton, GeneralGraphics, InternalMouseEvent, Mouse};
 use object::CnvObjectBuilder;
 
 trait SomeWarnable {
@@ -76,12 +85,18 @@ pub enum RunnerError {
     TooFewArguments { expected_min: usize, actual: usize },
     #[error("Integer {actual} cannot be cast to unsigned")]
     ExpectedUnsignedInteger { actual: i32 },
+    #[error("Division by zero in object {object_name}")]
+    DivisionByZero { object_name: String },
     #[error("Left operand missing for object {object_name}")]
     MissingLeftOperand { object_name: String },
     #[error("Right operand missing for object {object_name}")]
     MissingRightOperand { object_name: String },
     #[error("Operator missing for object {object_name}")]
     MissingOperator { object_name: String },
+    #[error("Invalid operator {operator}")]
+    InvalidOperator { operator: String },
+    #[error("Invalid anchor {anchor}")]
+    InvalidAnchor { anchor: String },
     #[error("Object {name} not found")]
     ObjectNotFound { name: String },
     #[error("Object {object_name} not found in group {group_name}")]
@@ -103,6 +118,8 @@ pub enum RunnerError {
     NoImageDataLoaded(String),
     #[error("No sequence data loaded for object {0}")]
     NoSequenceDataLoaded(String),
+    #[error("No font data loaded for object {0}")]
+    NoFontDataLoaded(String),
     #[error("Sequence object {0} is not currently playing")]
     SeqNotPlaying(String),
     #[error("Animation object {0} is not currently playing")]
@@ -126,15 +143,41 @@ pub enum RunnerError {
     },
     #[error("Sprite #{index} not found in object {object_name}")]
     SpriteIndexNotFound { object_name: String, index: usize },
+    #[error("Sprite #{index} of object {object_name} has less data than its size requires")]
+    InvalidSpriteData { object_name: String, index: usize },
+    #[error("Field {field_name} not found in structure {object_name}")]
+    FieldNotFound {
+        object_name: String,
+        field_name: String,
+    },
+    #[error("Value {value} cannot be stored in field {field_name} of type {type_name} in structure {object_name}")]
+    InvalidFieldValue {
+        object_name: String,
+        field_name: String,
+        type_name: String,
+        value: CnvValue,
+    },
     #[error("Method or event handler missing on object {object_name} for callable {callable}")]
     InvalidCallable {
         object_name: String,
         callable: CallableIdentifierOwned,
     },
+    #[error("Method {method} of type {type_name} (object {object_name}) is not implemented")]
+    Unimplemented {
+        object_name: String,
+        type_name: String,
+        method: String,
+    },
     #[error("Missing filename to load")]
     MissingFilenameToLoad,
     #[error("Execution interrupted (one: {one})")]
     ExecutionInterrupted { one: bool },
+    #[error("Execution returned {value}")]
+    ExecutionReturned { value: CnvValue },
+    #[error("Loop did not finish within {limit} iterations")]
+    LoopLimitExceeded { limit: usize },
+    #[error("References nested too deeply while resolving {name}")]
+    ReferenceDepthExceeded { name: String },
 
     #[error("Script {path} not found")]
     ScriptNotFound { path: String },
@@ -191,8 +234,16 @@ pub struct CnvRunner {
     pub filesystem: Arc<RwLock<dyn FileSystem>>,
     pub game_paths: Arc<GamePaths>,
     pub global_objects: RefCell<ObjectContainer>,
-    pub window_rect: Rect,
+    pub window_rect: RefCell<Rect>,
+    pub event_trace: RefCell<EventTrace>,
+    pub clock: RefCell<Clock>,
     cursor_state: RefCell<CursorState>,
+    keyboard_modifiers: RefCell<Modifiers>,
+    cached_scene_script: RefCell<Option<(String, CnvFile)>>,
+    colliding_pairs: RefCell<HashSet<(String, String)>>,
+    internal_event_limit: RefCell<InternalEventLimit>,
+    reference_depth: RefCell<usize>,
+    parsed_scripts: RefCell<ParsedScriptCache>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -210,6 +261,65 @@ impl Default for CursorState {
     }
 }
 
+/// What to do once a single step has to deal with more internal events than allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventOverflowPolicy {
+    /// Discard the oldest queued events and leave the rest for the next step.
+    DropOldest,
+    /// Clear the queue and fail the step with [`RunnerError::ExecutionInterrupted`].
+    AbortStep,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InternalEventLimit {
+    /// Maximum number of events handled in one step, as well as kept queued.
+    pub capacity: usize,
+    pub policy: EventOverflowPolicy,
+}
+
+impl Default for InternalEventLimit {
+    fn default() -> Self {
+        Self {
+            capacity: 100_000,
+            policy: EventOverflowPolicy::AbortStep,
+        }
+    }
+}
+
+/// Scanned scripts keyed by the hash of their raw contents.
+#[derive(Debug, Clone, Default)]
+struct ParsedScriptCache {
+    entries: HashMap<u64, CnvFile>,
+    parse_count: usize,
+}
+
+/// A problem with a file referenced by a loaded object, found by [`CnvRunner::verify_resources`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceIssue {
+    Missing {
+        object_name: String,
+        filename: String,
+    },
+    Unreadable {
+        object_name: String,
+        filename: String,
+        message: String,
+    },
+    WrongFormat {
+        object_name: String,
+        filename: String,
+        expected: FileFormat,
+        detected: Option<FileFormat>,
+    },
+}
+
+/// An episode declared by the application, found by [`CnvRunner::list_episodes_and_scenes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpisodeInfo {
+    pub name: String,
+    pub scenes: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct ObjectIndex {
     pub script_idx: usize,
@@ -398,16 +508,24 @@ impl CnvRunner {
             internal_events: RefCell::new(VecDeque::new()),
             game_paths,
             global_objects: RefCell::new(ObjectContainer::default()),
-            window_rect: Rect {
-                top_left_x: 0,
-                top_left_y: 0,
-                bottom_right_x: window_resolution.0 as isize,
-                bottom_right_y: window_resolution.1 as isize,
-            },
+            window_rect: RefCell::new(Rect::from((0, 0), window_resolution)),
+            event_trace: RefCell::new(EventTrace::default()),
+            clock: RefCell::new(Clock::default()),
             cursor_state: RefCell::new(CursorState::default()),
+            keyboard_modifiers: RefCell::new(Modifiers::empty()),
+            cached_scene_script: RefCell::new(None),
+            colliding_pairs: RefCell::new(HashSet::new()),
+            internal_event_limit: RefCell::new(InternalEventLimit::default()),
+            reference_depth: RefCell::new(0),
+            parsed_scripts: RefCell::new(ParsedScriptCache::default()),
         });
+        runner.create_global_objects();
+        Ok(runner)
+    }
+
+    fn create_global_objects(self: &Arc<Self>) {
         let global_script = Arc::new(CnvScript::new(
-            Arc::clone(&runner),
+            Arc::clone(self),
             ScenePath {
                 dir_path: ".".into(),
                 file_path: "__GLOBAL__".into(),
@@ -415,8 +533,7 @@ impl CnvRunner {
             None,
             ScriptSource::Root,
         ));
-        runner
-            .global_objects
+        self.global_objects
             .borrow_mut()
             .use_and_drop_mut(|objects| {
                 for (name, type_name) in [
@@ -432,20 +549,50 @@ impl CnvRunner {
                         .map(|o| objects.push_object(o).ok_or_error());
                 }
             });
-        Ok(runner)
     }
 
     pub(crate) fn init_objects(&self) -> anyhow::Result<()> {
         let mut to_init = Vec::new();
         self.find_objects(|o| !*o.initialized.read().unwrap(), &mut to_init);
-        for object in to_init {
+        let to_init: Vec<_> = to_init
+            .into_iter()
+            .map(|o| {
+                let has_resources = !o.content.get_referenced_files().is_empty();
+                (o, has_resources)
+            })
+            .collect();
+        let mut resource_counts: HashMap<ScenePath, (usize, usize)> = HashMap::new();
+        for (object, _) in to_init.iter().filter(|(_, has_resources)| *has_resources) {
+            resource_counts
+                .entry(object.parent.path.clone())
+                .or_default()
+                .1 += 1;
+        }
+        for (object, has_resources) in to_init {
             object.init(None).ok_or_error();
+            if !has_resources {
+                continue;
+            }
+            let path = object.parent.path.clone();
+            let counts = resource_counts.entry(path.clone()).or_default();
+            counts.0 += 1;
+            let (loaded, total) = *counts;
+            self.events_out
+                .script
+                .borrow_mut()
+                .push_back(ScriptEvent::ScriptResourcesLoading {
+                    path,
+                    loaded,
+                    total,
+                });
         }
         Ok(())
     }
 
     #[allow(clippy::mutable_key_type)]
     pub fn step(self: &Arc<CnvRunner>) -> anyhow::Result<()> {
+        #[cfg(feature = "hot_reload")]
+        self.reload_changed_resources();
         self.init_objects()?;
         let mut finished_animations = HashSet::new();
         self.events_in
@@ -455,6 +602,7 @@ impl CnvRunner {
                 while let Some(evt) = events.pop_front() {
                     match evt {
                         TimerEvent::Elapsed { seconds } => {
+                            let seconds = self.clock.borrow_mut().advance(seconds);
                             let mut buffer = Vec::new();
                             self.find_objects(
                                 |o| matches!(&o.content, CnvContent::Animation(_)),
@@ -511,6 +659,15 @@ impl CnvRunner {
                 }
                 Ok(())
             })?;
+        self.events_in
+            .keyboard
+            .borrow_mut()
+            .use_and_drop_mut::<anyhow::Result<()>>(|events| {
+                while let Some(evt) = events.pop_front() {
+                    self.handle_keyboard_event(evt)?;
+                }
+                Ok(())
+            })?;
         self.events_in
             .multimedia
             .borrow_mut()
@@ -532,6 +689,7 @@ impl CnvRunner {
                                 SoundSource::Sound {
                                     script_path,
                                     object_name,
+                                    instance,
                                 } => {
                                     let Some(sound_object) = self
                                         .get_script(script_path)
@@ -548,7 +706,7 @@ impl CnvRunner {
                                     let CnvContent::Sound(ref sound) = &sound_object.content else {
                                         unreachable!();
                                     };
-                                    sound.handle_finished()?;
+                                    sound.handle_finished(*instance)?;
                                 }
                                 SoundSource::Sequence {
                                     script_path,
@@ -575,38 +733,36 @@ impl CnvRunner {
                                         sequence.handle_sound_finished()?;
                                     }
                                 }
-                                SoundSource::AnimationSfx { .. } => {}
+                                SoundSource::AnimationSfx {
+                                    script_path,
+                                    object_name,
+                                } => {
+                                    let Some(animation_object) = self
+                                        .get_script(script_path)
+                                        .and_then(|s| s.get_object(object_name))
+                                    else {
+                                        warn!(
+                                            "Object {} / {} not found for event {:?}",
+                                            script_path.to_str(),
+                                            object_name,
+                                            evt
+                                        );
+                                        continue;
+                                    };
+                                    let CnvContent::Animation(ref animation) =
+                                        &animation_object.content
+                                    else {
+                                        unreachable!();
+                                    };
+                                    animation.handle_sfx_finished()?;
+                                }
                             };
                         }
                     }
                 }
                 Ok(())
             })?;
-        let mut enabled_buttons = Vec::new();
-        self.filter_map_objects(
-            |id, o| {
-                let button: &dyn GeneralButton = match &o.content {
-                    CnvContent::Animation(a) => a,
-                    CnvContent::Button(b) => b,
-                    CnvContent::Image(i) => i,
-                    _ => return Ok(None),
-                };
-                if !button.is_enabled()? {
-                    return Ok(None);
-                }
-                let Some(rect) = button.get_rect().ok_or_error().flatten() else {
-                    return Ok(None);
-                };
-                Ok(Some(ButtonDescriptor {
-                    priority: button.get_priority()?,
-                    object_index: id,
-                    object: o.clone(),
-                    rect,
-                }))
-            },
-            &mut enabled_buttons,
-        )?;
-        enabled_buttons.sort();
+        let enabled_buttons = self.get_enabled_buttons()?;
         let mouse_position = Mouse::get_position()?;
         let found_button_index =
             self.find_relevant_button(enabled_buttons.as_ref(), mouse_position)?;
@@ -728,6 +884,7 @@ impl CnvRunner {
             }
             Ok(())
         })?;
+        let hidden_by_groups = self.get_objects_hidden_by_groups();
         let mut collidable = Vec::new();
         self.find_objects(
             |o| match &o.content {
@@ -737,6 +894,8 @@ impl CnvRunner {
             },
             &mut collidable,
         );
+        collidable.retain(|o| !is_hidden_by_group(&hidden_by_groups, o));
+        let mut colliding_pairs = HashSet::new();
         if collidable.len() > 1 {
             for i in 0..(collidable.len() - 1) {
                 for j in (i + 1)..collidable.len() {
@@ -792,33 +951,27 @@ impl CnvRunner {
                                 .clamp(left_top_left.1, left_bottom_right.1)
                                 == right_bottom_right.1);
                     if do_collide {
-                        let callable = CallableIdentifier::Event("ONCOLLISION");
-                        self.internal_events
-                            .borrow_mut()
-                            .use_and_drop_mut(|events| {
-                                events.push_back(InternalEvent {
-                                    context: RunnerContext::new(
-                                        self,
-                                        left,
-                                        left,
-                                        &[CnvValue::String(right.name.clone())],
-                                    ),
-                                    callable: callable.to_owned(),
-                                });
-                                events.push_back(InternalEvent {
-                                    context: RunnerContext::new(
-                                        self,
-                                        right,
-                                        right,
-                                        &[CnvValue::String(left.name.clone())],
-                                    ),
-                                    callable: callable.to_owned(),
-                                });
-                            })
+                        self.push_collision_events("ONCOLLISION", left, right);
+                        colliding_pairs.insert(if left.name <= right.name {
+                            (left.name.clone(), right.name.clone())
+                        } else {
+                            (right.name.clone(), left.name.clone())
+                        });
                     }
                 }
             }
         }
+        let previous_pairs = self.colliding_pairs.replace(colliding_pairs);
+        for (left_name, right_name) in previous_pairs.difference(&self.colliding_pairs.borrow()) {
+            let (Some(left), Some(right)) =
+                (self.get_object(left_name), self.get_object(right_name))
+            else {
+                continue;
+            };
+            self.push_collision_events("ONCOLLISIONFINISHED", &left, &right);
+        }
+        let InternalEventLimit { capacity, policy } = *self.internal_event_limit.borrow();
+        let mut handled_count = 0;
         while let Some(evt) = self
             .internal_events
             .borrow_mut()
@@ -833,10 +986,84 @@ impl CnvRunner {
                     Some(evt.context.clone().with_arguments(Vec::new())),
                 )
                 .ok_or_error();
+            handled_count += 1;
+            let queued_count = self.internal_events.borrow().len();
+            if handled_count < capacity && queued_count <= capacity {
+                continue;
+            }
+            match policy {
+                EventOverflowPolicy::DropOldest => {
+                    let excess = queued_count.saturating_sub(capacity);
+                    warn!(
+                        "Internal event limit ({}) reached, dropping {} oldest events",
+                        capacity, excess
+                    );
+                    self.internal_events
+                        .borrow_mut()
+                        .use_and_drop_mut(|events| events.drain(..excess).for_each(drop));
+                    if handled_count >= capacity {
+                        break;
+                    }
+                }
+                EventOverflowPolicy::AbortStep => {
+                    error!("Internal event limit ({}) reached, aborting step", capacity);
+                    self.internal_events
+                        .borrow_mut()
+                        .use_and_drop_mut(|events| events.clear());
+                    return Err(RunnerError::ExecutionInterrupted { one: false }.into());
+                }
+            }
         }
         Ok(())
     }
 
+    /// Bounds the internal events handled per step, protecting against scripts which keep
+    /// queueing new events.
+    pub fn set_internal_event_limit(&self, limit: InternalEventLimit) {
+        *self.internal_event_limit.borrow_mut() = limit;
+    }
+
+    fn get_enabled_buttons(&self) -> anyhow::Result<Vec<ButtonDescriptor>> {
+        let hidden_by_groups = self.get_objects_hidden_by_groups();
+        let (min_priority, max_priority) = self
+            .get_current_scene()
+            .and_then(|o| match &o.content {
+                CnvContent::Scene(s) => Some(s.get_hs_priority_range()),
+                _ => None,
+            })
+            .unwrap_or((isize::MIN, isize::MAX));
+        let mut enabled_buttons = Vec::new();
+        self.filter_map_objects(
+            |id, o| {
+                let button: &dyn GeneralButton = match &o.content {
+                    CnvContent::Animation(a) => a,
+                    CnvContent::Button(b) => b,
+                    CnvContent::Image(i) => i,
+                    _ => return Ok(None),
+                };
+                if !button.is_enabled()? || is_hidden_by_group(&hidden_by_groups, o) {
+                    return Ok(None);
+                }
+                let priority = button.get_priority()?;
+                if !(min_priority..=max_priority).contains(&priority) {
+                    return Ok(None);
+                }
+                let Some(rect) = button.get_rect().ok_or_error().flatten() else {
+                    return Ok(None);
+                };
+                Ok(Some(ButtonDescriptor {
+                    priority,
+                    object_index: id,
+                    object: o.clone(),
+                    rect,
+                }))
+            },
+            &mut enabled_buttons,
+        )?;
+        enabled_buttons.sort();
+        Ok(enabled_buttons)
+    }
+
     fn find_relevant_button(
         &self,
         buttons: &[ButtonDescriptor],
@@ -847,7 +1074,10 @@ impl CnvRunner {
             if result_index.is_some() {
                 break;
             }
-            if let Some(visible_rect) = button.rect.intersect(&self.window_rect) {
+            if button.rect.is_degenerate() {
+                continue;
+            }
+            if let Some(visible_rect) = button.rect.intersect(&self.window_rect.borrow()) {
                 if visible_rect.has_inside(mouse_position.0, mouse_position.1) {
                     result_index = Some(i);
                     break;
@@ -861,6 +1091,8 @@ impl CnvRunner {
         &self,
         background: Option<(Rect, Arc<Vec<u8>>)>,
     ) -> anyhow::Result<(Rect, Vec<u8>)> {
+        let window_rect = *self.window_rect.borrow();
+        let hidden_by_groups = self.get_objects_hidden_by_groups();
         let mut visible_graphics = Vec::new();
         self.filter_map_objects(
             |id, o| {
@@ -869,12 +1101,15 @@ impl CnvRunner {
                     CnvContent::Image(i) => i,
                     _ => return Ok(None),
                 };
-                if !graphics.is_visible()? {
+                if !graphics.is_visible()? || is_hidden_by_group(&hidden_by_groups, o) {
                     return Ok(None);
                 }
                 let Some(rect) = graphics.get_rect().ok_or_error().flatten() else {
                     return Ok(None);
                 };
+                if rect.is_degenerate() {
+                    return Ok(None);
+                }
                 Ok(Some(GraphicsDescriptor {
                     priority: graphics.get_priority()?,
                     object_index: id,
@@ -890,7 +1125,7 @@ impl CnvRunner {
             .into_iter()
             .filter_map(|graphics| {
                 let graphics_rect = graphics.rect;
-                graphics_rect.intersect(&self.window_rect)?;
+                graphics_rect.intersect(&window_rect)?;
                 let graphics: &dyn GeneralGraphics = match &graphics.object.content {
                     CnvContent::Animation(a) => a,
                     CnvContent::Image(i) => i,
@@ -900,24 +1135,29 @@ impl CnvRunner {
                 Some((graphics_rect, graphics))
             })
             .collect();
-        if let Some(background) = background {
+        if let Some(background) = background.filter(|(rect, _)| !rect.is_degenerate()) {
             visible_graphics.insert(0, background);
         };
         let mut screenshot: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_pixel(
-            self.window_rect.get_width() as u32,
-            self.window_rect.get_height() as u32,
+            window_rect.get_width() as u32,
+            window_rect.get_height() as u32,
             Rgba([0xFF, 0xFF, 0xFF, 0xFF]),
         );
         for (graphics_rect, graphics) in visible_graphics.into_iter() {
-            let Some(fitting_rect) = graphics_rect.intersect(&self.window_rect) else {
-                unreachable!();
+            let Some(fitting_rect) = graphics_rect.intersect(&window_rect) else {
+                continue;
             };
-            let graphics: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_raw(
+            let Some(graphics): Option<ImageBuffer<Rgba<u8>, Vec<u8>>> = ImageBuffer::from_raw(
                 graphics_rect.get_width() as u32,
                 graphics_rect.get_height() as u32,
                 (*graphics).clone(),
-            )
-            .unwrap();
+            ) else {
+                warn!(
+                    "Skipping graphics with mismatched pixel data for rect {:?}",
+                    graphics_rect
+                );
+                continue;
+            };
             let graphics_offset: (u32, u32) = (
                 (fitting_rect.top_left_x - graphics_rect.top_left_x) as u32,
                 (fitting_rect.top_left_y - graphics_rect.top_left_y) as u32,
@@ -927,8 +1167,8 @@ impl CnvRunner {
                 (graphics_offset.1 as usize + fitting_rect.get_height()) as u32,
             );
             let window_offset: (u32, u32) = (
-                (fitting_rect.top_left_x - self.window_rect.top_left_x) as u32,
-                (fitting_rect.top_left_y - self.window_rect.top_left_y) as u32,
+                (fitting_rect.top_left_x - window_rect.top_left_x) as u32,
+                (fitting_rect.top_left_y - window_rect.top_left_y) as u32,
             );
             for (x, y, pixel) in graphics.enumerate_pixels() {
                 if x < graphics_offset.0
@@ -952,7 +1192,7 @@ impl CnvRunner {
                     .0[3] = 255;
             }
         }
-        Ok((self.window_rect, screenshot.into_raw()))
+        Ok((window_rect, screenshot.into_raw()))
     }
 
     pub fn load_script(
@@ -962,7 +1202,10 @@ impl CnvRunner {
         parent_object: Option<Arc<CnvObject>>,
         source_kind: ScriptSource,
     ) -> anyhow::Result<()> {
-        let mut dec_parser = DeclarativeParser::new(contents, Default::default()).peekable();
+        let mut issue_manager = IssueManager::default();
+        issue_manager.set_handler(Box::new(IssueLogger));
+        let mut dec_parser =
+            DeclarativeParser::new(contents, Default::default(), issue_manager).peekable();
         let mut objects: Vec<CnvObjectBuilder> = Vec::new();
         let mut name_to_object: HashMap<String, usize> = HashMap::new();
         let script = Arc::new(CnvScript::new(
@@ -1040,6 +1283,14 @@ impl CnvRunner {
         Ok(())
     }
 
+    /// Passes all pending sound events to the given backend.
+    pub fn drain_sound_events(&self, backend: &mut (impl SoundBackend + ?Sized)) {
+        let events: Vec<_> = self.events_out.sound.borrow_mut().drain(..).collect();
+        for event in events {
+            event.apply_to(backend);
+        }
+    }
+
     pub fn get_script(&self, path: &ScenePath) -> Option<Arc<CnvScript>> {
         self.scripts.borrow().get_script(path)
     }
@@ -1048,6 +1299,40 @@ impl CnvRunner {
         self.scripts.borrow().get_root_script()
     }
 
+    /// Lists loaded scripts in load order along with the kind of their source.
+    pub fn active_scripts(&self) -> Vec<(ScriptSource, ScenePath)> {
+        self.scripts
+            .borrow()
+            .iter()
+            .map(|s| (s.source_kind, s.path.clone()))
+            .collect()
+    }
+
+    /// Lists the episodes declared by the loaded application along with their scenes,
+    /// in declaration order. Episodes without a loaded object are listed with no scenes.
+    pub fn list_episodes_and_scenes(&self) -> Vec<EpisodeInfo> {
+        let Some(application_object) =
+            self.find_object(|o| matches!(&o.content, CnvContent::Application(_)))
+        else {
+            return Vec::new();
+        };
+        let CnvContent::Application(ref application) = &application_object.content else {
+            return Vec::new();
+        };
+        application
+            .get_episode_list()
+            .into_iter()
+            .map(|name| {
+                let episode_object = self.get_object(&name);
+                let scenes = match episode_object.as_ref().map(|o| &o.content) {
+                    Some(CnvContent::Episode(episode)) => episode.get_scene_list(),
+                    _ => Vec::new(),
+                };
+                EpisodeInfo { name, scenes }
+            })
+            .collect()
+    }
+
     pub fn find_scripts(
         &self,
         predicate: impl Fn(&CnvScript) -> bool,
@@ -1062,11 +1347,69 @@ impl CnvRunner {
     }
 
     pub fn unload_all_scripts(&self) {
+        let loaded_scripts: Vec<_> = self.scripts.borrow().iter().cloned().collect();
         self.scripts.borrow_mut().remove_all_scripts();
+        for script in loaded_scripts.iter() {
+            self.release_script_resources(script);
+        }
+    }
+
+    /// Unloads the script of the current scene (along with the scripts it loaded), if any.
+    pub fn unload_scene_script(&self) -> anyhow::Result<()> {
+        let scene_script = self.scripts.borrow().get_scene_script();
+        if let Some(scene_script) = scene_script {
+            self.unload_script(&scene_script.path)?;
+        }
+        Ok(())
     }
 
     pub fn unload_script(&self, path: &ScenePath) -> anyhow::Result<()> {
-        self.scripts.borrow_mut().remove_script(path)
+        let loaded_scripts: Vec<_> = self.scripts.borrow().iter().cloned().collect();
+        self.scripts.borrow_mut().remove_script(path)?;
+        for script in loaded_scripts
+            .iter()
+            .filter(|s| self.get_script(&s.path).is_none())
+        {
+            self.release_script_resources(script);
+        }
+        Ok(())
+    }
+
+    /// Stops the sounds of an unloaded script and forgets everything queued for its objects.
+    fn release_script_resources(&self, script: &CnvScript) {
+        let objects: Vec<_> = script.objects.borrow().iter().cloned().collect();
+        self.events_out
+            .sound
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                for object in objects.iter() {
+                    let script_path = script.path.clone();
+                    let object_name = object.name.clone();
+                    let source = match &object.content {
+                        CnvContent::Sound(_) => SoundSource::Sound {
+                            script_path,
+                            object_name,
+                            instance: 0,
+                        },
+                        CnvContent::Animation(_) => SoundSource::AnimationSfx {
+                            script_path,
+                            object_name,
+                        },
+                        CnvContent::Sequence(_) => SoundSource::Sequence {
+                            script_path,
+                            object_name,
+                        },
+                        _ => continue,
+                    };
+                    events.push_back(SoundEvent::SoundStopped(source));
+                }
+            });
+        self.internal_events
+            .borrow_mut()
+            .retain(|e| e.context.current_object.parent.path != script.path);
+        self.colliding_pairs
+            .borrow_mut()
+            .retain(|(left, right)| !objects.iter().any(|o| o.name == *left || o.name == *right));
     }
 
     pub fn get_object(&self, name: &str) -> Option<Arc<CnvObject>> {
@@ -1092,6 +1435,70 @@ impl CnvRunner {
             .or(self.global_objects.borrow().find_object(&predicate))
     }
 
+    pub fn collect_referenced_files(&self) -> Vec<String> {
+        self.scripts
+            .borrow()
+            .iter()
+            .flat_map(|s| s.collect_referenced_files())
+            .unique()
+            .collect()
+    }
+
+    /// Reads every file referenced by the loaded objects and checks its header
+    /// against the format the object expects, without decoding the contents.
+    pub fn verify_resources(&self) -> Vec<ResourceIssue> {
+        let mut issues = Vec::new();
+        for script in self.scripts.borrow().iter() {
+            for object in script.objects.borrow().iter() {
+                let base_path = match &object.content {
+                    CnvContent::Scene(scene) => {
+                        ScenePath::new(scene.get_script_path().as_deref().unwrap_or_default(), "")
+                    }
+                    _ => script.path.clone(),
+                };
+                for (filename, expected) in object.content.get_referenced_resources() {
+                    let path = base_path.with_file_path(&filename);
+                    let mut filesystem = self.filesystem.write().unwrap();
+                    let data = if expected == FileFormat::Wav {
+                        filesystem.read_sound(Arc::clone(&self.game_paths), &path)
+                    } else {
+                        filesystem.read_scene_asset(Arc::clone(&self.game_paths), &path)
+                    };
+                    let object_name = object.name.clone();
+                    match data {
+                        Ok(data) => {
+                            let detected = detect_format(&data);
+                            // fonts are stored as ANN files
+                            let is_expected = detected == Some(expected)
+                                || (expected == FileFormat::Font
+                                    && detected == Some(FileFormat::Ann));
+                            if !is_expected {
+                                issues.push(ResourceIssue::WrongFormat {
+                                    object_name,
+                                    filename,
+                                    expected,
+                                    detected,
+                                });
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                            issues.push(ResourceIssue::Missing {
+                                object_name,
+                                filename,
+                            });
+                        }
+                        Err(e) => issues.push(ResourceIssue::Unreadable {
+                            object_name,
+                            filename,
+                            message: e.to_string(),
+                        }),
+                    }
+                }
+            }
+        }
+        issues
+    }
+
     pub fn find_objects(
         &self,
         predicate: impl Fn(&CnvObject) -> bool,
@@ -1112,6 +1519,237 @@ impl CnvRunner {
         }
     }
 
+    fn push_collision_events(
+        self: &Arc<CnvRunner>,
+        event_name: &'static str,
+        left: &Arc<CnvObject>,
+        right: &Arc<CnvObject>,
+    ) {
+        let callable = CallableIdentifier::Event(event_name);
+        self.internal_events
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(InternalEvent {
+                    context: RunnerContext::new(
+                        self,
+                        left,
+                        left,
+                        &[CnvValue::String(right.name.clone())],
+                    ),
+                    callable: callable.to_owned(),
+                });
+                events.push_back(InternalEvent {
+                    context: RunnerContext::new(
+                        self,
+                        right,
+                        right,
+                        &[CnvValue::String(left.name.clone())],
+                    ),
+                    callable: callable.to_owned(),
+                });
+            })
+    }
+
+    /// Lists all objects with their key state, one per line, sorted for stable comparisons.
+    pub fn dump_state(&self) -> String {
+        let mut objects = Vec::new();
+        self.find_objects(|_| true, &mut objects);
+        let mut lines: Vec<_> = objects
+            .iter()
+            .map(|object| {
+                let mut line = format!("{}: {}", object.name, object.content.get_type_id());
+                match object.content.describe_state() {
+                    Ok(Some(state)) => {
+                        line.push(' ');
+                        line.push_str(&state);
+                    }
+                    Ok(None) => {}
+                    Err(e) => line.push_str(&format!(" error={}", e)),
+                }
+                line
+            })
+            .collect();
+        lines.sort();
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+
+    /// Marks objects using the given file as not loaded, so that they read it again on next use.
+    ///
+    /// Returns the number of invalidated objects.
+    pub fn reload_resource(&self, filename: &str) -> usize {
+        let mut buffer = Vec::new();
+        self.find_objects(|o| o.content.invalidate_file(filename), &mut buffer);
+        self.parsed_scripts.borrow_mut().entries.clear();
+        if let Some(current_scene) = self.get_current_scene() {
+            if buffer.iter().any(|o| Arc::ptr_eq(o, &current_scene)) {
+                let CnvContent::Scene(scene) = &current_scene.content else {
+                    unreachable!();
+                };
+                scene.refresh_background().ok_or_error();
+            }
+        }
+        buffer.len()
+    }
+
+    #[cfg(feature = "hot_reload")]
+    fn reload_changed_resources(&self) {
+        let changed_files = self.filesystem.write().unwrap().poll_changed_files();
+        for filename in changed_files {
+            let count = self.reload_resource(&filename);
+            log::info!("Reloaded {filename} for {count} object(s)");
+        }
+    }
+
+    /// Updates the window rect used for hit-testing and screenshots, e.g. after a resize.
+    pub fn set_window_size(&self, width: usize, height: usize) {
+        *self.window_rect.borrow_mut() = Rect::from((0, 0), (width, height));
+    }
+
+    /// Lists currently clickable objects as (name, rect, priority) in hit-testing order.
+    pub fn list_buttons(&self) -> Vec<(String, Rect, isize)> {
+        self.get_enabled_buttons()
+            .ok_or_error()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|b| (b.object.name.clone(), b.rect, b.priority))
+            .collect()
+    }
+
+    /// Simulates a left click at the center of the given button's rect.
+    /// The click goes to whichever button is hit there, just like a real one.
+    pub fn click_object(&self, name: &str) -> anyhow::Result<()> {
+        let enabled_buttons = self.get_enabled_buttons()?;
+        let Some(target) = enabled_buttons.iter().find(|b| b.object.name == name) else {
+            return Err(RunnerError::ObjectNotFound {
+                name: name.to_owned(),
+            }
+            .into());
+        };
+        let Some(button_idx) =
+            self.find_relevant_button(enabled_buttons.as_ref(), target.rect.get_center())?
+        else {
+            return Ok(());
+        };
+        let button: &dyn GeneralButton = match &enabled_buttons[button_idx].object.content {
+            CnvContent::Animation(a) => a,
+            CnvContent::Button(b) => b,
+            CnvContent::Image(i) => i,
+            _ => unreachable!(),
+        };
+        button.handle_cursor_over()?;
+        button.handle_lmb_pressed()?;
+        button.handle_lmb_released()
+    }
+
+    fn handle_keyboard_event(self: &Arc<CnvRunner>, event: KeyboardEvent) -> anyhow::Result<()> {
+        let (key_code, is_pressed) = match event {
+            KeyboardEvent::KeyPressed { key_code } => (key_code, true),
+            KeyboardEvent::KeyReleased { key_code } => (key_code, false),
+        };
+        let modifier = keymap::get_modifier(key_code);
+        if let Some(modifier) = modifier {
+            self.keyboard_modifiers
+                .borrow_mut()
+                .set(modifier, is_pressed);
+        }
+        let character = if is_pressed && modifier.is_none() {
+            keymap::translate_key(key_code, *self.keyboard_modifiers.borrow())
+        } else {
+            None
+        };
+        let mut keyboards = Vec::new();
+        self.find_objects(
+            |o| matches!(&o.content, CnvContent::Keyboard(k) if k.is_enabled().unwrap_or_default()),
+            &mut keyboards,
+        );
+        let mut internal_events = self.internal_events.borrow_mut();
+        for keyboard_object in keyboards {
+            let context = RunnerContext::new_minimal(self, &keyboard_object);
+            internal_events.push_back(InternalEvent {
+                context: context
+                    .clone()
+                    .with_arguments(vec![CnvValue::String(key_code.to_string())]),
+                callable: CallableIdentifier::Event(if is_pressed {
+                    "ONKEYDOWN"
+                } else {
+                    "ONKEYUP"
+                })
+                .to_owned(),
+            });
+            if let Some(character) = character {
+                internal_events.push_back(InternalEvent {
+                    context: context.with_arguments(vec![CnvValue::String(character.into())]),
+                    callable: CallableIdentifier::Event("ONCHAR").to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists objects hidden by belonging (possibly through nested groups) to a hidden group.
+    ///
+    /// The own visibility flags of such objects are left untouched.
+    pub fn get_objects_hidden_by_groups(&self) -> Vec<Arc<CnvObject>> {
+        let mut hidden_groups = Vec::new();
+        self.find_objects(
+            |o| matches!(&o.content, CnvContent::Group(g) if !g.is_visible().unwrap_or_default()),
+            &mut hidden_groups,
+        );
+        let mut hidden: Vec<Arc<CnvObject>> = Vec::new();
+        while let Some(group) = hidden_groups.pop() {
+            let CnvContent::Group(ref group) = &group.content else {
+                unreachable!();
+            };
+            for member in group.get_objects() {
+                if is_hidden_by_group(&hidden, &member) {
+                    continue;
+                }
+                if matches!(&member.content, CnvContent::Group(_)) {
+                    hidden_groups.push(member.clone());
+                }
+                hidden.push(member);
+            }
+        }
+        hidden
+    }
+
+    /// Combines the object's own visibility with the visibility of groups containing it
+    /// (as returned by [`Self::get_objects_hidden_by_groups`]) and of its owning scene.
+    pub fn is_effectively_visible(
+        &self,
+        object: &Arc<CnvObject>,
+        hidden_by_groups: &[Arc<CnvObject>],
+    ) -> anyhow::Result<bool> {
+        let is_visible = match &object.content {
+            CnvContent::Animation(a) => GeneralGraphics::is_visible(a)?,
+            CnvContent::Image(i) => GeneralGraphics::is_visible(i)?,
+            CnvContent::Group(g) => g.is_visible()?,
+            _ => true,
+        };
+        Ok(is_visible
+            && !is_hidden_by_group(hidden_by_groups, object)
+            && self.is_in_current_scene(object))
+    }
+
+    /// Objects coming from a scene other than the current one (or from scripts loaded by it)
+    /// are never shown.
+    fn is_in_current_scene(&self, object: &CnvObject) -> bool {
+        let mut script = object.parent.clone();
+        loop {
+            if script.source_kind == ScriptSource::Scene {
+                return self
+                    .scripts
+                    .borrow()
+                    .get_scene_script()
+                    .is_some_and(|s| s.path == script.path);
+            }
+            let Some(parent_object) = script.parent_object.as_ref() else {
+                return true;
+            };
+            script = parent_object.parent.clone();
+        }
+    }
+
     pub fn filter_map_objects<T>(
         &self,
         f: impl Fn(ObjectIndex, &Arc<CnvObject>) -> anyhow::Result<Option<T>>,
@@ -1143,7 +1781,6 @@ impl CnvRunner {
         self.internal_events
             .borrow_mut()
             .use_and_drop_mut(|events| events.clear());
-        self.scripts.borrow_mut().remove_scene_script()?;
         let Some(scene_object) = self.get_object(scene_name) else {
             return Err(RunnerError::ObjectNotFound {
                 name: scene_name.to_owned(),
@@ -1154,16 +1791,29 @@ impl CnvRunner {
             panic!();
         };
         let scene_name = scene_object.name.clone();
+        let cached_contents = self
+            .cached_scene_script
+            .take()
+            .filter(|(name, _)| *name == scene_name)
+            .map(|(_, contents)| contents);
+        let transition = scene.get_transition();
+        if let Some(previous_scene) = self.get_current_scene() {
+            self.events_out
+                .graphics
+                .borrow_mut()
+                .use_and_drop_mut(|events| {
+                    events.push_back(GraphicsEvent::SceneUnloading {
+                        name: previous_scene.name.clone(),
+                        transition: transition.clone(),
+                    })
+                });
+        }
+        self.unload_scene_script()?;
         if let Some(scene_path) = scene.get_script_path() {
-            let contents = (*self.filesystem)
-                .write()
-                .unwrap()
-                .read_scene_asset(
-                    self.game_paths.clone(),
-                    &ScenePath::new(&scene_path, &(scene_name.clone() + ".cnv")),
-                )
-                .unwrap();
-            let contents = parse_cnv(&contents);
+            let contents = match cached_contents {
+                Some(contents) => contents,
+                None => self.read_scene_script(&scene_path, &scene_name)?,
+            };
             self.load_script(
                 ScenePath::new(&scene_path, &scene_name),
                 contents.as_parser_input(),
@@ -1171,9 +1821,66 @@ impl CnvRunner {
                 ScriptSource::Scene,
             )?;
         }
+        self.events_out
+            .graphics
+            .borrow_mut()
+            .use_and_drop_mut(|events| {
+                events.push_back(GraphicsEvent::SceneLoaded {
+                    name: scene_name,
+                    transition,
+                })
+            });
         scene.handle_scene_loaded()
     }
 
+    /// Reads and scans the scene script ahead of time, so that the next change to this scene
+    /// does not have to read it again. Resources referenced by the script (images, animations,
+    /// the background) are still loaded on the scene change.
+    pub fn cache_scene_script(&self, scene_name: &str) -> anyhow::Result<()> {
+        let Some(scene_object) = self.get_object(scene_name) else {
+            return Err(RunnerError::ObjectNotFound {
+                name: scene_name.to_owned(),
+            }
+            .into());
+        };
+        let CnvContent::Scene(ref scene) = &scene_object.content else {
+            return Err(RunnerError::UnexpectedType {
+                object_name: scene_object.name.clone(),
+                expected: "SCENE".to_owned(),
+                actual: scene_object.content.get_type_id().to_owned(),
+            }
+            .into());
+        };
+        let Some(scene_path) = scene.get_script_path() else {
+            return Ok(());
+        };
+        let contents = self.read_scene_script(&scene_path, &scene_object.name)?;
+        self.cached_scene_script
+            .replace(Some((scene_object.name.clone(), contents)));
+        Ok(())
+    }
+
+    fn read_scene_script(&self, scene_path: &str, scene_name: &str) -> anyhow::Result<CnvFile> {
+        let contents = (*self.filesystem).write().unwrap().read_scene_asset(
+            self.game_paths.clone(),
+            &ScenePath::new(scene_path, &(scene_name.to_owned() + ".cnv")),
+        )?;
+        Ok(self.parse_cnv_cached(&contents))
+    }
+
+    /// Scans the raw script, reusing the result for contents scanned before.
+    fn parse_cnv_cached(&self, contents: &[u8]) -> CnvFile {
+        let hash = xxh3_64(contents);
+        let mut cache = self.parsed_scripts.borrow_mut();
+        if let Some(file) = cache.entries.get(&hash) {
+            return file.clone();
+        }
+        cache.parse_count += 1;
+        let file = parse_cnv(contents);
+        cache.entries.insert(hash, file.clone());
+        file
+    }
+
     pub fn get_current_scene(&self) -> Option<Arc<CnvObject>> {
         self.scripts
             .borrow()
@@ -1182,10 +1889,25 @@ impl CnvRunner {
     }
 
     pub fn reload_application(self: &Arc<Self>) -> anyhow::Result<()> {
+        self.reload_application_into(None, None)
+    }
+
+    /// Reloads the application, starting from the given episode and/or scene
+    /// instead of the ones declared as starting.
+    pub fn reload_application_into(
+        self: &Arc<Self>,
+        episode: Option<&str>,
+        scene: Option<&str>,
+    ) -> anyhow::Result<()> {
         self.internal_events
             .borrow_mut()
             .use_and_drop_mut(|events| events.clear());
-        self.scripts.borrow_mut().remove_all_scripts();
+        self.unload_all_scripts();
+        self.global_objects.borrow_mut().remove_all_objects();
+        *self.cursor_state.borrow_mut() = CursorState::default();
+        Mouse::reset_state();
+        CanvasObserver::reset_state();
+        self.create_global_objects();
         //#region Loading application.def
         let root_script_path = self.game_paths.game_definition_filename.clone();
         let root_script_path = ScenePath::new(".", &root_script_path);
@@ -1201,7 +1923,7 @@ impl CnvRunner {
                     RunnerError::IoError { source: e }
                 }
             })?;
-        let contents = parse_cnv(&contents);
+        let contents = self.parse_cnv_cached(&contents);
         self.load_script(
             root_script_path,
             contents.as_parser_input(),
@@ -1233,7 +1955,7 @@ impl CnvRunner {
                 .write()
                 .unwrap()
                 .read_scene_asset(self.game_paths.clone(), &application_script_path)?;
-            let contents = parse_cnv(&contents);
+            let contents = self.parse_cnv_cached(&contents);
             self.load_script(
                 application_script_path,
                 contents.as_parser_input(),
@@ -1244,11 +1966,12 @@ impl CnvRunner {
         //#endregion
 
         let episode_name =
-            application
-                .get_starting_episode()
-                .ok_or(RunnerError::NoEpisodesInApplication(
-                    application_name.clone(),
-                ))?;
+            match episode {
+                Some(episode_name) => episode_name.to_owned(),
+                None => application.get_starting_episode().ok_or(
+                    RunnerError::NoEpisodesInApplication(application_name.clone()),
+                )?,
+            };
         let episode_object = self
             .get_object(&episode_name)
             .ok_or(RunnerError::ObjectNotFound {
@@ -1271,7 +1994,7 @@ impl CnvRunner {
                 .write()
                 .unwrap()
                 .read_scene_asset(self.game_paths.clone(), &episode_script_path)?;
-            let contents = parse_cnv(&contents);
+            let contents = self.parse_cnv_cached(&contents);
             self.load_script(
                 episode_script_path,
                 contents.as_parser_input(),
@@ -1280,11 +2003,26 @@ impl CnvRunner {
             )?;
         };
         //#endregion
-        if let Some(starting_scene) = episode.get_starting_scene() {
-            self.change_scene(&starting_scene)
-        } else {
-            Ok(())
+        let Some(scene_name) = scene
+            .map(|s| s.to_owned())
+            .or_else(|| episode.get_starting_scene())
+        else {
+            return Ok(());
+        };
+        let scene_object = self
+            .get_object(&scene_name)
+            .ok_or(RunnerError::ObjectNotFound {
+                name: scene_name.clone(),
+            })?;
+        if !matches!(&scene_object.content, CnvContent::Scene(_)) {
+            return Err(RunnerError::UnexpectedType {
+                object_name: scene_name,
+                expected: "SCENE".to_owned(),
+                actual: scene_object.content.get_type_id().to_owned(),
+            }
+            .into());
         }
+        self.change_scene(&scene_name)
     }
 }
 
@@ -1305,3 +2043,7 @@ fn create_object(
     }
     Ok(builder.build()?)
 }
+
+fn is_hidden_by_group(hidden_by_groups: &[Arc<CnvObject>], object: &Arc<CnvObject>) -> bool {
+    hidden_by_groups.iter().any(|o| Arc::ptr_eq(o, object))
+}
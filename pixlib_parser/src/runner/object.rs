@@ -18,7 +18,7 @@ use super::{
     classes::{CnvTypeFactory, DummyCnvType},
     initable::Initable,
     parsers::{discard_if_empty, ProgramParsingError, TypeParsingError},
-    CallableIdentifier, CnvContent,
+    CallableIdentifier, CnvContent, TraceEntry,
 };
 use OkResult::{NoError, WithError};
 
@@ -184,13 +184,14 @@ impl CnvObject {
             arguments
                 .iter()
                 .map(|v| v.to_owned().resolve(context.clone()))
-                .collect::<Vec<_>>()
+                .collect::<anyhow::Result<Vec<_>>>()?
         } else {
             arguments.to_owned()
         };
 
-        self.content
-            .call_method(identifier.clone(), &arguments, context.clone())
+        let result = self
+            .content
+            .call_method(identifier.clone(), &arguments, context.clone());
         // .inspect(|v| {
         //     log::trace!(
         //         "[2] Called method: {:?} of: {:?} with context {}, arguments: {:?} and result: {:?}",
@@ -203,6 +204,34 @@ impl CnvObject {
         //         identifier, self.name, context, arguments, e
         //     )
         // })
+        if context.runner.event_trace.borrow().is_enabled() {
+            context.runner.event_trace.borrow_mut().record(TraceEntry {
+                script_path: self.parent.path.clone(),
+                object_name: self.name.clone(),
+                callable: identifier.to_owned(),
+                arguments,
+                result: result.as_ref().cloned().map_err(|e| e.to_string()),
+            });
+        }
+        result
+    }
+
+    /// Creates a copy of the object under a new name and index, carrying over
+    /// the current state of its content instead of the initial definition.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn deep_clone(&self, new_name: &str, new_index: usize) -> Arc<CnvObject> {
+        let mut object = Arc::new(CnvObject {
+            parent: Arc::clone(&self.parent),
+            name: new_name.to_owned(),
+            index: new_index,
+            initialized: RwLock::new(*self.initialized.read().unwrap()),
+            content: CnvContent::None(DummyCnvType {}),
+        });
+        let content = self.content.clone_with_parent(Arc::clone(&object));
+        unsafe {
+            Arc::get_mut_unchecked(&mut object).content = content;
+        }
+        object
     }
 
     pub fn init(self: &Arc<Self>, context: Option<RunnerContext>) -> anyhow::Result<()> {
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
     num::{ParseFloatError, ParseIntError},
     sync::Arc,
@@ -58,6 +59,19 @@ impl ConditionOperator {
             _ => Err(TypeParsingError::InvalidConditionOperator(s)),
         }
     }
+
+    /// Parses operators used inline in @IF and @WHILE, e.g. `'<'` or `'!_'`.
+    pub fn parse_inline(s: &str) -> Option<Self> {
+        match s.trim_matches(|c| c == '\'' || c == '"') {
+            "_" | "==" => Some(Self::Equal),
+            "!_" | "!=" => Some(Self::NotEqual),
+            "<" => Some(Self::Less),
+            ">" => Some(Self::Greater),
+            "<_" | "<=" => Some(Self::LessEqual),
+            ">_" | ">=" => Some(Self::GreaterEqual),
+            other => Self::parse(other.to_ascii_uppercase()).ok(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -99,7 +113,7 @@ impl ExpressionOperator {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct FontDef {
     pub family: String,
     pub style: String,
@@ -122,6 +136,8 @@ pub enum TypeParsingError {
     InvalidFloatingLiteral(ParseFloatError),
     #[error("Invalid rect literal: {0}")]
     InvalidRectLiteral(String),
+    #[error("Invalid color literal: {0}")]
+    InvalidColorLiteral(String),
     #[error("Invalid condition operator: {0}")]
     InvalidConditionOperator(String),
     #[error("Invalid complex condition operator: {0}")]
@@ -215,11 +231,37 @@ pub fn parse_event_handler(s: String) -> Result<Arc<ParsedScript>, TypeParsingEr
     }
 }
 
+/// Collects the handlers of an event declared both plainly (`ONDONE`, stored under an empty key)
+/// and with an argument suffix (`ONDONE^ARGUMENT`).
+pub fn parse_event_handlers(
+    properties: &HashMap<String, String>,
+    event_name: &str,
+) -> Result<HashMap<String, Arc<ParsedScript>>, TypeParsingError> {
+    let mut handlers = HashMap::new();
+    for (k, v) in properties.iter() {
+        let argument = if k == event_name {
+            ""
+        } else if let Some(argument) = k
+            .strip_prefix(event_name)
+            .and_then(|rest| rest.strip_prefix('^'))
+        {
+            argument
+        } else {
+            continue;
+        };
+        if let Some(code) = discard_if_empty(v.to_owned()) {
+            handlers.insert(argument.to_owned(), parse_event_handler(code)?);
+        }
+    }
+    Ok(handlers)
+}
+
 pub fn parse_rect(s: String) -> Result<ReferenceRect, TypeParsingError> {
     if s.contains(',') {
         s.split(',')
-            .map(|s| s.parse().unwrap())
-            .collect_tuple()
+            .map(|s| s.trim().parse().ok())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|v| v.into_iter().collect_tuple())
             .map(|t: (isize, isize, isize, isize)| ReferenceRect::Literal(t.into()))
             .ok_or(TypeParsingError::InvalidRectLiteral(s))
     } else {
@@ -227,6 +269,35 @@ pub fn parse_rect(s: String) -> Result<ReferenceRect, TypeParsingError> {
     }
 }
 
+/// Parses a color given as `#RRGGBB` / `0xRRGGBB`, as `R,G,B` components or as a packed decimal.
+pub fn parse_color(s: String) -> Result<(u8, u8, u8), TypeParsingError> {
+    let unpack = |v: u32| ((v >> 16) as u8, (v >> 8) as u8, v as u8);
+    let trimmed = s.trim();
+    let hex = trimmed
+        .strip_prefix('#')
+        .or_else(|| trimmed.strip_prefix("0x"))
+        .or_else(|| trimmed.strip_prefix("0X"));
+    let color = if let Some(hex) = hex {
+        u32::from_str_radix(hex, 16)
+            .ok()
+            .filter(|_| hex.len() == 6)
+            .map(unpack)
+    } else if trimmed.contains(',') {
+        trimmed
+            .split(',')
+            .map(|c| c.trim().parse::<u8>().ok())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|c| c.into_iter().collect_tuple())
+    } else {
+        trimmed
+            .parse::<u32>()
+            .ok()
+            .filter(|v| *v <= 0xffffff)
+            .map(unpack)
+    };
+    color.ok_or(TypeParsingError::InvalidColorLiteral(s))
+}
+
 pub fn discard_if_empty(s: String) -> Option<String> {
     if s.is_empty() {
         None
@@ -1,5 +1,7 @@
 use std::{cell::RefCell, hash::Hash, sync::Arc};
 
+use itertools::Itertools;
+
 use super::{containers::ObjectContainer, path::ScenePath, CnvObject, CnvRunner};
 
 #[derive(Clone)]
@@ -98,6 +100,16 @@ impl CnvScript {
     pub fn add_object(&self, object: Arc<CnvObject>) -> anyhow::Result<()> {
         self.objects.borrow_mut().push_object(object)
     }
+
+    /// Lists external files (images, animations, sounds, etc.) referenced by the objects.
+    pub fn collect_referenced_files(&self) -> Vec<String> {
+        self.objects
+            .borrow()
+            .iter()
+            .flat_map(|o| o.content.get_referenced_files())
+            .unique()
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
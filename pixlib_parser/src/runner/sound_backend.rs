@@ -0,0 +1,43 @@
+use super::{
+    common::{SoundData, SoundParameters},
+    events::{SoundEvent, SoundSource},
+};
+
+/// Audio output driven by the runner's outgoing sound events.
+pub trait SoundBackend {
+    /// Called when new sound data becomes available for the source.
+    /// Backends which manage decoding on their own may ignore it.
+    fn load(&mut self, _source: &SoundSource, _sound_data: &SoundData) {}
+    fn play(&mut self, source: &SoundSource);
+    fn stop(&mut self, source: &SoundSource);
+    fn pause(&mut self, source: &SoundSource);
+    fn resume(&mut self, source: &SoundSource);
+    fn set_volume(&mut self, source: &SoundSource, volume: f32);
+    fn set_pan(&mut self, source: &SoundSource, pan: f32);
+    fn set_frequency(&mut self, _source: &SoundSource, _frequency: Option<usize>) {}
+}
+
+impl SoundEvent {
+    pub fn apply_to(&self, backend: &mut (impl SoundBackend + ?Sized)) {
+        match self {
+            SoundEvent::SoundLoaded { source, sound_data } => backend.load(source, sound_data),
+            SoundEvent::SoundParametersChanged {
+                source,
+                parameters:
+                    SoundParameters {
+                        volume,
+                        pan,
+                        frequency,
+                    },
+            } => {
+                backend.set_volume(source, *volume);
+                backend.set_pan(source, *pan);
+                backend.set_frequency(source, *frequency);
+            }
+            SoundEvent::SoundStarted(source) => backend.play(source),
+            SoundEvent::SoundPaused(source) => backend.pause(source),
+            SoundEvent::SoundResumed(source) => backend.resume(source),
+            SoundEvent::SoundStopped(source) => backend.stop(source),
+        }
+    }
+}
@@ -0,0 +1,2347 @@
+use crate::runner::common::SoundParameters;
+
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_animation(sequences: &[TestSequence]) -> Arc<CnvRunner> {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("TEST.ANN", build_ann(sequences))
+        .with_file("SFX.WAV", b"RIFF".to_vec());
+    CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn frame_sfx_should_be_played_with_set_volume() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: Some("SFX.WAV"),
+        }],
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^SETVOLUME(500);TESTANIM^SETPAN(-1000);TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let expected_source = SoundSource::AnimationSfx {
+        script_path: ScenePath::new(".", "SCRIPT.CNV"),
+        object_name: "TESTANIM".into(),
+    };
+    let sound_events = runner.events_out.sound.borrow();
+    let parameters = sound_events.iter().find_map(|e| match e {
+        SoundEvent::SoundParametersChanged { source, parameters } if *source == expected_source => {
+            Some(*parameters)
+        }
+        _ => None,
+    });
+    assert_eq!(
+        parameters,
+        Some(SoundParameters {
+            volume: 0.5,
+            pan: -1.0,
+            frequency: None,
+        })
+    );
+    assert!(matches!(
+        sound_events.back(),
+        Some(SoundEvent::SoundStarted(source)) if *source == expected_source
+    ));
+}
+
+#[test]
+fn finished_sfx_should_clear_playing_state() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: Some("SFX.WAV"),
+        }],
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("PLAY"),
+            &[CnvValue::String("MAIN".into())],
+            None,
+        )
+        .unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert!(animation.is_playing_sfx().unwrap());
+
+    runner
+        .events_in
+        .multimedia
+        .borrow_mut()
+        .push_back(MultimediaEvents::SoundFinishedPlaying(
+            SoundSource::AnimationSfx {
+                script_path: ScenePath::new(".", "SCRIPT.CNV"),
+                object_name: "TESTANIM".into(),
+            },
+        ));
+    runner.step().unwrap();
+
+    assert!(!animation.is_playing_sfx().unwrap());
+}
+
+#[test_case("ATTACK_LEFT", "PATTERN")]
+#[test_case("ATTACK_RIGHT", "EXACT")]
+#[test_case("DEFEND", "DEFAULT")]
+fn signal_handlers_should_be_matched_by_precedence(signal: &str, expected: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:ONSIGNAL^ATTACK*={TESTSTR^SET("PATTERN");}
+        TESTANIM:ONSIGNAL^ATTACK_RIGHT={TESTSTR^SET("EXACT");}
+        TESTANIM:ONSIGNAL={TESTSTR^SET("DEFAULT");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Event("ONSIGNAL"),
+            &[CnvValue::String(signal.into())],
+            None,
+        )
+        .unwrap();
+    let test_str_object = runner.get_object("TESTSTR").unwrap();
+    let result = test_str_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(result, CnvValue::String(expected.into()));
+}
+
+#[test]
+fn equally_long_signal_patterns_should_be_matched_in_lexical_order() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:ONSIGNAL^*_LEFT={TESTSTR^SET("SUFFIX");}
+        TESTANIM:ONSIGNAL^ATTACK*={TESTSTR^SET("PREFIX");}
+        TESTANIM:ONSIGNAL^AT*LEFT={TESTSTR^SET("INFIX");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Event("ONSIGNAL"),
+            &[CnvValue::String("ATTACK_LEFT".into())],
+            None,
+        )
+        .unwrap();
+    let test_str_object = runner.get_object("TESTSTR").unwrap();
+    let result = test_str_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(result, CnvValue::String("INFIX".into()));
+}
+
+#[test_case("STOP()", 1)]
+#[test_case("STOP(TRUE)", 1)]
+#[test_case("STOP(FALSE)", 0)]
+fn stop_should_emit_finished_handlers_on_request(stop_call: &str, expected_count: i32) {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[
+            TestFrame {
+                name: "FIRST",
+                sprite_idx: 0,
+                offset_px: (0, 0),
+                sfx: None,
+            },
+            TestFrame {
+                name: "SECOND",
+                sprite_idx: 0,
+                offset_px: (0, 0),
+                sfx: None,
+            },
+        ],
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=FINISHEDCOUNT
+        FINISHEDCOUNT:TYPE=INTEGER
+        FINISHEDCOUNT:VALUE=0
+
+        OBJECT=DONECOUNT
+        DONECOUNT:TYPE=INTEGER
+        DONECOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:ONFINISHED={{FINISHEDCOUNT^INC();}}
+        TESTANIM:ONDONE={{DONECOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^PLAY("MAIN");TESTANIM^{};}}
+        "#,
+        stop_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    runner.step().unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert!(!animation.is_playing().unwrap());
+    for counter_name in ["FINISHEDCOUNT", "DONECOUNT"] {
+        let counter_object = runner.get_object(counter_name).unwrap();
+        let result = counter_object
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap();
+        assert_eq!(
+            result,
+            CnvValue::Integer(expected_count),
+            "{}",
+            counter_name
+        );
+    }
+}
+
+#[test_case("NPLAY()", 2)]
+#[test_case("PLAY(\"MAIN\")", 0)]
+fn nplay_should_continue_from_the_current_frame(play_call: &str, expected_frame: i32) {
+    let frames = ["FIRST", "SECOND", "THIRD", "FOURTH"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=STARTEDCOUNT
+        STARTEDCOUNT:TYPE=INTEGER
+        STARTEDCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:ONSTARTED={{STARTEDCOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^SETFRAME("MAIN", 2);TESTANIM^{};}}
+        "#,
+        play_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    runner.step().unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert!(animation.is_playing().unwrap());
+    let frame_no = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(expected_frame));
+    let started_count = runner
+        .get_object("STARTEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(started_count, CnvValue::Integer(1));
+}
+
+#[test]
+fn end_position_should_account_for_last_frame_offset() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[
+            TestFrame {
+                name: "FIRST",
+                sprite_idx: 0,
+                offset_px: (0, 0),
+                sfx: None,
+            },
+            TestFrame {
+                name: "LAST",
+                sprite_idx: 0,
+                offset_px: (15, -5),
+                sfx: None,
+            },
+        ],
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^SETPOSITION(100, 50);TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let end_x = test_anim_object
+        .call_method(CallableIdentifier::Method("GETENDX"), &Vec::new(), None)
+        .unwrap();
+    let end_y = test_anim_object
+        .call_method(CallableIdentifier::Method("GETENDY"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(
+        (end_x, end_y),
+        (CnvValue::Integer(115), CnvValue::Integer(45))
+    );
+}
+
+#[test_case("", |pan| pan < 0.0)]
+#[test_case("TESTANIM^SETPAN(500);", |pan| pan == 0.5)]
+fn sfx_pan_should_follow_object_position_unless_set(
+    set_pan_call: &str,
+    check_pan: fn(f32) -> bool,
+) {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "TEST.ANN",
+            build_ann(&[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: Some("SFX.WAV"),
+                }],
+            }]),
+        )
+        .with_file("SFX.WAV", b"RIFF".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^SETPOSITION(100, 300);{}TESTANIM^PLAY("MAIN");}}
+        "#,
+        set_pan_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let sound_events = runner.events_out.sound.borrow();
+    let pan = sound_events.iter().find_map(|e| match e {
+        SoundEvent::SoundParametersChanged { parameters, .. } => Some(parameters.pan),
+        _ => None,
+    });
+    assert!(pan.is_some_and(check_pan), "{:?}", pan);
+}
+
+#[test_case("ISAT(11, 21)", true)]
+#[test_case("ISAT(9, 21)", false)]
+#[test_case("ISAT(12, 20)", false)]
+#[test_case("ISAT(10, 20)", true)]
+#[test_case("ISAT(10, 20, TRUE)", false)]
+#[test_case("ISAT(11, 21, TRUE)", true)]
+#[test_case("ISINSIDE(0, 0, 12, 22)", true)]
+#[test_case("ISINSIDE(0, 0, 11, 22)", false)]
+fn point_and_rect_tests_should_use_frame_rect(method_call: &str, expected: bool) {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_alpha_masks(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }],
+            &[((2, 2), vec![0x00, 0xff, 0xff, 0xff])],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=RESULT
+        RESULT:TYPE=BOOL
+        RESULT:VALUE=FALSE
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^SETPOSITION(10, 20);RESULT^SET(TESTANIM^{});}}
+        "#,
+        method_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let result = runner
+        .get_object("RESULT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Bool(expected));
+}
+
+#[test_case("", 255)]
+#[test_case("TESTANIM^SETOPACITY(128);", 128)]
+#[test_case("TESTANIM^SETOPACITY(300);", 255)]
+#[test_case("TESTANIM^SETOPACITY(-5);", 0)]
+fn alpha_should_reflect_object_opacity(set_opacity_call: &str, expected: i32) {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        }],
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=RESULT
+        RESULT:TYPE=INTEGER
+        RESULT:VALUE=-1
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{{}RESULT^SET(TESTANIM^GETALPHA());}}
+        "#,
+        set_opacity_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let result = runner
+        .get_object("RESULT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(expected));
+}
+
+#[test_case(3, true, 1, 0)]
+#[test_case(0, false, 2, 1)]
+fn looping_sequences_should_wrap_around_instead_of_finishing(
+    looping_after: u32,
+    expected_playing: bool,
+    expected_frame: i32,
+    expected_finished_count: i32,
+) {
+    let frames = ["FIRST", "SECOND", "THIRD"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after,
+        frames: &frames,
+    }]);
+    let script = r#"
+        OBJECT=FINISHEDCOUNT
+        FINISHEDCOUNT:TYPE=INTEGER
+        FINISHEDCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONFINISHED={FINISHEDCOUNT^INC();}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    // enough frame steps to pass the end of the sequence twice
+    for _ in 0..7 {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert_eq!(animation.is_playing().unwrap(), expected_playing);
+    let frame_no = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(expected_frame));
+    let finished_count = runner
+        .get_object("FINISHEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(finished_count, CnvValue::Integer(expected_finished_count));
+}
+
+#[test]
+fn render_frame_should_not_depend_on_playback_state() {
+    let frames = [("FIRST", 0), ("SECOND", 0), ("THIRD", 1)].map(|(name, sprite_idx)| TestFrame {
+        name,
+        sprite_idx,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &frames,
+            }],
+            &[(1, 1), (2, 2)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    let (first_rect, first_sprite) = animation.render_frame(0, 0).unwrap();
+    let (third_rect, third_sprite) = animation.render_frame(0, 2).unwrap();
+    assert_eq!(first_rect, Rect::from((0, 0), (1, 1)));
+    assert_eq!(third_rect, Rect::from((0, 0), (2, 2)));
+    assert_ne!(first_sprite.hash, third_sprite.hash);
+    assert!(animation.render_frame(0, 3).is_err());
+    assert!(animation.render_frame(1, 0).is_err());
+    let frame_no = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(0));
+}
+
+#[test]
+fn volume_change_should_update_playing_sfx() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: Some("SFX.WAV"),
+        }],
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("PLAY"),
+            &[CnvValue::String("MAIN".into())],
+            None,
+        )
+        .unwrap();
+    runner.events_out.sound.borrow_mut().clear();
+
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETVOLUME"),
+            &[CnvValue::Integer(250)],
+            None,
+        )
+        .unwrap();
+
+    let expected_source = SoundSource::AnimationSfx {
+        script_path: ScenePath::new(".", "SCRIPT.CNV"),
+        object_name: "TESTANIM".into(),
+    };
+    let sound_events = runner.events_out.sound.borrow();
+    assert_eq!(sound_events.len(), 1);
+    assert!(matches!(
+        sound_events.front(),
+        Some(SoundEvent::SoundParametersChanged { source, parameters })
+            if *source == expected_source && parameters.volume == 0.25
+    ));
+}
+
+#[test]
+fn volume_change_should_not_emit_events_without_playing_sfx() {
+    let runner = create_runner_with_animation(&[]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.events_out.sound.borrow_mut().clear();
+
+    runner
+        .get_object("TESTANIM")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("SETVOLUME"),
+            &[CnvValue::Integer(250)],
+            None,
+        )
+        .unwrap();
+
+    assert!(runner.events_out.sound.borrow().is_empty());
+}
+
+#[test]
+fn cframe_in_event_should_track_playback() {
+    let frames = ["FIRST", "SECOND", "THIRD", "FOURTH"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[
+        TestSequence {
+            name: "IDLE",
+            looping_after: 0,
+            frames: &frames[..1],
+        },
+        TestSequence {
+            name: "MAIN",
+            looping_after: 0,
+            frames: &frames,
+        },
+    ]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    for expected_frame in 0..3 {
+        let frame_in_event = test_anim_object
+            .call_method(
+                CallableIdentifier::Method("GETCFRAMEINEVENT"),
+                &Vec::new(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(frame_in_event, CnvValue::Integer(expected_frame));
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+}
+
+#[test_case("GETPOSITIONX", 100)]
+#[test_case("GETPOSITIONY", 50)]
+#[test_case("GETCURRFRAMEPOSX", 100)]
+#[test_case("GETCURRFRAMEPOSY", 50)]
+#[test_case("GETCENTERX", 102)]
+#[test_case("GETCENTERY", 53)]
+#[test_case("GETENDX", 100)]
+#[test_case("GETENDY", 50)]
+#[test_case("GETWIDTH", 4)]
+#[test_case("GETHEIGHT", 6)]
+fn geometry_getters_should_return_integers(method: &str, expected: i32) {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }],
+            &[(4, 6)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^SETPOSITION(100, 50);TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let result = runner
+        .get_object("TESTANIM")
+        .unwrap()
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(expected));
+}
+
+#[test_case("GETCURRFRAMEPOSX", 100)]
+#[test_case("GETCURRFRAMEPOSY", 50)]
+#[test_case("GETCENTERX", 102)]
+#[test_case("GETCENTERY", 53)]
+#[test_case("GETENDX", 100)]
+#[test_case("GETENDY", 50)]
+fn geometry_getters_should_load_animation_data(method: &str, expected: i32) {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }],
+            &[(4, 6)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^SETPOSITION(100, 50);}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let result = runner
+        .get_object("TESTANIM")
+        .unwrap()
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(expected));
+}
+
+#[test]
+fn reloaded_resource_should_be_read_again() {
+    let frames = [TestFrame {
+        name: "FIRST",
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    }];
+    let sequences = [TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }];
+    let filesystem = MemoryFileSystem::default()
+        .with_file("TEST.ANN", build_ann_with_sprites(&sequences, &[(1, 1)]));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    let (old_rect, old_sprite) = animation.render_frame(0, 0).unwrap();
+
+    runner
+        .filesystem
+        .write()
+        .unwrap()
+        .write_file("TEST.ANN", &build_ann_with_sprites(&sequences, &[(2, 2)]))
+        .unwrap();
+    assert_eq!(animation.render_frame(0, 0).unwrap().0, old_rect);
+    assert_eq!(runner.reload_resource("test.ann"), 1);
+
+    let (new_rect, new_sprite) = animation.render_frame(0, 0).unwrap();
+    assert_eq!(new_rect, Rect::from((0, 0), (2, 2)));
+    assert_ne!(new_sprite.hash, old_sprite.hash);
+    assert_eq!(runner.reload_resource("OTHER.ANN"), 0);
+}
+
+#[test_case("WALK", "WALK", 1, 0)]
+#[test_case("IDLE", "WALK", 0, 1)]
+#[test_case("WALK", "WA*", 1, 0)]
+#[test_case("IDLE", "WA*", 0, 1)]
+fn sequence_specific_handlers_should_take_precedence_over_fallback(
+    sequence_name: &str,
+    handler_key: &str,
+    expected_specific_count: i32,
+    expected_fallback_count: i32,
+) {
+    let frames = ["FIRST", "SECOND"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[
+        TestSequence {
+            name: "WALK",
+            looping_after: 0,
+            frames: &frames,
+        },
+        TestSequence {
+            name: "IDLE",
+            looping_after: 0,
+            frames: &frames,
+        },
+    ]);
+    let script = format!(
+        r#"
+        OBJECT=STARTEDCOUNT
+        STARTEDCOUNT:TYPE=INTEGER
+        STARTEDCOUNT:VALUE=0
+
+        OBJECT=WALKCOUNT
+        WALKCOUNT:TYPE=INTEGER
+        WALKCOUNT:VALUE=0
+
+        OBJECT=FALLBACKCOUNT
+        FALLBACKCOUNT:TYPE=INTEGER
+        FALLBACKCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONSTARTED^WALK={{STARTEDCOUNT^INC();}}
+        TESTANIM:ONFINISHED^{}={{WALKCOUNT^INC();}}
+        TESTANIM:ONFINISHED={{FALLBACKCOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^PLAY("{}");}}
+        "#,
+        handler_key, sequence_name
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    for _ in 0..4 {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+
+    let get_count = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(
+        get_count("STARTEDCOUNT"),
+        CnvValue::Integer(expected_specific_count)
+    );
+    assert_eq!(
+        get_count("WALKCOUNT"),
+        CnvValue::Integer(expected_specific_count)
+    );
+    assert_eq!(
+        get_count("FALLBACKCOUNT"),
+        CnvValue::Integer(expected_fallback_count)
+    );
+}
+
+fn run_animation_with_deltas(frame_count: usize, deltas: &[f64]) -> Arc<CnvRunner> {
+    let frames = (0..frame_count)
+        .map(|_| TestFrame {
+            name: "FRAME",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        })
+        .collect::<Vec<_>>();
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = r#"
+        OBJECT=FINISHEDCOUNT
+        FINISHEDCOUNT:TYPE=INTEGER
+        FINISHEDCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONFINISHED={FINISHEDCOUNT^INC();}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    for &seconds in deltas {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed { seconds });
+        runner.step().unwrap();
+    }
+    runner
+}
+
+#[test_case(&[0.0625; 5], 5 ; "regular ticks")]
+#[test_case(&[0.03125, 0.03125, 0.5, 0.015625, 0.046875], 10 ; "variable ticks with long frame")]
+#[test_case(&[0.5, -1.0, f64::NAN], 8 ; "invalid deltas ignored")]
+fn animation_should_advance_with_elapsed_time(deltas: &[f64], expected_frame: i32) {
+    let runner = run_animation_with_deltas(40, deltas);
+    let frame_no = runner
+        .get_object("TESTANIM")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(expected_frame));
+    assert_eq!(
+        runner.clock.borrow().get_elapsed_seconds(),
+        expected_frame as f64 / 16.0
+    );
+}
+
+#[test]
+fn long_frame_should_finish_animation_once() {
+    let runner = run_animation_with_deltas(3, &[1.0]);
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert!(!animation.is_playing().unwrap());
+    let finished_count = runner
+        .get_object("FINISHEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(finished_count, CnvValue::Integer(1));
+}
+
+#[test]
+fn merge_alpha_without_source_should_bake_frame_into_canvas() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "TEST.ANN",
+            build_ann(&[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }]),
+        )
+        .with_file("BLACK.IMG", build_img((4, 4), (0, 0), 0));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (4, 4),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=BLACKIMG
+        BLACKIMG:TYPE=IMAGE
+        BLACKIMG:FILENAME=BLACK.IMG
+        BLACKIMG:VISIBLE=FALSE
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={CANVAS_OBSERVER^SETBACKGROUND("BLACKIMG");TESTANIM^SETPOSITION(1, 2);TESTANIM^MERGEALPHA();TESTANIM^HIDE();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let canvas_observer_object = runner.get_object("CANVAS_OBSERVER").unwrap();
+    let CnvContent::CanvasObserver(ref canvas_observer) = &canvas_observer_object.content else {
+        unreachable!();
+    };
+    let (background_rect, _, background_data) =
+        canvas_observer.get_background_to_show().unwrap().unwrap();
+    let (_, screenshot) = runner
+        .get_screenshot(Some((background_rect, background_data.data)))
+        .unwrap();
+    let baked_offset = (2 * 4 + 1) * 4;
+    assert_eq!(screenshot[baked_offset..baked_offset + 4], [0xff; 4]);
+    assert_eq!(screenshot[0..4], [0, 0, 0, 0xff]);
+}
+
+#[test_case("", 0 ; "plain animation")]
+#[test_case("TESTANIM^SETASBUTTON(TRUE, FALSE);", 1 ; "animation set as button")]
+#[test_case("TESTANIM^SETASBUTTON(TRUE, FALSE);TESTANIM^SETASBUTTON(FALSE, FALSE);", 0 ; "animation unset as button")]
+fn only_animations_set_as_buttons_should_receive_clicks(set_as_button_call: &str, expected: i32) {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }],
+            &[(10, 10)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=CLICKCOUNT
+        CLICKCOUNT:TYPE=INTEGER
+        CLICKCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:ONCLICK={{CLICKCOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{{}}}
+        "#,
+        set_as_button_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let buttons = runner.get_enabled_buttons().unwrap();
+    if let Some(button_idx) = runner.find_relevant_button(&buttons, (5, 5)).unwrap() {
+        let CnvContent::Animation(animation) = &buttons[button_idx].object.content else {
+            unreachable!();
+        };
+        animation.handle_lmb_pressed().unwrap();
+    }
+    runner.step().unwrap();
+
+    let click_count = runner
+        .get_object("CLICKCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(click_count, CnvValue::Integer(expected));
+}
+
+fn create_runner_with_frame_changed_counter() -> Arc<CnvRunner> {
+    let frames = ["FIRST", "SECOND", "THIRD"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[
+        TestSequence {
+            name: "IDLE",
+            looping_after: 0,
+            frames: &frames[..1],
+        },
+        TestSequence {
+            name: "MAIN",
+            looping_after: 0,
+            frames: &frames,
+        },
+    ]);
+    let script = r#"
+        OBJECT=CHANGEDCOUNT
+        CHANGEDCOUNT:TYPE=INTEGER
+        CHANGEDCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:ONFRAMECHANGED^MAIN={CHANGEDCOUNT^INC();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test_case(1, 1)]
+#[test_case(2, 2)]
+#[test_case(3, 2)]
+#[test_case(100, 2)]
+fn set_frame_should_clamp_to_last_frame(frame_no: i32, expected_frame: i32) {
+    let runner = create_runner_with_frame_changed_counter();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETFRAME"),
+            &[
+                CnvValue::String("MAIN".to_owned()),
+                CnvValue::Integer(frame_no),
+            ],
+            None,
+        )
+        .unwrap();
+    runner.step().unwrap();
+
+    let frame = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame, CnvValue::Integer(expected_frame));
+    let changed_count = runner
+        .get_object("CHANGEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(changed_count, CnvValue::Integer(1));
+}
+
+#[test]
+fn set_frame_by_sprite_index_should_clamp_to_last_sprite() {
+    let runner = create_runner_with_frame_changed_counter();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETFRAME"),
+            &[CnvValue::String("MAIN".to_owned()), CnvValue::Integer(0)],
+            None,
+        )
+        .unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETFRAME"),
+            &[CnvValue::Integer(100)],
+            None,
+        )
+        .unwrap();
+    runner.step().unwrap();
+
+    let CnvContent::Animation(animation) = &test_anim_object.content else {
+        panic!();
+    };
+    assert!(animation.get_frame_to_show().unwrap().is_some());
+    let changed_count = runner
+        .get_object("CHANGEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(changed_count, CnvValue::Integer(2));
+}
+
+#[test]
+fn set_frame_with_unknown_sequence_should_fail() {
+    let runner = create_runner_with_frame_changed_counter();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let error = test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETFRAME"),
+            &[CnvValue::String("MISSING".to_owned()), CnvValue::Integer(0)],
+            None,
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::SequenceNameNotFound { sequence_name, .. }) if sequence_name == "MISSING"
+    ));
+}
+
+#[test_case("SETONFF(TRUE)", 1)]
+#[test_case("SETONFF(FALSE)", 0)]
+fn set_onff_should_gate_first_frame_events(set_onff_call: &str, expected_count: i32) {
+    let frames = ["FIRST", "SECOND", "THIRD"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=FIRSTFRAMECOUNT
+        FIRSTFRAMECOUNT:TYPE=INTEGER
+        FIRSTFRAMECOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONFIRSTFRAME={{FIRSTFRAMECOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^{};TESTANIM^PLAY("MAIN");}}
+        "#,
+        set_onff_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    runner
+        .events_in
+        .timer
+        .borrow_mut()
+        .push_back(TimerEvent::Elapsed {
+            seconds: 1.0 / 16.0,
+        });
+    runner.step().unwrap();
+
+    let frame_no = runner
+        .get_object("TESTANIM")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(1));
+    let first_frame_count = runner
+        .get_object("FIRSTFRAMECOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(first_frame_count, CnvValue::Integer(expected_count));
+}
+
+#[test]
+fn separating_animations_should_emit_collision_finished_on_both() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        }],
+    }]);
+    let script = r#"
+        OBJECT=COLLISIONLOG
+        COLLISIONLOG:TYPE=STRING
+
+        OBJECT=FIRSTANIM
+        FIRSTANIM:TYPE=ANIMO
+        FIRSTANIM:FILENAME=TEST.ANN
+        FIRSTANIM:MONITORCOLLISION=TRUE
+        FIRSTANIM:ONCOLLISION={COLLISIONLOG^ADD("FIRST+");}
+        FIRSTANIM:ONCOLLISIONFINISHED={COLLISIONLOG^ADD("FIRST-");}
+
+        OBJECT=SECONDANIM
+        SECONDANIM:TYPE=ANIMO
+        SECONDANIM:FILENAME=TEST.ANN
+        SECONDANIM:MONITORCOLLISION=TRUE
+        SECONDANIM:ONCOLLISION^FIRSTANIM={COLLISIONLOG^ADD("SECOND+");}
+        SECONDANIM:ONCOLLISIONFINISHED^FIRSTANIM={COLLISIONLOG^ADD("SECOND-");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let second_anim_object = runner.get_object("SECONDANIM").unwrap();
+    let collision_log_object = runner.get_object("COLLISIONLOG").unwrap();
+    let step_at = |position: i32| {
+        second_anim_object
+            .call_method(
+                CallableIdentifier::Method("SETPOSITION"),
+                &[CnvValue::Integer(position), CnvValue::Integer(position)],
+                None,
+            )
+            .unwrap();
+        runner.step().unwrap();
+        let log = collision_log_object
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap();
+        collision_log_object
+            .call_method(
+                CallableIdentifier::Method("SET"),
+                &[CnvValue::String(String::new())],
+                None,
+            )
+            .unwrap();
+        log
+    };
+
+    assert_eq!(step_at(100), CnvValue::String(String::new()));
+    assert_eq!(step_at(1), CnvValue::String("FIRST+SECOND+".into()));
+    assert_eq!(step_at(100), CnvValue::String("FIRST-SECOND-".into()));
+    assert_eq!(step_at(100), CnvValue::String(String::new()));
+}
+
+#[test_case("" ; "while playing")]
+#[test_case("TESTANIM^PAUSE();" ; "while paused")]
+fn play_should_restart_the_current_sequence(interruption: &str) {
+    let frames = ["FIRST", "SECOND", "THIRD", "FOURTH"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=STARTEDCOUNT
+        STARTEDCOUNT:TYPE=INTEGER
+        STARTEDCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONSTARTED^MAIN={{STARTEDCOUNT^INC();}}
+
+        OBJECT=PLAYBEH
+        PLAYBEH:TYPE=BEHAVIOUR
+        PLAYBEH:CODE={{TESTANIM^PLAY("MAIN");}}
+
+        OBJECT=INTERRUPTBEH
+        INTERRUPTBEH:TYPE=BEHAVIOUR
+        INTERRUPTBEH:CODE={{{}}}
+        "#,
+        interruption
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let run = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+            .unwrap();
+    };
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let get_frame_no = || {
+        test_anim_object
+            .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+            .unwrap()
+    };
+
+    run("PLAYBEH");
+    runner
+        .events_in
+        .timer
+        .borrow_mut()
+        .push_back(TimerEvent::Elapsed {
+            seconds: 2.5 / 16.0,
+        });
+    runner.step().unwrap();
+    assert_eq!(get_frame_no(), CnvValue::Integer(2));
+    run("INTERRUPTBEH");
+    run("PLAYBEH");
+    runner.step().unwrap();
+
+    assert_eq!(get_frame_no(), CnvValue::Integer(0));
+    let is_playing = test_anim_object
+        .call_method(CallableIdentifier::Method("ISPLAYING"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(is_playing, CnvValue::Bool(true));
+    let started_count = runner
+        .get_object("STARTEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(started_count, CnvValue::Integer(2));
+
+    // the half frame elapsed before the restart must not carry over
+    runner
+        .events_in
+        .timer
+        .borrow_mut()
+        .push_back(TimerEvent::Elapsed {
+            seconds: 0.75 / 16.0,
+        });
+    runner.step().unwrap();
+    assert_eq!(get_frame_no(), CnvValue::Integer(0));
+}
+
+#[test]
+fn sprite_sheet_should_tile_all_sprites() {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (0, 0),
+                    sfx: None,
+                }],
+            }],
+            &[(1, 1), (4, 2), (2, 6)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        panic!();
+    };
+    let (width, height, data) = animation.export_sprite_sheet().unwrap();
+
+    assert_eq!((width, height), (8, 12));
+    assert_eq!(data.len(), 8 * 12 * 4);
+    assert_eq!(&data[..4], &[255, 255, 255, 255]);
+    assert_eq!(&data[4..8], &[0, 0, 0, 0]);
+}
+
+#[test_case("FALSE")]
+#[test_case("TRUE")]
+fn one_shot_should_emit_done_once_and_hold_last_frame(flush_after_played: &str) {
+    let frames = ["FIRST", "SECOND", "THIRD"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=DONECOUNT
+        DONECOUNT:TYPE=INTEGER
+        DONECOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:FLUSHAFTERPLAYED={flush_after_played}
+        TESTANIM:ONDONE={{DONECOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^PLAY("MAIN");}}
+        "#
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    for _ in 0..7 {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let CnvContent::Animation(ref animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    assert!(!animation.is_playing().unwrap());
+    let frame_no = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame_no, CnvValue::Integer(2));
+    assert!(animation.get_frame_to_show().unwrap().is_some());
+    let done_count = runner
+        .get_object("DONECOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(done_count, CnvValue::Integer(1));
+}
+
+#[test]
+fn priority_and_opacity_getters_should_return_integers() {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        }],
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:PRIORITY=7
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETOPACITY"),
+            &[CnvValue::Integer(128)],
+            None,
+        )
+        .unwrap();
+
+    let priority = test_anim_object
+        .call_method(CallableIdentifier::Method("GETPRIORITY"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(priority, CnvValue::Integer(7));
+    let opacity = test_anim_object
+        .call_method(CallableIdentifier::Method("GETOPACITY"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(opacity, CnvValue::Integer(128));
+}
+
+#[test]
+fn size_getters_should_follow_the_current_frame() {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[
+                    TestFrame {
+                        name: "SMALL",
+                        sprite_idx: 0,
+                        offset_px: (0, 0),
+                        sfx: None,
+                    },
+                    TestFrame {
+                        name: "WIDE",
+                        sprite_idx: 1,
+                        offset_px: (0, 0),
+                        sfx: None,
+                    },
+                ],
+            }],
+            &[(4, 6), (8, 3)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let get_size = || {
+        ["GETWIDTH", "GETHEIGHT", "GETMAXWIDTH", "GETMAXHEIGHT"].map(|method| {
+            test_anim_object
+                .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+                .unwrap()
+        })
+    };
+    assert_eq!(
+        get_size(),
+        [4, 6, 8, 6].map(CnvValue::Integer),
+        "first frame"
+    );
+
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETFRAME"),
+            &[CnvValue::String("MAIN".to_owned()), CnvValue::Integer(1)],
+            None,
+        )
+        .unwrap();
+    runner.step().unwrap();
+
+    assert_eq!(
+        get_size(),
+        [8, 3, 8, 6].map(CnvValue::Integer),
+        "second frame"
+    );
+}
+
+#[test_case("TESTANIM:FILENAME=TEST.ANN", true, true ; "with data")]
+#[test_case("TESTANIM:FILENAME=TEST.ANN", false, false ; "with data not loaded yet")]
+#[test_case("", false, false ; "without data")]
+fn is_visible_should_require_loaded_data(filename_line: &str, load: bool, expected: bool) {
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        }],
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        {}
+        "#,
+        filename_line
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    if load {
+        test_anim_object
+            .call_method(
+                CallableIdentifier::Method("LOAD"),
+                &[CnvValue::String("TEST.ANN".to_owned())],
+                None,
+            )
+            .unwrap();
+    }
+    test_anim_object
+        .call_method(CallableIdentifier::Method("SHOW"), &Vec::new(), None)
+        .unwrap();
+
+    let is_visible = test_anim_object
+        .call_method(CallableIdentifier::Method("ISVISIBLE"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(is_visible, CnvValue::Bool(expected));
+}
+
+#[test]
+fn frame_name_should_follow_playback() {
+    let frames = ["FIRST", "SECOND", "THIRD"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^PLAY("MAIN");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    for expected_name in ["FIRST", "SECOND", "THIRD"] {
+        let frame_name = test_anim_object
+            .call_method(
+                CallableIdentifier::Method("GETFRAMENAME"),
+                &Vec::new(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(frame_name, CnvValue::String(expected_name.to_owned()));
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+}
+
+#[test_case(0, 2 ; "paused")]
+#[test_case(16, 4 ; "forward")]
+#[test_case(8, 3 ; "forward at half rate")]
+#[test_case(-16, 0 ; "reverse")]
+fn fps_sign_should_control_playback_direction(fps: i32, expected_frame: i32) {
+    let frames = ["A", "B", "C", "D", "E"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &frames,
+    }]);
+    let script = format!(
+        r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^PLAY("MAIN");TESTANIM^SETFRAME("MAIN", 2);TESTANIM^SETFPS({});}}
+        "#,
+        fps
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    for _ in 0..2 {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+    }
+
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let frame = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFRAMENO"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(frame, CnvValue::Integer(expected_frame));
+    let reported_fps = test_anim_object
+        .call_method(CallableIdentifier::Method("GETFPS"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(reported_fps, CnvValue::Integer(fps));
+}
+
+#[test]
+fn center_anchored_animation_should_center_on_set_position() {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: "MAIN",
+                looping_after: 0,
+                frames: &[TestFrame {
+                    name: "FIRST",
+                    sprite_idx: 0,
+                    offset_px: (3, 5),
+                    sfx: None,
+                }],
+            }],
+            &[(10, 20)],
+        ),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETANCHOR"),
+            &[CnvValue::String("CENTER".into())],
+            None,
+        )
+        .unwrap();
+    test_anim_object
+        .call_method(
+            CallableIdentifier::Method("SETPOSITION"),
+            &[CnvValue::Integer(100), CnvValue::Integer(100)],
+            None,
+        )
+        .unwrap();
+
+    let CnvContent::Animation(animation) = &test_anim_object.content else {
+        unreachable!();
+    };
+    let rect = animation.get_frame_rect().unwrap();
+    assert_eq!(
+        (
+            (rect.top_left_x + rect.bottom_right_x) / 2,
+            (rect.top_left_y + rect.bottom_right_y) / 2
+        ),
+        (100, 100)
+    );
+    assert_eq!(
+        test_anim_object
+            .call_method(CallableIdentifier::Method("GETANCHOR"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::String("CENTER".into())
+    );
+}
+
+#[test]
+fn set_fps_should_survive_reloading() {
+    let sequences = [TestSequence {
+        name: "MAIN",
+        looping_after: 0,
+        frames: &[TestFrame {
+            name: "FIRST",
+            sprite_idx: 0,
+            offset_px: (0, 0),
+            sfx: None,
+        }],
+    }];
+    let mut fast_ann = build_ann(&sequences);
+    // frames per second field of the header
+    fast_ann[23..27].copy_from_slice(&25u32.to_le_bytes());
+    let filesystem = MemoryFileSystem::default()
+        .with_file("TEST.ANN", build_ann(&sequences))
+        .with_file("FAST.ANN", fast_ann);
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let call = |method: &str, arguments: &[CnvValue]| {
+        test_anim_object
+            .call_method(CallableIdentifier::Method(method), arguments, None)
+            .unwrap()
+    };
+
+    call("LOAD", &[CnvValue::String("FAST.ANN".into())]);
+    assert_eq!(call("GETFPS", &[]), CnvValue::Integer(25));
+
+    call("SETFPS", &[CnvValue::Integer(10)]);
+    call("LOAD", &[CnvValue::String("TEST.ANN".into())]);
+    assert_eq!(call("GETFPS", &[]), CnvValue::Integer(10));
+}
+
+#[test]
+fn queued_sequence_should_start_after_the_current_one_finishes() {
+    let frames = ["FIRST", "SECOND"].map(|name| TestFrame {
+        name,
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let runner = create_runner_with_animation(&[
+        TestSequence {
+            name: "INTRO",
+            looping_after: 0,
+            frames: &frames,
+        },
+        TestSequence {
+            name: "OUTRO",
+            looping_after: 0,
+            frames: &frames,
+        },
+    ]);
+    let script = r#"
+        OBJECT=EVENTLOG
+        EVENTLOG:TYPE=STRING
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+        TESTANIM:FPS=16
+        TESTANIM:ONSTARTED^INTRO={EVENTLOG^ADD("+INTRO");}
+        TESTANIM:ONFINISHED^INTRO={EVENTLOG^ADD("-INTRO");}
+        TESTANIM:ONSTARTED^OUTRO={EVENTLOG^ADD("+OUTRO");}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^PLAYQUEUED("INTRO");TESTANIM^PLAYQUEUED("OUTRO");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    let step_and_get_sequence = || {
+        runner
+            .events_in
+            .timer
+            .borrow_mut()
+            .push_back(TimerEvent::Elapsed {
+                seconds: 1.0 / 16.0,
+            });
+        runner.step().unwrap();
+        test_anim_object
+            .call_method(
+                CallableIdentifier::Method("GETEVENTNAME"),
+                &Vec::new(),
+                None,
+            )
+            .unwrap()
+    };
+
+    assert_eq!(step_and_get_sequence(), CnvValue::String("INTRO".into()));
+    assert_eq!(step_and_get_sequence(), CnvValue::String("OUTRO".into()));
+    assert_eq!(
+        runner
+            .get_object("EVENTLOG")
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::String("+INTRO-INTRO+OUTRO".into())
+    );
+}
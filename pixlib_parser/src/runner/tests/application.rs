@@ -0,0 +1,360 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test]
+fn exit_should_queue_application_exited_event() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTAPP
+        TESTAPP:TYPE=APPLICATION
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTAPP^EXIT();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+    assert!(runner.events_out.app.borrow().is_empty());
+
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(
+        runner.events_out.app.borrow().iter().collect::<Vec<_>>(),
+        vec![&ApplicationEvent::ApplicationExited]
+    );
+}
+
+#[test]
+fn run_should_return_result_of_called_behaviour() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTAPP
+        TESTAPP:TYPE=APPLICATION
+
+        OBJECT=RESULT
+        RESULT:TYPE=INTEGER
+        RESULT:VALUE=0
+
+        OBJECT=ECHOBEH
+        ECHOBEH:TYPE=BEHAVIOUR
+        ECHOBEH:CODE={@RETURN($1);RESULT^SET(-1);}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={RESULT^SET(TESTAPP^RUN("ECHOBEH", "RUN", 42));}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let direct_result = runner
+        .get_object("TESTAPP")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("RUN"),
+            &[
+                CnvValue::String("ECHOBEH".into()),
+                CnvValue::String("RUN".into()),
+                CnvValue::Integer(7),
+            ],
+            None,
+        )
+        .unwrap();
+    assert_eq!(direct_result, CnvValue::Integer(7));
+
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    let result = runner
+        .get_object("RESULT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(42));
+}
+
+#[test]
+fn run_should_fail_for_missing_object() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTAPP
+        TESTAPP:TYPE=APPLICATION
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let result = runner.get_object("TESTAPP").unwrap().call_method(
+        CallableIdentifier::Method("RUN"),
+        &[CnvValue::String("MISSING".into())],
+        None,
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn active_scripts_should_report_loaded_stack_after_reload() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "APPLICATION.DEF",
+            b"OBJECT=TESTAPP\nTESTAPP:TYPE=APPLICATION\nTESTAPP:PATH=APP\nTESTAPP:EPISODES=TESTEPISODE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTAPP.cnv",
+            b"OBJECT=TESTEPISODE\nTESTEPISODE:TYPE=EPISODE\nTESTEPISODE:PATH=EPISODE\nTESTEPISODE:SCENES=TESTSCENE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTEPISODE.cnv",
+            b"OBJECT=TESTSCENE\nTESTSCENE:TYPE=SCENE\nTESTSCENE:PATH=SCENES\n".to_vec(),
+        )
+        .with_file(
+            "TESTSCENE.cnv",
+            b"OBJECT=SCENEVAR\nSCENEVAR:TYPE=INTEGER\n".to_vec(),
+        );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner.reload_application().unwrap();
+
+    assert_eq!(
+        runner.active_scripts(),
+        vec![
+            (ScriptSource::Root, ScenePath::new(".", "./APPLICATION.DEF")),
+            (
+                ScriptSource::Application,
+                ScenePath::new("APP", "TESTAPP.cnv")
+            ),
+            (
+                ScriptSource::Episode,
+                ScenePath::new("EPISODE", "TESTEPISODE.cnv")
+            ),
+            (ScriptSource::Scene, ScenePath::new("SCENES", "TESTSCENE")),
+        ]
+    );
+}
+
+#[test_case(None, Some("FIRSTSCENE") ; "default starting scene")]
+#[test_case(Some("SECONDSCENE"), Some("SECONDSCENE") ; "overridden scene")]
+#[test_case(Some("MISSINGSCENE"), None ; "missing scene")]
+#[test_case(Some("TESTEPISODE"), None ; "not a scene")]
+fn reload_should_start_from_the_requested_scene(scene: Option<&str>, expected_scene: Option<&str>) {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "APPLICATION.DEF",
+            b"OBJECT=TESTAPP\nTESTAPP:TYPE=APPLICATION\nTESTAPP:PATH=APP\nTESTAPP:EPISODES=TESTEPISODE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTAPP.cnv",
+            b"OBJECT=TESTEPISODE\nTESTEPISODE:TYPE=EPISODE\nTESTEPISODE:PATH=EPISODE\nTESTEPISODE:SCENES=FIRSTSCENE,SECONDSCENE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTEPISODE.cnv",
+            b"OBJECT=FIRSTSCENE\nFIRSTSCENE:TYPE=SCENE\nFIRSTSCENE:PATH=SCENES\nOBJECT=SECONDSCENE\nSECONDSCENE:TYPE=SCENE\nSECONDSCENE:PATH=SCENES\n"
+                .to_vec(),
+        )
+        .with_file("FIRSTSCENE.cnv", b"OBJECT=FIRSTVAR\nFIRSTVAR:TYPE=INTEGER\n".to_vec())
+        .with_file("SECONDSCENE.cnv", b"OBJECT=SECONDVAR\nSECONDVAR:TYPE=INTEGER\n".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    let result = runner.reload_application_into(None, scene);
+
+    assert_eq!(result.is_ok(), expected_scene.is_some());
+    if let Some(expected_scene) = expected_scene {
+        assert_eq!(
+            runner.get_current_scene().map(|s| s.name.clone()),
+            Some(expected_scene.to_owned())
+        );
+    }
+}
+
+#[test]
+fn reload_into_missing_episode_should_fail() {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "APPLICATION.DEF",
+        b"OBJECT=TESTAPP\nTESTAPP:TYPE=APPLICATION\nTESTAPP:EPISODES=TESTEPISODE\n".to_vec(),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+
+    assert!(runner
+        .reload_application_into(Some("MISSINGEPISODE"), None)
+        .is_err());
+}
+
+#[test]
+fn reload_should_recreate_global_objects() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "APPLICATION.DEF",
+            b"OBJECT=TESTAPP\nTESTAPP:TYPE=APPLICATION\nTESTAPP:PATH=APP\nTESTAPP:EPISODES=TESTEPISODE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTAPP.cnv",
+            b"OBJECT=TESTEPISODE\nTESTEPISODE:TYPE=EPISODE\nTESTEPISODE:PATH=EPISODE\nTESTEPISODE:SCENES=TESTSCENE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTEPISODE.cnv",
+            b"OBJECT=TESTSCENE\nTESTSCENE:TYPE=SCENE\nTESTSCENE:PATH=SCENES\n".to_vec(),
+        )
+        .with_file(
+            "TESTSCENE.cnv",
+            b"OBJECT=SCENEVAR\nSCENEVAR:TYPE=INTEGER\n".to_vec(),
+        );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner.reload_application().unwrap();
+    let random_before = runner.get_object("RANDOM").unwrap();
+    runner
+        .get_object("MOUSE")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("HIDE"), &Vec::new(), None)
+        .unwrap();
+    assert!(!Mouse::is_visible().unwrap());
+
+    runner.reload_application().unwrap();
+
+    assert!(Mouse::is_visible().unwrap());
+    let random_after = runner.get_object("RANDOM").unwrap();
+    assert!(!Arc::ptr_eq(&random_before, &random_after));
+}
+
+#[test]
+fn episodes_and_scenes_should_be_listed_in_declaration_order() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "APPLICATION.DEF",
+            b"OBJECT=TESTAPP\nTESTAPP:TYPE=APPLICATION\nTESTAPP:PATH=APP\nTESTAPP:EPISODES=FIRSTEPISODE,SECONDEPISODE,MISSINGEPISODE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "TESTAPP.cnv",
+            b"OBJECT=FIRSTEPISODE\nFIRSTEPISODE:TYPE=EPISODE\nFIRSTEPISODE:PATH=EPISODE\nFIRSTEPISODE:SCENES=INTRO,MENU\nOBJECT=SECONDEPISODE\nSECONDEPISODE:TYPE=EPISODE\nSECONDEPISODE:SCENES=FINALE\n"
+                .to_vec(),
+        )
+        .with_file(
+            "FIRSTEPISODE.cnv",
+            b"OBJECT=INTRO\nINTRO:TYPE=SCENE\nINTRO:PATH=SCENES\nOBJECT=MENU\nMENU:TYPE=SCENE\nMENU:PATH=SCENES\n"
+                .to_vec(),
+        )
+        .with_file("INTRO.cnv", b"OBJECT=SCENEVAR\nSCENEVAR:TYPE=INTEGER\n".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner.reload_application().unwrap();
+
+    assert_eq!(
+        runner.list_episodes_and_scenes(),
+        vec![
+            EpisodeInfo {
+                name: "FIRSTEPISODE".into(),
+                scenes: vec!["INTRO".into(), "MENU".into()],
+            },
+            EpisodeInfo {
+                name: "SECONDEPISODE".into(),
+                scenes: vec!["FINALE".into()],
+            },
+            EpisodeInfo {
+                name: "MISSINGEPISODE".into(),
+                scenes: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[test_case("RUN" ; "run")]
+#[test_case("RUNENV" ; "run in environment")]
+fn run_without_arguments_should_fail(method: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTAPP
+        TESTAPP:TYPE=APPLICATION
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let error = runner
+        .get_object("TESTAPP")
+        .unwrap()
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::TooFewArguments { .. })
+    ));
+}
@@ -0,0 +1,366 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case("SAVE(\"TEST.ARR\")", "LOAD(\"TEST.ARR\")")]
+#[test_case(
+    "SAVEINI(\"TEST.INI\", \"SCORES\")",
+    "LOADINI(\"TEST.INI\", \"SCORES\")"
+)]
+fn saved_array_should_be_loaded_back(save_call: &str, load_call: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(MemoryFileSystem::default())),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTARR^ADD(7, 2.5, TRUE, "TEXT");TESTARR^{};TESTARR^REMOVEALL();TESTARR^{};}}
+        "#,
+        save_call, load_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_arr_object = runner.get_object("TESTARR").unwrap();
+    let values = (0..4)
+        .map(|i| {
+            test_arr_object
+                .call_method(
+                    CallableIdentifier::Method("GET"),
+                    &[CnvValue::Integer(i)],
+                    None,
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        vec![
+            CnvValue::Integer(7),
+            CnvValue::Double(2.5),
+            CnvValue::Bool(true),
+            CnvValue::String("TEXT".into()),
+        ]
+    );
+}
+
+#[test]
+fn saving_ini_section_should_keep_other_sections() {
+    let filesystem = Arc::new(RwLock::new(MemoryFileSystem::default().with_file(
+        "DANE/TEST.INI",
+        b"[OTHER]\r\nKEY=VALUE\r\n[SCORES]\r\n0=1\r\n".to_vec(),
+    )));
+    let runner =
+        CnvRunner::try_new(filesystem.clone(), Default::default(), Default::default()).unwrap();
+    let script = r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTARR^ADD(5, 6);TESTARR^SAVEINI("TEST.INI", "SCORES");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let data = filesystem
+        .write()
+        .unwrap()
+        .read_file("DANE/TEST.INI")
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&data),
+        "[OTHER]\r\nKEY=VALUE\r\n[SCORES]\r\n0=\"5\"\r\n1=\"6\"\r\n"
+    );
+}
+
+#[test]
+fn ini_should_keep_numeric_looking_strings_as_strings() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(MemoryFileSystem::default())),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_arr_object = runner.get_object("TESTARR").unwrap();
+    let ini_arguments = [
+        CnvValue::String("TEST.INI".into()),
+        CnvValue::String("SCORES".into()),
+    ];
+    for (method, arguments) in [
+        (
+            "ADD",
+            &[
+                CnvValue::String("5".into()),
+                CnvValue::Integer(5),
+                CnvValue::String("TEXT".into()),
+            ][..],
+        ),
+        ("SAVEINI", &ini_arguments[..]),
+        ("REMOVEALL", &[][..]),
+        ("LOADINI", &ini_arguments[..]),
+    ] {
+        test_arr_object
+            .call_method(CallableIdentifier::Method(method), arguments, None)
+            .unwrap();
+    }
+
+    let values = (0..3)
+        .map(|i| {
+            test_arr_object
+                .call_method(
+                    CallableIdentifier::Method("GET"),
+                    &[CnvValue::Integer(i)],
+                    None,
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(&values[0], CnvValue::String(s) if s == "5"));
+    assert!(matches!(&values[1], CnvValue::Integer(5)));
+    assert!(matches!(&values[2], CnvValue::String(s) if s == "TEXT"));
+}
+
+#[test]
+fn loading_missing_ini_section_should_keep_contents() {
+    let filesystem = Arc::new(RwLock::new(
+        MemoryFileSystem::default().with_file("DANE/TEST.INI", b"[OTHER]\r\n0=1\r\n".to_vec()),
+    ));
+    let runner =
+        CnvRunner::try_new(filesystem.clone(), Default::default(), Default::default()).unwrap();
+    let script = r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTARR^ADD(3);TESTARR^LOADINI("TEST.INI", "SCORES");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_arr_object = runner.get_object("TESTARR").unwrap();
+    assert_eq!(
+        test_arr_object
+            .call_method(
+                CallableIdentifier::Method("GET"),
+                &[CnvValue::Integer(0)],
+                None,
+            )
+            .unwrap(),
+        CnvValue::Integer(3)
+    );
+}
+
+#[test]
+fn copied_array_should_be_independent_snapshot() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(MemoryFileSystem::default())),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SOURCEARR
+        SOURCEARR:TYPE=ARRAY
+
+        OBJECT=TARGETARR
+        TARGETARR:TYPE=ARRAY
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TARGETARR^ADD(9);SOURCEARR^ADD(1, 2);SOURCEARR^COPYTO("TARGETARR");SOURCEARR^REMOVEALL();SOURCEARR^ADD(5);}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let get_values = |name: &str| {
+        let object = runner.get_object(name).unwrap();
+        (0..3)
+            .map(|i| {
+                object
+                    .call_method(
+                        CallableIdentifier::Method("GET"),
+                        &[CnvValue::Integer(i)],
+                        None,
+                    )
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        get_values("TARGETARR"),
+        vec![CnvValue::Integer(1), CnvValue::Integer(2), CnvValue::Null]
+    );
+    assert_eq!(
+        get_values("SOURCEARR"),
+        vec![CnvValue::Integer(5), CnvValue::Null, CnvValue::Null]
+    );
+}
+
+#[test_case("MULAT(1, 3)", &[2, 15, 7] ; "multiply at")]
+#[test_case("MULAT(9, 2)", &[2, 5, 14] ; "multiply past the end")]
+#[test_case("CLAMPAT(2, 0, 4)", &[2, 5, 4] ; "clamp at")]
+#[test_case("CLAMPAT(-1, 3, 4)", &[3, 5, 7] ; "clamp before the start")]
+#[test_case("ADDAT(0, 1)", &[3, 5, 7] ; "add at")]
+#[test_case("SUBAT(1, 6)", &[2, -1, 7] ; "subtract at")]
+#[test_case("MODAT(2, 4)", &[2, 5, 3] ; "modulo at")]
+fn positional_arithmetic_should_change_single_element(call: &str, expected: &[i32]) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTARR^ADD(2, 5, 7);TESTARR^{};}}
+        "#,
+        call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let test_arr_object = runner.get_object("TESTARR").unwrap();
+    let values = (0..3)
+        .map(|i| {
+            test_arr_object
+                .call_method(
+                    CallableIdentifier::Method("GET"),
+                    &[CnvValue::Integer(i)],
+                    None,
+                )
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        expected
+            .iter()
+            .map(|&v| CnvValue::Integer(v))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test_case(CnvValue::Integer(0) ; "zero divisor")]
+#[test_case(CnvValue::Double(0.5) ; "fractional divisor truncated to zero")]
+fn modulo_at_by_zero_should_fail(divisor: CnvValue) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTARR
+        TESTARR:TYPE=ARRAY
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_arr_object = runner.get_object("TESTARR").unwrap();
+    test_arr_object
+        .call_method(
+            CallableIdentifier::Method("ADD"),
+            &[CnvValue::Integer(7)],
+            None,
+        )
+        .unwrap();
+
+    let error = test_arr_object
+        .call_method(
+            CallableIdentifier::Method("MODAT"),
+            &[CnvValue::Integer(0), divisor],
+            None,
+        )
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::DivisionByZero { object_name }) if object_name == "TESTARR"
+    ));
+}
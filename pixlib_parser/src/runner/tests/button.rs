@@ -0,0 +1,456 @@
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_button() -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTBUTTON
+        TESTBUTTON:TYPE=BUTTON
+        TESTBUTTON:GFXSTANDARD=TESTIMG
+        TESTBUTTON:ONACTION={ACTIONCOUNT^INC();}
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        OBJECT=ACTIONCOUNT
+        ACTIONCOUNT:TYPE=INTEGER
+        ACTIONCOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+fn click_at(runner: &Arc<CnvRunner>, position: (isize, isize)) {
+    let buttons = runner.get_enabled_buttons().unwrap();
+    if let Some(button_idx) = runner.find_relevant_button(&buttons, position).unwrap() {
+        let CnvContent::Button(button) = &buttons[button_idx].object.content else {
+            unreachable!();
+        };
+        button.handle_lmb_released().unwrap();
+    }
+    runner.step().unwrap();
+}
+
+fn get_action_count(runner: &Arc<CnvRunner>) -> CnvValue {
+    runner
+        .get_object("ACTIONCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap()
+}
+
+#[test_case(&[CnvValue::String("10,20,110,70".into())], (50, 40), 1 ; "literal string inside")]
+#[test_case(&[CnvValue::String("10,20,110,70".into())], (150, 40), 0 ; "literal string outside")]
+#[test_case(&[CnvValue::Integer(10), CnvValue::Integer(20), CnvValue::Integer(110), CnvValue::Integer(70)], (50, 40), 1 ; "four integers inside")]
+#[test_case(&[CnvValue::Integer(10), CnvValue::Integer(20), CnvValue::Integer(110), CnvValue::Integer(70)], (50, 90), 0 ; "four integers outside")]
+fn set_rect_should_define_clickable_region(
+    arguments: &[CnvValue],
+    click_position: (isize, isize),
+    expected_actions: i32,
+) {
+    let runner = create_runner_with_button();
+    runner
+        .get_object("TESTBUTTON")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("SETRECT"), arguments, None)
+        .unwrap();
+    click_at(&runner, click_position);
+    assert_eq!(
+        get_action_count(&runner),
+        CnvValue::Integer(expected_actions)
+    );
+}
+
+#[test]
+fn set_rect_should_reject_malformed_literal() {
+    let runner = create_runner_with_button();
+    let result = runner.get_object("TESTBUTTON").unwrap().call_method(
+        CallableIdentifier::Method("SETRECT"),
+        &[CnvValue::String("10,A,110,70".into())],
+        None,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn set_rect_should_coerce_non_integer_arguments() {
+    let runner = create_runner_with_button();
+    runner
+        .get_object("TESTBUTTON")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("SETRECT"),
+            &[
+                CnvValue::Bool(false),
+                CnvValue::String("0".into()),
+                CnvValue::String("\"110\"".into()),
+                CnvValue::Double(70.0),
+            ],
+            None,
+        )
+        .unwrap();
+    click_at(&runner, (50, 40));
+    assert_eq!(get_action_count(&runner), CnvValue::Integer(1));
+}
+
+#[test]
+fn set_std_should_be_returned_by_get_std() {
+    let runner = create_runner_with_button();
+    let test_button_object = runner.get_object("TESTBUTTON").unwrap();
+    test_button_object
+        .call_method(
+            CallableIdentifier::Method("SETSTD"),
+            &[CnvValue::String("TESTIMG".into())],
+            None,
+        )
+        .unwrap();
+    let result = test_button_object
+        .call_method(CallableIdentifier::Method("GETSTD"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::String("TESTIMG".into()));
+}
+
+#[test_case("ENABLE", 1, true)]
+#[test_case("DISABLE", 0, false)]
+#[test_case("DISABLEBUTVISIBLE", 0, true)]
+fn enable_state_should_control_clicks_and_visibility(
+    method: &str,
+    expected_actions: i32,
+    expected_visible: bool,
+) {
+    let runner = create_runner_with_button();
+    let test_button_object = runner.get_object("TESTBUTTON").unwrap();
+    test_button_object
+        .call_method(
+            CallableIdentifier::Method("SETRECT"),
+            &[CnvValue::String("10,20,110,70".into())],
+            None,
+        )
+        .unwrap();
+    test_button_object
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap();
+    click_at(&runner, (50, 40));
+    assert_eq!(
+        get_action_count(&runner),
+        CnvValue::Integer(expected_actions)
+    );
+    let is_visible = runner
+        .get_object("TESTIMG")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("ISVISIBLE"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(is_visible, CnvValue::Bool(expected_visible));
+}
+
+#[test_case("DISABLE")]
+#[test_case("DISABLEBUTVISIBLE")]
+fn enable_should_restore_interactivity(method: &str) {
+    let runner = create_runner_with_button();
+    let test_button_object = runner.get_object("TESTBUTTON").unwrap();
+    test_button_object
+        .call_method(
+            CallableIdentifier::Method("SETRECT"),
+            &[CnvValue::String("10,20,110,70".into())],
+            None,
+        )
+        .unwrap();
+    test_button_object
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap();
+    test_button_object
+        .call_method(CallableIdentifier::Method("ENABLE"), &Vec::new(), None)
+        .unwrap();
+    click_at(&runner, (50, 40));
+    assert_eq!(get_action_count(&runner), CnvValue::Integer(1));
+    let is_visible = runner
+        .get_object("TESTIMG")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("ISVISIBLE"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(is_visible, CnvValue::Bool(true));
+}
+
+#[test_case(None, "FIRSTCOUNT" ; "declaration order")]
+#[test_case(Some(5), "SECONDCOUNT" ; "raised priority")]
+fn overlapping_buttons_should_resolve_click_by_priority(
+    second_priority: Option<i32>,
+    expected_counter: &str,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=FIRSTBUTTON
+        FIRSTBUTTON:TYPE=BUTTON
+        FIRSTBUTTON:RECT=0,0,100,100
+        FIRSTBUTTON:ONACTION={FIRSTCOUNT^INC();}
+        OBJECT=SECONDBUTTON
+        SECONDBUTTON:TYPE=BUTTON
+        SECONDBUTTON:RECT=50,50,150,150
+        SECONDBUTTON:ONACTION={SECONDCOUNT^INC();}
+        OBJECT=FIRSTCOUNT
+        FIRSTCOUNT:TYPE=INTEGER
+        FIRSTCOUNT:VALUE=0
+        OBJECT=SECONDCOUNT
+        SECONDCOUNT:TYPE=INTEGER
+        SECONDCOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let second_button_object = runner.get_object("SECONDBUTTON").unwrap();
+    if let Some(priority) = second_priority {
+        second_button_object
+            .call_method(
+                CallableIdentifier::Method("SETPRIORITY"),
+                &[CnvValue::Integer(priority)],
+                None,
+            )
+            .unwrap();
+    }
+    let queried_priority = second_button_object
+        .call_method(CallableIdentifier::Method("GETPRIORITY"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(
+        queried_priority,
+        CnvValue::Integer(second_priority.unwrap_or_default())
+    );
+    click_at(&runner, (75, 75));
+    for counter in ["FIRSTCOUNT", "SECONDCOUNT"] {
+        let value = runner
+            .get_object(counter)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap();
+        let expected = if counter == expected_counter { 1 } else { 0 };
+        assert_eq!(value, CnvValue::Integer(expected), "{}", counter);
+    }
+}
+
+#[test_case(None, 1 ; "unbounded")]
+#[test_case(Some(1), 0 ; "min above button priority")]
+fn scene_hotspot_priority_range_should_filter_clickable_buttons(
+    min_priority: Option<i32>,
+    expected_low_count: i32,
+) {
+    let scene_script = r#"
+        OBJECT=LOWBUTTON
+        LOWBUTTON:TYPE=BUTTON
+        LOWBUTTON:RECT=0,0,100,100
+        LOWBUTTON:ONACTION={LOWCOUNT^INC();}
+        OBJECT=HIGHBUTTON
+        HIGHBUTTON:TYPE=BUTTON
+        HIGHBUTTON:RECT=200,0,300,100
+        HIGHBUTTON:ONACTION={HIGHCOUNT^INC();}
+        OBJECT=LOWCOUNT
+        LOWCOUNT:TYPE=INTEGER
+        LOWCOUNT:VALUE=0
+        OBJECT=HIGHCOUNT
+        HIGHCOUNT:TYPE=INTEGER
+        HIGHCOUNT:VALUE=0
+        "#;
+    let filesystem =
+        MemoryFileSystem::default().with_file("SCENE1.CNV", scene_script.as_bytes().to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.step().unwrap();
+    runner
+        .get_object("HIGHBUTTON")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("SETPRIORITY"),
+            &[CnvValue::Integer(5)],
+            None,
+        )
+        .unwrap();
+    let scene_object = runner.get_object("SCENE1").unwrap();
+    if let Some(priority) = min_priority {
+        scene_object
+            .call_method(
+                CallableIdentifier::Method("SETMINHSPRIORITY"),
+                &[CnvValue::Integer(priority)],
+                None,
+            )
+            .unwrap();
+        let queried_priority = scene_object
+            .call_method(
+                CallableIdentifier::Method("GETMINHSPRIORITY"),
+                &Vec::new(),
+                None,
+            )
+            .unwrap();
+        assert_eq!(queried_priority, CnvValue::Integer(priority));
+    }
+
+    click_at(&runner, (50, 50));
+    click_at(&runner, (250, 50));
+    let get_count = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(get_count("LOWCOUNT"), CnvValue::Integer(expected_low_count));
+    assert_eq!(get_count("HIGHCOUNT"), CnvValue::Integer(1));
+}
+
+#[test_case(&[CnvValue::String("SPECIAL".into())], 1, 0 ; "matching argument")]
+#[test_case(&[CnvValue::String("OTHER".into())], 0, 1 ; "other argument")]
+#[test_case(&[], 0, 1 ; "no argument")]
+fn argument_suffixed_handlers_should_be_registered(
+    arguments: &[CnvValue],
+    expected_special: i32,
+    expected_default: i32,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTBUTTON
+        TESTBUTTON:TYPE=BUTTON
+        TESTBUTTON:ONCLICKED^SPECIAL={SPECIALCOUNT^INC();}
+        TESTBUTTON:ONCLICKED={DEFAULTCOUNT^INC();}
+        OBJECT=SPECIALCOUNT
+        SPECIALCOUNT:TYPE=INTEGER
+        SPECIALCOUNT:VALUE=0
+        OBJECT=DEFAULTCOUNT
+        DEFAULTCOUNT:TYPE=INTEGER
+        DEFAULTCOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    runner
+        .get_object("TESTBUTTON")
+        .unwrap()
+        .call_method(CallableIdentifier::Event("ONCLICKED"), arguments, None)
+        .unwrap();
+
+    let get_count = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(
+        get_count("SPECIALCOUNT"),
+        CnvValue::Integer(expected_special)
+    );
+    assert_eq!(
+        get_count("DEFAULTCOUNT"),
+        CnvValue::Integer(expected_default)
+    );
+}
+
+#[test]
+fn buttons_should_be_listed_and_clickable_by_name() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=FIRSTBUTTON
+        FIRSTBUTTON:TYPE=BUTTON
+        FIRSTBUTTON:RECT=0,0,100,100
+        FIRSTBUTTON:ONCLICKED={FIRSTCOUNT^INC();}
+        OBJECT=SECONDBUTTON
+        SECONDBUTTON:TYPE=BUTTON
+        SECONDBUTTON:RECT=200,200,300,260
+        SECONDBUTTON:ONCLICKED={SECONDCOUNT^INC();}
+        SECONDBUTTON:ONACTION={SECONDCOUNT^INC();}
+        OBJECT=DISABLEDBUTTON
+        DISABLEDBUTTON:TYPE=BUTTON
+        DISABLEDBUTTON:RECT=0,0,10,10
+        DISABLEDBUTTON:ENABLE=FALSE
+        OBJECT=FIRSTCOUNT
+        FIRSTCOUNT:TYPE=INTEGER
+        FIRSTCOUNT:VALUE=0
+        OBJECT=SECONDCOUNT
+        SECONDCOUNT:TYPE=INTEGER
+        SECONDCOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let buttons = runner.list_buttons();
+    assert_eq!(
+        buttons,
+        vec![
+            ("FIRSTBUTTON".to_owned(), Rect::from((0, 0), (100, 100)), 0),
+            (
+                "SECONDBUTTON".to_owned(),
+                Rect::from((200, 200), (100, 60)),
+                0
+            ),
+        ]
+    );
+
+    runner.click_object("SECONDBUTTON").unwrap();
+    runner.step().unwrap();
+    for (counter, expected) in [("FIRSTCOUNT", 0), ("SECONDCOUNT", 2)] {
+        let value = runner
+            .get_object(counter)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap();
+        assert_eq!(value, CnvValue::Integer(expected), "{}", counter);
+    }
+    assert!(runner.click_object("DISABLEDBUTTON").is_err());
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case("_description: condition", "TESTCOND", 5, 1)]
+#[test_case("_description: condition", "TESTCOND", 3, 0)]
+#[test_case("_description: complex condition", "TESTCOMPLEX", 5, 1)]
+#[test_case("_description: complex condition", "TESTCOMPLEX", 3, 0)]
+fn success_handler_should_run_only_when_condition_holds(
+    _description: &str,
+    condition_name: &str,
+    value: i32,
+    expected_count: i32,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=SUCCESSCOUNT
+        SUCCESSCOUNT:TYPE=INTEGER
+        SUCCESSCOUNT:VALUE=0
+
+        OBJECT=FAILURECOUNT
+        FAILURECOUNT:TYPE=INTEGER
+        FAILURECOUNT:VALUE=0
+
+        OBJECT=TESTVALUE
+        TESTVALUE:TYPE=INTEGER
+        TESTVALUE:VALUE={0}
+
+        OBJECT=TESTCOND
+        TESTCOND:TYPE=CONDITION
+        TESTCOND:OPERAND1=TESTVALUE
+        TESTCOND:OPERAND2=5
+        TESTCOND:OPERATOR=EQUAL
+
+        OBJECT=TRUECOND
+        TRUECOND:TYPE=CONDITION
+        TRUECOND:OPERAND1=1
+        TRUECOND:OPERAND2=1
+        TRUECOND:OPERATOR=EQUAL
+
+        OBJECT=TESTCOMPLEX
+        TESTCOMPLEX:TYPE=COMPLEXCONDITION
+        TESTCOMPLEX:CONDITION1=TRUECOND
+        TESTCOMPLEX:CONDITION2=TESTCOND
+        TESTCOMPLEX:OPERATOR=AND
+
+        {1}:ONRUNTIMESUCCESS={{SUCCESSCOUNT^INC();}}
+        {1}:ONRUNTIMEFAILED={{FAILURECOUNT^INC();}}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{{1}^CHECK();}}
+        "#,
+        value, condition_name
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    runner.step().unwrap();
+
+    let get_count = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(get_count("SUCCESSCOUNT"), CnvValue::Integer(expected_count));
+    assert_eq!(
+        get_count("FAILURECOUNT"),
+        CnvValue::Integer(1 - expected_count)
+    );
+}
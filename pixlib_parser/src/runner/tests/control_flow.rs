@@ -0,0 +1,275 @@
+use super::*;
+
+use test_case::test_case;
+
+fn run_code(code: &str) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+        COUNTER:VALUE=0
+
+        OBJECT=RESULT
+        RESULT:TYPE=STRING
+
+        OBJECT=BEH_THEN
+        BEH_THEN:TYPE=BEHAVIOUR
+        BEH_THEN:CODE={{RESULT^SET("THEN");}}
+
+        OBJECT=BEH_ELSE
+        BEH_ELSE:TYPE=BEHAVIOUR
+        BEH_ELSE:CODE={{RESULT^SET("ELSE");}}
+
+        OBJECT=COND_POSITIVE
+        COND_POSITIVE:TYPE=CONDITION
+        COND_POSITIVE:OPERAND1=COUNTER
+        COND_POSITIVE:OPERAND2=0
+        COND_POSITIVE:OPERATOR=GREATER
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={}
+        "#,
+        code
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    runner
+}
+
+fn get_value(runner: &Arc<CnvRunner>, name: &str) -> CnvValue {
+    runner
+        .get_object(name)
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap()
+}
+
+#[test_case(
+    "{COUNTER^SET(5);@IF(COUNTER,'>',3,\"BEH_THEN\",\"BEH_ELSE\");}",
+    "THEN"
+)]
+#[test_case(
+    "{COUNTER^SET(2);@IF(COUNTER,'>',3,\"BEH_THEN\",\"BEH_ELSE\");}",
+    "ELSE"
+)]
+#[test_case("{COUNTER^SET(3);@IF(COUNTER,'_',3,BEH_THEN,BEH_ELSE);}", "THEN")]
+#[test_case(
+    "{COUNTER^SET(1);@IF(COND_POSITIVE,\"BEH_THEN\",\"BEH_ELSE\");}",
+    "THEN"
+)]
+#[test_case("{@IF(COND_POSITIVE,\"BEH_THEN\",\"BEH_ELSE\");}", "ELSE")]
+fn if_should_select_branch(code: &str, expected: &str) {
+    let runner = run_code(code);
+
+    assert_eq!(
+        get_value(&runner, "RESULT"),
+        CnvValue::String(expected.into())
+    );
+}
+
+#[test_case("{@WHILE(COUNTER,'<',5,{COUNTER^INC();});}", 5)]
+#[test_case(
+    "{@WHILE(COUNTER,'<',5,{COUNTER^INC();@IF(COUNTER,'_',3,{@BREAK();},\"\");});}",
+    3
+)]
+#[test_case("{@WHILE(COUNTER,'>',5,{COUNTER^INC();});}", 0)]
+fn while_should_loop_counter(code: &str, expected: i32) {
+    let runner = run_code(code);
+
+    assert_eq!(get_value(&runner, "COUNTER"), CnvValue::Integer(expected));
+}
+
+#[test_case("$1 + 2", 7 ; "addition")]
+#[test_case("$1+2", 7 ; "addition without spaces")]
+#[test_case("$1 - 2", 3 ; "subtraction")]
+#[test_case("$1 * $2", 15 ; "multiplication")]
+#[test_case("$1@$2", 1 ; "division")]
+#[test_case("$1 % $2", 2 ; "remainder")]
+#[test_case("$1 + 2 - $2", 4 ; "chained operations")]
+#[test_case("COUNTER^GET() + $1", 5 ; "invocation operand")]
+#[test_case("[$1 + 1] * $2", 18 ; "bracketed operand")]
+#[test_case("$1 + COUNTER^GET()", 5 ; "invocation after operator")]
+fn inline_arithmetic_should_be_evaluated_in_arguments(expression: &str, expected: i32) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+        COUNTER:VALUE=0
+
+        OBJECT=CALCBEH
+        CALCBEH:TYPE=BEHAVIOUR
+        CALCBEH:CODE={{COUNTER^SET({});}}
+        "#,
+        expression
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("CALCBEH")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("RUN"),
+            &[CnvValue::Integer(5), CnvValue::Integer(3)],
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(get_value(&runner, "COUNTER"), CnvValue::Integer(expected));
+}
+
+#[test_case("$1 > $2", true ; "greater")]
+#[test_case("$1 < $2", false ; "less")]
+#[test_case("$1 >= 5", true ; "greater or equal")]
+#[test_case("$1 <= $2", false ; "less or equal")]
+#[test_case("$1 == 5", true ; "equal")]
+#[test_case("$1 != 5", false ; "not equal")]
+#[test_case("$1 <> $2", true ; "not equal with angle brackets")]
+fn inline_comparison_should_be_evaluated_in_arguments(expression: &str, expected: bool) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=FLAG
+        FLAG:TYPE=BOOL
+        FLAG:VALUE={}
+
+        OBJECT=CALCBEH
+        CALCBEH:TYPE=BEHAVIOUR
+        CALCBEH:CODE={{FLAG^SET({});}}
+        "#,
+        if expected { "FALSE" } else { "TRUE" },
+        expression
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("CALCBEH")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("RUN"),
+            &[CnvValue::Integer(5), CnvValue::Integer(3)],
+            None,
+        )
+        .unwrap();
+
+    assert!(matches!(
+        get_value(&runner, "FLAG"),
+        CnvValue::Bool(value) if value == expected
+    ));
+}
+
+#[test]
+fn return_should_end_event_handler_without_error() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=RESULT
+        RESULT:TYPE=STRING
+
+        OBJECT=WATCHED
+        WATCHED:TYPE=INTEGER
+        WATCHED:ONCHANGED={RESULT^SET("BEFORE");@RETURN(1);RESULT^SET("AFTER");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let result = runner.get_object("WATCHED").unwrap().call_method(
+        CallableIdentifier::Event("ONCHANGED"),
+        &Vec::new(),
+        None,
+    );
+
+    assert_eq!(result.unwrap(), CnvValue::Null);
+    assert_eq!(
+        get_value(&runner, "RESULT"),
+        CnvValue::String("BEFORE".into())
+    );
+}
+
+#[test]
+fn endless_while_should_fail_instead_of_hanging() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+        COUNTER:VALUE=0
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={@WHILE(COUNTER,'_',0,{});}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let error = runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::LoopLimitExceeded { .. })
+    ));
+}
@@ -0,0 +1,175 @@
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_double(value: f64) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTDBL
+        TESTDBL:TYPE=DOUBLE
+        TESTDBL:VALUE={}
+        "#,
+        value
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test]
+fn length_should_return_vector_magnitude() {
+    let runner = create_runner_with_double(0.0);
+    let test_dbl_object = runner.get_object("TESTDBL").unwrap();
+    let result = test_dbl_object
+        .call_method(
+            CallableIdentifier::Method("LENGTH"),
+            &[CnvValue::Integer(3), CnvValue::Integer(4)],
+            None,
+        )
+        .unwrap();
+    assert_eq!(result, CnvValue::Double(5.0));
+    let value = test_dbl_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Double(5.0));
+}
+
+#[test_case(-5.0, 1.0, 10.0, 1.0)]
+#[test_case(5.5, 1.0, 10.0, 5.5)]
+#[test_case(15.0, 1.0, 10.0, 10.0)]
+#[test_case(15.0, 10.0, 1.0, 10.0)]
+fn clamp_should_bound_value(value: f64, min: f64, max: f64, expected: f64) {
+    let runner = create_runner_with_double(value);
+    let result = runner
+        .get_object("TESTDBL")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Method("CLAMP"),
+            &[CnvValue::Double(min), CnvValue::Double(max)],
+            None,
+        )
+        .unwrap();
+    assert_eq!(result, CnvValue::Double(expected));
+}
+
+fn create_runner_with_change_counter(value: f64) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=CHANGEDCOUNT
+        CHANGEDCOUNT:TYPE=INTEGER
+        CHANGEDCOUNT:VALUE=0
+
+        OBJECT=TESTDBL
+        TESTDBL:TYPE=DOUBLE
+        TESTDBL:VALUE={}
+        TESTDBL:ONCHANGED={{CHANGEDCOUNT^INC();}}
+        "#,
+        value
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test_case("DIV", 4.0, CnvValue::Null, 2.5)]
+#[test_case("MUL", 4.0, CnvValue::Null, 40.0)]
+#[test_case("SUB", 4.0, CnvValue::Double(6.0), 6.0)]
+#[test_case("ADD", 4.0, CnvValue::Double(14.0), 14.0)]
+#[test_case("MOD", 4.0, CnvValue::Null, 2.0)]
+fn arithmetic_should_update_value_in_place(
+    method: &str,
+    operand: f64,
+    expected_result: CnvValue,
+    expected_value: f64,
+) {
+    let runner = create_runner_with_change_counter(10.0);
+    let test_dbl_object = runner.get_object("TESTDBL").unwrap();
+    let result = test_dbl_object
+        .call_method(
+            CallableIdentifier::Method(method),
+            &[CnvValue::Double(operand)],
+            None,
+        )
+        .unwrap();
+    runner.step().unwrap();
+
+    assert_eq!(result, expected_result);
+    let value = test_dbl_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Double(expected_value));
+    let changed_count = runner
+        .get_object("CHANGEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(changed_count, CnvValue::Integer(1));
+}
+
+#[test_case("DIV", CnvValue::Double(0.0))]
+#[test_case("MOD", CnvValue::Integer(0))]
+fn division_by_zero_should_fail_without_changing_value(method: &str, divisor: CnvValue) {
+    let runner = create_runner_with_change_counter(10.0);
+    let test_dbl_object = runner.get_object("TESTDBL").unwrap();
+    let error = test_dbl_object
+        .call_method(CallableIdentifier::Method(method), &[divisor], None)
+        .unwrap_err();
+    runner.step().unwrap();
+
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::DivisionByZero { object_name }) if object_name == "TESTDBL"
+    ));
+    let value = test_dbl_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Double(10.0));
+    let changed_count = runner
+        .get_object("CHANGEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(changed_count, CnvValue::Integer(0));
+}
+
+#[test]
+fn modulus_of_out_of_range_value_should_not_overflow() {
+    let runner = create_runner_with_change_counter(-1e12);
+    let test_dbl_object = runner.get_object("TESTDBL").unwrap();
+    test_dbl_object
+        .call_method(
+            CallableIdentifier::Method("MOD"),
+            &[CnvValue::Integer(-1)],
+            None,
+        )
+        .unwrap();
+
+    let value = test_dbl_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Double(0.0));
+}
@@ -0,0 +1,207 @@
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_font() -> Arc<CnvRunner> {
+    let frames = [0, 1, 2].map(|sprite_idx| TestFrame {
+        name: "GLYPH",
+        sprite_idx,
+        offset_px: (0, 0),
+        sfx: None,
+    });
+    let sequences = ["A", "B", " "]
+        .into_iter()
+        .zip(frames.chunks(1))
+        .map(|(name, frames)| TestSequence {
+            name,
+            looping_after: 0,
+            frames,
+        })
+        .collect::<Vec<_>>();
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.FNT",
+        build_ann_with_sprites(&sequences, &[(5, 10), (7, 10), (3, 8)]),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTFONT
+        TESTFONT:TYPE=FONT
+        TESTFONT:DEF_ARIAL_STANDARD_14=TEST.FNT
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test_case("AB", (12, 10))]
+#[test_case("AB A", (20, 10))]
+#[test_case("AZB", (15, 10))]
+#[test_case("AB\nBBB", (21, 20))]
+fn text_should_be_measured_as_sum_of_glyph_advances(text: &str, expected: (usize, usize)) {
+    let runner = create_runner_with_font();
+    let test_font_object = runner.get_object("TESTFONT").unwrap();
+    let CnvContent::Font(ref font) = &test_font_object.content else {
+        unreachable!();
+    };
+
+    assert_eq!(font.measure(text).unwrap(), expected);
+}
+
+#[test]
+fn lines_should_be_measured_separately() {
+    let runner = create_runner_with_font();
+    let test_font_object = runner.get_object("TESTFONT").unwrap();
+    let CnvContent::Font(ref font) = &test_font_object.content else {
+        unreachable!();
+    };
+
+    assert_eq!(
+        font.measure_lines("A\nBB A").unwrap(),
+        vec![(5, 10), (22, 10)]
+    );
+}
+
+#[test_case("#FF8000", Some((255, 128, 0)))]
+#[test_case("0x00ff7f", Some((0, 255, 127)))]
+#[test_case("10, 20, 30", Some((10, 20, 30)))]
+#[test_case("16711680", Some((255, 0, 0)))]
+#[test_case("#FFF", None)]
+#[test_case("256,0,0", None)]
+#[test_case("RED", None)]
+fn colors_should_be_parsed_from_literals(literal: &str, expected: Option<(u8, u8, u8)>) {
+    assert_eq!(parsers::parse_color(literal.to_owned()).ok(), expected);
+}
+
+#[test_case(&[], None, [0xff, 0xff, 0xff, 0xff] ; "glyph color")]
+#[test_case(&[CnvValue::String("#FF0000".into())], Some((255, 0, 0)), [0xff, 0, 0, 0xff] ; "hex color")]
+#[test_case(&[CnvValue::Integer(0), CnvValue::Integer(128), CnvValue::Integer(255)], Some((0, 128, 255)), [0, 128, 0xff, 0xff] ; "component color")]
+fn text_color_should_be_used_for_rendered_glyphs(
+    arguments: &[CnvValue],
+    expected_color: Option<(u8, u8, u8)>,
+    expected_pixel: [u8; 4],
+) {
+    let runner = create_runner_with_font();
+    let script = r#"
+        OBJECT=TESTTEXT
+        TESTTEXT:TYPE=TEXT
+        TESTTEXT:FONT=TESTFONT
+        TESTTEXT:TEXT=AB
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "TEXT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_text_object = runner.get_object("TESTTEXT").unwrap();
+    if !arguments.is_empty() {
+        test_text_object
+            .call_method(CallableIdentifier::Method("SETCOLOR"), arguments, None)
+            .unwrap();
+    }
+    let CnvContent::Text(ref text) = &test_text_object.content else {
+        unreachable!();
+    };
+    assert_eq!(text.get_color(), expected_color);
+
+    let ((width, height), data) = text.render().unwrap().unwrap();
+    assert_eq!((width, height), (12, 10));
+    for x in [0, 5, 11] {
+        assert_eq!(data[x * 4..x * 4 + 4], expected_pixel, "pixel {}", x);
+    }
+}
+
+#[test_case("AB AB BA\nA", "0,0,20,30", "FALSE", &[("AB", 0), ("AB", 10), ("BA", 20)] ; "wrapped and clipped")]
+#[test_case("AB A", "0,0,20,50", "TRUE", &[("AB A", 20)] ; "centered vertically")]
+#[test_case("BBB A", "0,0,10,30", "FALSE", &[("BBB", 0), ("A", 10)] ; "overlong word kept whole")]
+fn text_should_be_wrapped_within_its_rect(
+    content: &str,
+    rect: &str,
+    vertical_justify: &str,
+    expected: &[(&str, usize)],
+) {
+    let runner = create_runner_with_font();
+    let script = format!(
+        r#"
+        OBJECT=TESTTEXT
+        TESTTEXT:TYPE=TEXT
+        TESTTEXT:FONT=TESTFONT
+        TESTTEXT:RECT={rect}
+        TESTTEXT:VJUSTIFY={vertical_justify}
+        "#
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "TEXT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_text_object = runner.get_object("TESTTEXT").unwrap();
+    test_text_object
+        .call_method(
+            CallableIdentifier::Method("SETTEXT"),
+            &[CnvValue::String(content.into())],
+            None,
+        )
+        .unwrap();
+    let CnvContent::Text(ref text) = &test_text_object.content else {
+        unreachable!();
+    };
+
+    let lines = text.layout_lines().unwrap().unwrap();
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|(line, offset)| (line.to_string(), *offset))
+        .collect();
+    assert_eq!(lines, expected);
+    let rect = text.get_rect().unwrap().unwrap();
+    let ((width, height), _) = text.render().unwrap().unwrap();
+    assert_eq!((width, height), (rect.get_width(), rect.get_height()));
+}
+
+#[test]
+fn reloaded_font_file_should_be_read_again() {
+    let runner = create_runner_with_font();
+    let test_font_object = runner.get_object("TESTFONT").unwrap();
+    let CnvContent::Font(ref font) = &test_font_object.content else {
+        unreachable!();
+    };
+    assert_eq!(font.measure("A").unwrap(), (5, 10));
+
+    let frames = [TestFrame {
+        name: "GLYPH",
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    }];
+    let sequences = [TestSequence {
+        name: "A",
+        looping_after: 0,
+        frames: &frames,
+    }];
+    runner
+        .filesystem
+        .write()
+        .unwrap()
+        .write_file("TEST.FNT", &build_ann_with_sprites(&sequences, &[(9, 12)]))
+        .unwrap();
+    assert_eq!(runner.reload_resource("OTHER.FNT"), 0);
+    assert_eq!(runner.reload_resource("test.fnt"), 1);
+
+    assert_eq!(font.measure("A").unwrap(), (9, 12));
+}
@@ -0,0 +1,80 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case("", true)]
+#[test_case("TESTGROUP^HIDE();", false)]
+#[test_case("TESTGROUP^HIDE();TESTGROUP^SHOW();", true)]
+#[test_case("OUTERGROUP^HIDE();", false)]
+fn group_visibility_should_apply_to_members_in_screenshot(hide_call: &str, expected: bool) {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann(&[TestSequence {
+            name: "MAIN",
+            looping_after: 0,
+            frames: &[TestFrame {
+                name: "FIRST",
+                sprite_idx: 0,
+                offset_px: (0, 0),
+                sfx: None,
+            }],
+        }]),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (4, 4),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTGROUP
+        TESTGROUP:TYPE=GROUP
+
+        OBJECT=OUTERGROUP
+        OUTERGROUP:TYPE=GROUP
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTANIM^SETPOSITION(1, 2);TESTGROUP^ADD("TESTANIM");OUTERGROUP^ADD("TESTGROUP");{}}}
+        "#,
+        hide_call
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let black_background = Arc::new([0, 0, 0, 0xff].repeat(16));
+    let (_, screenshot) = runner
+        .get_screenshot(Some((*runner.window_rect.borrow(), black_background)))
+        .unwrap();
+    let pixel_offset = (2 * 4 + 1) * 4;
+    assert_eq!(screenshot[pixel_offset] == 0xff, expected);
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    assert_eq!(
+        test_anim_object
+            .call_method(CallableIdentifier::Method("ISVISIBLE"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::Bool(expected)
+    );
+    assert_eq!(
+        runner
+            .is_effectively_visible(&test_anim_object, &runner.get_objects_hidden_by_groups())
+            .unwrap(),
+        expected
+    );
+}
@@ -0,0 +1,226 @@
+use super::*;
+
+use test_case::test_case;
+
+const RED: u16 = 0xf800;
+const BLUE: u16 = 0x001f;
+
+#[test_case(None, None, true ; "declaration order")]
+#[test_case(None, Some(5), false ; "raised second")]
+#[test_case(Some(5), Some(3), true ; "raised both")]
+#[test_case(Some(-1), None, false ; "lowered first")]
+fn image_priority_should_define_composite_order(
+    first_priority: Option<i32>,
+    second_priority: Option<i32>,
+    expected_first_on_top: bool,
+) {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("FIRST.IMG", build_img((2, 2), (0, 0), RED))
+        .with_file("SECOND.IMG", build_img((2, 2), (1, 1), BLUE));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (4, 4),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=FIRSTIMG
+        FIRSTIMG:TYPE=IMAGE
+        FIRSTIMG:FILENAME=FIRST.IMG
+
+        OBJECT=SECONDIMG
+        SECONDIMG:TYPE=IMAGE
+        SECONDIMG:FILENAME=SECOND.IMG
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    for (name, priority) in [("FIRSTIMG", first_priority), ("SECONDIMG", second_priority)] {
+        let image_object = runner.get_object(name).unwrap();
+        if let Some(priority) = priority {
+            image_object
+                .call_method(
+                    CallableIdentifier::Method("SETPRIORITY"),
+                    &[CnvValue::Integer(priority)],
+                    None,
+                )
+                .unwrap();
+        }
+        assert_eq!(
+            image_object
+                .call_method(CallableIdentifier::Method("GETPRIORITY"), &Vec::new(), None)
+                .unwrap(),
+            CnvValue::Integer(priority.unwrap_or_default())
+        );
+    }
+
+    let (_, screenshot) = runner.get_screenshot(None).unwrap();
+    let pixel_offset = (4 + 1) * 4;
+    let is_red = screenshot[pixel_offset] > 0x80 && screenshot[pixel_offset + 2] < 0x80;
+    assert_eq!(is_red, expected_first_on_top);
+}
+
+#[test_case(None, 255, 0xf0..=0xff ; "default opacity")]
+#[test_case(Some(128), 128, 0x70..=0x80 ; "half opacity")]
+#[test_case(Some(0), 0, 0x00..=0x00 ; "transparent")]
+#[test_case(Some(300), 255, 0xf0..=0xff ; "clamped opacity")]
+fn image_opacity_should_blend_over_background(
+    opacity: Option<i32>,
+    expected_alpha: i32,
+    expected_red: std::ops::RangeInclusive<u8>,
+) {
+    let filesystem =
+        MemoryFileSystem::default().with_file("TEST.IMG", build_img((1, 1), (0, 0), RED));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (1, 1),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        TESTIMG:FILENAME=TEST.IMG
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_img_object = runner.get_object("TESTIMG").unwrap();
+    if let Some(opacity) = opacity {
+        test_img_object
+            .call_method(
+                CallableIdentifier::Method("SETOPACITY"),
+                &[CnvValue::Integer(opacity)],
+                None,
+            )
+            .unwrap();
+    }
+    assert_eq!(
+        test_img_object
+            .call_method(CallableIdentifier::Method("GETALPHA"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::Integer(expected_alpha)
+    );
+
+    let black_background = Arc::new(vec![0, 0, 0, 0xff]);
+    let (_, screenshot) = runner
+        .get_screenshot(Some((*runner.window_rect.borrow(), black_background)))
+        .unwrap();
+    assert!(
+        expected_red.contains(&screenshot[0]),
+        "red channel {} not in {:?}",
+        screenshot[0],
+        expected_red
+    );
+}
+
+#[test]
+fn screenshot_should_skip_graphics_with_mismatched_pixel_data() {
+    let filesystem =
+        MemoryFileSystem::default().with_file("TEST.IMG", build_img((1, 1), (1, 1), RED));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (2, 2),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        TESTIMG:FILENAME=TEST.IMG
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    // a 2x2 background described by the data of a single pixel
+    let truncated_background = Arc::new(vec![0, 0, 0, 0xff]);
+    let (_, screenshot) = runner
+        .get_screenshot(Some((*runner.window_rect.borrow(), truncated_background)))
+        .unwrap();
+    assert_eq!(screenshot[0..4], [0xff, 0xff, 0xff, 0xff]);
+    let pixel_offset = (2 + 1) * 4;
+    assert!(screenshot[pixel_offset] > 0x80 && screenshot[pixel_offset + 2] < 0x80);
+}
+
+#[test_case("REDRAW")]
+#[test_case("REFRESH")]
+fn canvas_redraw_should_request_full_recomposite(method: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(""),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.events_out.graphics.borrow_mut().clear();
+
+    runner
+        .get_object("CANVAS_OBSERVER")
+        .unwrap()
+        .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(
+        runner
+            .events_out
+            .graphics
+            .borrow()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>(),
+        vec![GraphicsEvent::CanvasRedrawRequested]
+    );
+}
+
+#[test_case("scene1/test.img", 1 ; "same resolved path")]
+#[test_case("DANE/SCENE1/TEST.IMG", 1 ; "path within data directory")]
+#[test_case("SCENE2/TEST.IMG", 0 ; "same name in another directory")]
+fn reloaded_resource_should_match_resolved_paths(changed_file: &str, expected: usize) {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("SCENE1/TEST.IMG", build_img((2, 2), (0, 0), RED))
+        .with_file("SCENE2/TEST.IMG", build_img((2, 2), (0, 0), BLUE));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        (4, 4),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        TESTIMG:FILENAME=TEST.IMG
+        "#;
+    runner
+        .load_script(
+            ScenePath::new("SCENE1", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    assert_eq!(runner.reload_resource(changed_file), expected);
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_integer(value: i32) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=CHANGEDCOUNT
+        CHANGEDCOUNT:TYPE=INTEGER
+        CHANGEDCOUNT:VALUE=0
+
+        OBJECT=TESTINT
+        TESTINT:TYPE=INTEGER
+        TESTINT:VALUE={}
+        TESTINT:ONCHANGED={{CHANGEDCOUNT^INC();}}
+        "#,
+        value
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test_case("INC", i32::MAX - 1, i32::MAX)]
+#[test_case("DEC", i32::MIN + 1, i32::MIN)]
+fn inc_and_dec_should_clamp_at_integer_bounds(method: &str, value: i32, expected: i32) {
+    let runner = create_runner_with_integer(value);
+    let test_int_object = runner.get_object("TESTINT").unwrap();
+    for _ in 0..2 {
+        let result = test_int_object
+            .call_method(CallableIdentifier::Method(method), &Vec::new(), None)
+            .unwrap();
+        assert_eq!(result, CnvValue::Null);
+        runner.step().unwrap();
+    }
+
+    let value = test_int_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Integer(expected));
+    let changed_count = runner
+        .get_object("CHANGEDCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(changed_count, CnvValue::Integer(1));
+}
+
+#[test_case(EventOverflowPolicy::AbortStep, false, 0 ; "abort step")]
+#[test_case(EventOverflowPolicy::DropOldest, true, 1 ; "drop oldest")]
+fn self_requeueing_handler_should_hit_event_limit(
+    policy: EventOverflowPolicy,
+    expected_ok: bool,
+    expected_queued: usize,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+        COUNTER:VALUE=0
+        COUNTER:ONCHANGED={COUNTER^INC();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+    runner.set_internal_event_limit(InternalEventLimit {
+        capacity: 5,
+        policy,
+    });
+    let counter_object = runner.get_object("COUNTER").unwrap();
+    counter_object
+        .call_method(CallableIdentifier::Method("INC"), &Vec::new(), None)
+        .unwrap();
+
+    let result = runner.step();
+
+    assert_eq!(result.is_ok(), expected_ok);
+    assert_eq!(runner.internal_events.borrow().len(), expected_queued);
+    let value = counter_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(value, CnvValue::Integer(3));
+}
+
+#[test_case(12, 1, 0 ; "matching pattern")]
+#[test_case(5, 0, 1 ; "other value")]
+fn changed_handlers_should_match_patterns(
+    value: i32,
+    expected_pattern: i32,
+    expected_default: i32,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTINT
+        TESTINT:TYPE=INTEGER
+        TESTINT:VALUE=0
+        TESTINT:ONCHANGED^1*={PATTERNCOUNT^INC();}
+        TESTINT:ONCHANGED={DEFAULTCOUNT^INC();}
+        OBJECT=PATTERNCOUNT
+        PATTERNCOUNT:TYPE=INTEGER
+        PATTERNCOUNT:VALUE=0
+        OBJECT=DEFAULTCOUNT
+        DEFAULTCOUNT:TYPE=INTEGER
+        DEFAULTCOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    runner
+        .get_object("TESTINT")
+        .unwrap()
+        .call_method(
+            CallableIdentifier::Event("ONCHANGED"),
+            &[CnvValue::Integer(value)],
+            None,
+        )
+        .unwrap();
+
+    let get_count = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(
+        get_count("PATTERNCOUNT"),
+        CnvValue::Integer(expected_pattern)
+    );
+    assert_eq!(
+        get_count("DEFAULTCOUNT"),
+        CnvValue::Integer(expected_default)
+    );
+}
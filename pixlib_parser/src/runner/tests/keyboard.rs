@@ -0,0 +1,106 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case(&[KeyboardKey::AltRight, KeyboardKey::KeyZ], "ż" ; "diacritic")]
+#[test_case(&[KeyboardKey::ShiftLeft, KeyboardKey::AltRight, KeyboardKey::KeyL], "Ł" ; "uppercase diacritic")]
+#[test_case(&[KeyboardKey::ShiftLeft, KeyboardKey::Digit1], "!" ; "shifted symbol")]
+#[test_case(&[KeyboardKey::KeyA], "a" ; "plain letter")]
+#[test_case(&[KeyboardKey::ControlLeft, KeyboardKey::KeyA], "" ; "control shortcut")]
+#[test_case(&[KeyboardKey::ShiftLeft], "" ; "modifier only")]
+fn key_presses_should_be_translated_into_characters(keys: &[KeyboardKey], expected: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=LASTCHAR
+        LASTCHAR:TYPE=STRING
+
+        OBJECT=KEYDOWNCOUNT
+        KEYDOWNCOUNT:TYPE=INTEGER
+        KEYDOWNCOUNT:VALUE=0
+
+        OBJECT=TESTKEYBOARD
+        TESTKEYBOARD:TYPE=KEYBOARD
+        TESTKEYBOARD:ONCHAR={LASTCHAR^SET($1);}
+        TESTKEYBOARD:ONKEYDOWN={KEYDOWNCOUNT^INC();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+    for &key_code in keys {
+        runner
+            .events_in
+            .keyboard
+            .borrow_mut()
+            .push_back(KeyboardEvent::KeyPressed { key_code });
+    }
+    runner.step().unwrap();
+
+    let last_char = runner
+        .get_object("LASTCHAR")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(last_char, CnvValue::String(expected.into()));
+    let key_down_count = runner
+        .get_object("KEYDOWNCOUNT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(key_down_count, CnvValue::Integer(keys.len() as i32));
+}
+
+#[test]
+fn released_modifiers_should_not_affect_later_characters() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=LASTCHAR
+        LASTCHAR:TYPE=STRING
+
+        OBJECT=TESTKEYBOARD
+        TESTKEYBOARD:TYPE=KEYBOARD
+        TESTKEYBOARD:ONCHAR={LASTCHAR^SET($1);}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.events_in.keyboard.borrow_mut().extend([
+        KeyboardEvent::KeyPressed {
+            key_code: KeyboardKey::AltRight,
+        },
+        KeyboardEvent::KeyReleased {
+            key_code: KeyboardKey::AltRight,
+        },
+        KeyboardEvent::KeyPressed {
+            key_code: KeyboardKey::KeyS,
+        },
+    ]);
+    runner.step().unwrap();
+
+    let last_char = runner
+        .get_object("LASTCHAR")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(last_char, CnvValue::String("s".into()));
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+#[test]
+fn referenced_files_should_be_collected_without_loading() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=HERO.ANN
+
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        TESTIMG:FILENAME=BACKGROUND.IMG
+
+        OBJECT=TESTSND
+        TESTSND:TYPE=SOUND
+        TESTSND:FILENAME=STEP.WAV
+
+        OBJECT=OTHERSND
+        OTHERSND:TYPE=SOUND
+        OTHERSND:FILENAME=STEP.WAV
+
+        OBJECT=TESTSEQ
+        TESTSEQ:TYPE=SEQUENCE
+        TESTSEQ:FILENAME=DIALOG.SEQ
+
+        OBJECT=TESTFONT
+        TESTFONT:TYPE=FONT
+        TESTFONT:DEF_ARIAL_STANDARD_14=ARIAL14.FNT
+
+        OBJECT=TESTSCENE
+        TESTSCENE:TYPE=SCENE
+        TESTSCENE:BACKGROUND=SCENE.IMG
+        TESTSCENE:MUSIC=THEME.WAV
+
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let expected = vec![
+        "HERO.ANN",
+        "BACKGROUND.IMG",
+        "STEP.WAV",
+        "DIALOG.SEQ",
+        "ARIAL14.FNT",
+        "SCENE.IMG",
+        "THEME.WAV",
+    ];
+    assert_eq!(runner.collect_referenced_files(), expected);
+    let script = runner.get_object("TESTANIM").unwrap().parent.clone();
+    assert_eq!(script.collect_referenced_files(), expected);
+}
+
+#[test]
+fn missing_and_mistyped_resources_should_be_reported() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("HERO.ANN", build_img((1, 1), (0, 0), 0xffff))
+        .with_file("STEP.WAV", b"RIFF\x24\0\0\0WAVEfmt ".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=HERO.ANN
+
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        TESTIMG:FILENAME=BACKGROUND.IMG
+
+        OBJECT=TESTSND
+        TESTSND:TYPE=SOUND
+        TESTSND:FILENAME=STEP.WAV
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    assert_eq!(
+        runner.verify_resources(),
+        vec![
+            ResourceIssue::WrongFormat {
+                object_name: "TESTANIM".to_owned(),
+                filename: "HERO.ANN".to_owned(),
+                expected: FileFormat::Ann,
+                detected: Some(FileFormat::Img),
+            },
+            ResourceIssue::Missing {
+                object_name: "TESTIMG".to_owned(),
+                filename: "BACKGROUND.IMG".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn fonts_and_encrypted_sequences_should_be_accepted() {
+    let frames = [TestFrame {
+        name: "FIRST",
+        sprite_idx: 0,
+        offset_px: (0, 0),
+        sfx: None,
+    }];
+    let build_ann = |sequence_name| {
+        build_ann_with_sprites(
+            &[TestSequence {
+                name: sequence_name,
+                looping_after: 0,
+                frames: &frames,
+            }],
+            &[(1, 1)],
+        )
+    };
+    let filesystem = MemoryFileSystem::default()
+        .with_file("HERO.ANN", build_ann("A"))
+        .with_file("ARIAL14.FNT", build_ann("WALK"))
+        .with_file("DIALOG.SEQ", b"{<C:6>}\r\n\x8a\x12\xf0".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=HERO.ANN
+
+        OBJECT=TESTFONT
+        TESTFONT:TYPE=FONT
+        TESTFONT:DEF_ARIAL_STANDARD_14=ARIAL14.FNT
+
+        OBJECT=TESTSEQ
+        TESTSEQ:TYPE=SEQUENCE
+        TESTSEQ:FILENAME=DIALOG.SEQ
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    assert_eq!(runner.verify_resources(), Vec::new());
+}
@@ -1,4 +1,29 @@
+mod animation;
+mod application;
+mod array;
+mod button;
+mod condition;
+mod control_flow;
+mod double;
+mod font;
+mod group;
+mod image;
+mod integer;
+mod keyboard;
+mod manifest;
+mod mouse;
+mod object;
+mod scene;
+mod sound;
+mod string;
+mod structure;
+mod timer;
+mod trace;
+mod value;
+mod window;
+
 use crate::filesystems::DummyFileSystem;
+use pixlib_formats::test_utils::{self, TestFrame, TestSequence, TestSprite};
 
 use crate::{common::Position, runner::CallableIdentifier};
 
@@ -250,6 +275,210 @@ fn surrounding_quotes_should_be_handled_correctly_with_two_level_indirect_set_an
     assert_eq!(result, CnvValue::String(expected.into()));
 }
 
+#[test_case(
+    "integer literal with one level",
+    "INTEGER",
+    "\"5\"",
+    CnvValue::Integer(5)
+)]
+#[test_case(
+    "integer literal with two levels",
+    "INTEGER",
+    "\"\"5\"\"",
+    CnvValue::Integer(5)
+)]
+#[test_case(
+    "integer from quoted string",
+    "INTEGER",
+    "TESTSTR",
+    CnvValue::Integer(5)
+)]
+#[test_case(
+    "double literal with one level",
+    "DOUBLE",
+    "\"2.5\"",
+    CnvValue::Double(2.5)
+)]
+#[test_case(
+    "double literal with two levels",
+    "DOUBLE",
+    "\"\"2.5\"\"",
+    CnvValue::Double(2.5)
+)]
+#[test_case(
+    "double from quoted string",
+    "DOUBLE",
+    "TESTDBLSTR",
+    CnvValue::Double(2.5)
+)]
+fn surrounding_quotes_should_be_stripped_before_coercion_with_set(
+    _description: &str,
+    type_name: &str,
+    argument: &str,
+    expected: CnvValue,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+        TESTSTR:VALUE="5"
+
+        OBJECT=TESTDBLSTR
+        TESTDBLSTR:TYPE=STRING
+        TESTDBLSTR:VALUE="2.5"
+
+        OBJECT=TESTVAR
+        TESTVAR:TYPE={}
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTVAR^SET({});}}
+        "#,
+        type_name, argument
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    let test_var_object = runner.get_object("TESTVAR").unwrap();
+    let result = test_var_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test_case("INTEGER", "\"\"2\"\"", CnvValue::Integer(3))]
+#[test_case("INTEGER", "TESTSTR", CnvValue::Integer(6))]
+#[test_case("DOUBLE", "TESTDBLSTR", CnvValue::Double(3.5))]
+fn surrounding_quotes_should_be_stripped_before_coercion_with_add(
+    type_name: &str,
+    argument: &str,
+    expected: CnvValue,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+        TESTSTR:VALUE="5"
+
+        OBJECT=TESTDBLSTR
+        TESTDBLSTR:TYPE=STRING
+        TESTDBLSTR:VALUE="2.5"
+
+        OBJECT=TESTVAR
+        TESTVAR:TYPE={}
+        TESTVAR:VALUE=1
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={{TESTVAR^ADD({});}}
+        "#,
+        type_name, argument
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_beh_object = runner.get_object("TESTBEH").unwrap();
+    test_beh_object
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+    let test_var_object = runner.get_object("TESTVAR").unwrap();
+    let result = test_var_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn circular_references_should_fail_instead_of_overflowing() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=FIRSTSTR
+        FIRSTSTR:TYPE=STRING
+        FIRSTSTR:VALUE=SECONDSTR
+
+        OBJECT=SECONDSTR
+        SECONDSTR:TYPE=STRING
+        SECONDSTR:VALUE=FIRSTSTR
+
+        OBJECT=FIRSTEXPR
+        FIRSTEXPR:TYPE=EXPRESSION
+        FIRSTEXPR:OPERAND1=SECONDEXPR
+        FIRSTEXPR:OPERATOR=ADD
+        FIRSTEXPR:OPERAND2=1
+
+        OBJECT=SECONDEXPR
+        SECONDEXPR:TYPE=EXPRESSION
+        SECONDEXPR:OPERAND1=FIRSTEXPR
+        SECONDEXPR:OPERATOR=ADD
+        SECONDEXPR:OPERAND2=1
+
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_str_object = runner.get_object("TESTSTR").unwrap();
+    let set = |argument: &str| {
+        test_str_object.call_method(
+            CallableIdentifier::Method("SET"),
+            &[CnvValue::String(argument.into())],
+            None,
+        )
+    };
+
+    set("FIRSTSTR").unwrap();
+    assert_eq!(
+        test_str_object
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::String("SECONDSTR".into())
+    );
+    assert!(matches!(
+        set("FIRSTEXPR").unwrap_err().downcast_ref::<RunnerError>(),
+        Some(RunnerError::ReferenceDepthExceeded { .. })
+    ));
+    assert!(set("FIRSTSTR").is_ok());
+}
+
 #[test]
 fn behaviors_passed_by_name_should_handle_arguments_correctly() {
     let runner = CnvRunner::try_new(
@@ -302,3 +531,132 @@ fn as_parser_input(string: &str) -> impl Iterator<Item = declarative_parser::Par
         ))
     })
 }
+
+#[derive(Debug, Default)]
+struct MemoryFileSystem(HashMap<String, Arc<Vec<u8>>>);
+
+impl MemoryFileSystem {
+    pub fn with_file(mut self, filename: &str, data: Vec<u8>) -> Self {
+        self.0.insert(filename.to_owned(), Arc::new(data));
+        self
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
+        ResolveStrategy::default()
+            .resolve(filename, self.0.keys().map(|k| k.as_str()))
+            .and_then(|k| self.0.get(k))
+            .map(Arc::clone)
+            .ok_or(std::io::Error::from(std::io::ErrorKind::NotFound))
+    }
+
+    fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()> {
+        self.0
+            .insert(filename.to_owned(), Arc::new(data.to_owned()));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SoundBackendCall {
+    Load(SoundSource, u64),
+    Play(SoundSource),
+    Stop(SoundSource),
+    Pause(SoundSource),
+    Resume(SoundSource),
+    SetVolume(SoundSource, f32),
+    SetPan(SoundSource, f32),
+}
+
+/// Sound backend which only records the calls it receives.
+#[derive(Debug, Default)]
+struct RecordingSoundBackend(Vec<SoundBackendCall>);
+
+impl SoundBackend for RecordingSoundBackend {
+    fn load(&mut self, source: &SoundSource, sound_data: &common::SoundData) {
+        self.0
+            .push(SoundBackendCall::Load(source.clone(), sound_data.hash));
+    }
+
+    fn play(&mut self, source: &SoundSource) {
+        self.0.push(SoundBackendCall::Play(source.clone()));
+    }
+
+    fn stop(&mut self, source: &SoundSource) {
+        self.0.push(SoundBackendCall::Stop(source.clone()));
+    }
+
+    fn pause(&mut self, source: &SoundSource) {
+        self.0.push(SoundBackendCall::Pause(source.clone()));
+    }
+
+    fn resume(&mut self, source: &SoundSource) {
+        self.0.push(SoundBackendCall::Resume(source.clone()));
+    }
+
+    fn set_volume(&mut self, source: &SoundSource, volume: f32) {
+        self.0
+            .push(SoundBackendCall::SetVolume(source.clone(), volume));
+    }
+
+    fn set_pan(&mut self, source: &SoundSource, pan: f32) {
+        self.0.push(SoundBackendCall::SetPan(source.clone(), pan));
+    }
+}
+
+/// Builds an ANN file whose frames all show a single 1x1 sprite.
+fn build_ann(sequences: &[TestSequence]) -> Vec<u8> {
+    build_ann_with_sprites(sequences, &[(1, 1)])
+}
+
+/// Builds an ANN file with opaque white sprites of the given sizes.
+fn build_ann_with_sprites(sequences: &[TestSequence], sprite_sizes: &[(u16, u16)]) -> Vec<u8> {
+    let sprites = sprite_sizes
+        .iter()
+        .map(|&(width, height)| {
+            (
+                (width, height),
+                vec![0xffu8; width as usize * height as usize],
+            )
+        })
+        .collect::<Vec<_>>();
+    build_ann_with_alpha_masks(sequences, &sprites)
+}
+
+/// Builds an ANN file with white sprites of the given sizes and per-pixel alpha.
+fn build_ann_with_alpha_masks(
+    sequences: &[TestSequence],
+    sprites: &[((u16, u16), Vec<u8>)],
+) -> Vec<u8> {
+    let sprites = sprites
+        .iter()
+        .map(|(size, alpha)| TestSprite {
+            size: *size,
+            offset_px: (0, 0),
+            color: 0xffff,
+            alpha: alpha.clone(),
+        })
+        .collect::<Vec<_>>();
+    test_utils::build_ann(16, sequences, &sprites)
+}
+
+/// Builds an uncompressed, opaque IMG file filled with a single RGB565 color.
+fn build_img(size: (u32, u32), position: (i32, i32), color: u16) -> Vec<u8> {
+    let pixel_count = size.0 as usize * size.1 as usize;
+    let mut buffer = Vec::new();
+    buffer.extend(b"PIK\0");
+    buffer.extend(size.0.to_le_bytes());
+    buffer.extend(size.1.to_le_bytes());
+    buffer.extend(16u32.to_le_bytes());
+    buffer.extend((pixel_count as u32 * 2).to_le_bytes());
+    buffer.extend(0u32.to_le_bytes());
+    buffer.extend(0u32.to_le_bytes());
+    buffer.extend(0u32.to_le_bytes());
+    buffer.extend(position.0.to_le_bytes());
+    buffer.extend(position.1.to_le_bytes());
+    for _ in 0..pixel_count {
+        buffer.extend(color.to_le_bytes());
+    }
+    buffer
+}
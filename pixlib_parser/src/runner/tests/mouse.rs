@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn set_should_queue_cursor_image_change() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTMOUSE
+        TESTMOUSE:TYPE=MOUSE
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTMOUSE^SET("WAIT");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.events_out.cursor.borrow_mut().clear();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let cursor_events: Vec<_> = runner.events_out.cursor.borrow().iter().cloned().collect();
+    assert_eq!(
+        cursor_events,
+        vec![CursorEvent::CursorSetToImage("WAIT".into())]
+    );
+}
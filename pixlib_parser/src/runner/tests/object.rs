@@ -0,0 +1,262 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test]
+fn unknown_types_should_not_prevent_loading_other_objects() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTDB
+        TESTDB:TYPE=DATABASE
+        TESTDB:FILENAME=DATA.DTA
+
+        OBJECT=TESTINT
+        TESTINT:TYPE=INTEGER
+        TESTINT:VALUE=7
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let test_db_object = runner.get_object("TESTDB").unwrap();
+    let CnvContent::Unknown(ref unknown) = &test_db_object.content else {
+        panic!("Expected an unknown type placeholder");
+    };
+    assert_eq!(unknown.get_type_name(), "DATABASE");
+    assert_eq!(
+        unknown.get_properties().get("FILENAME").map(String::as_str),
+        Some("DATA.DTA")
+    );
+    let error = test_db_object
+        .call_method(CallableIdentifier::Method("LOAD"), &Vec::new(), None)
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::Unimplemented { type_name, method, .. })
+            if type_name == "DATABASE" && method == "LOAD"
+    ));
+    let result = runner
+        .get_object("TESTINT")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(7));
+}
+
+#[test]
+fn unknown_methods_should_be_rejected_as_invalid_callables() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTINT
+        TESTINT:TYPE=INTEGER
+        TESTINT:VALUE=7
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_int_object = runner.get_object("TESTINT").unwrap();
+
+    for _ in 0..5 {
+        let error = test_int_object
+            .call_method(
+                CallableIdentifier::Method("NOSUCHMETHOD"),
+                &Vec::new(),
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<RunnerError>(),
+            Some(RunnerError::InvalidCallable { object_name, callable })
+                if object_name == "TESTINT"
+                    && *callable == CallableIdentifierOwned::Method("NOSUCHMETHOD".into())
+        ));
+    }
+    let result = test_int_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(result, CnvValue::Integer(7));
+}
+
+#[test]
+fn state_dump_should_list_objects_sorted_with_their_state() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SCORE
+        SCORE:TYPE=INTEGER
+        SCORE:VALUE=7
+
+        OBJECT=BACKGROUND
+        BACKGROUND:TYPE=IMAGE
+        BACKGROUND:FILENAME=BACKGROUND.IMG
+        BACKGROUND:VISIBLE=FALSE
+
+        OBJECT=HERO
+        HERO:TYPE=ANIMO
+        HERO:FILENAME=HERO.ANN
+
+        OBJECT=NAME
+        NAME:TYPE=STRING
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={HERO^SETPOSITION(10, 20);NAME^SET("REKSIO");}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let expected = r#"BACKGROUND: IMAGE position=(0, 0) visible=false
+CANVASOBSERVER: CANVASOBSERVER
+CANVAS_OBSERVER: CANVASOBSERVER
+HERO: ANIMO position=(10, 20) visible=true
+KEYBOARD: KEYBOARD
+MOUSE: MOUSE
+NAME: STRING value="REKSIO"
+RANDOM: RANDOM
+SCORE: INTEGER value=7
+SYSTEM: SYSTEM
+TESTBEH: BEHAVIOUR
+"#;
+    assert_eq!(runner.dump_state(), expected);
+}
+
+#[test_case("TESTINT", "ADD", &[], 1)]
+#[test_case("TESTSTR", "SET", &[], 1)]
+#[test_case("TESTDBL", "SET", &[], 1)]
+#[test_case("TESTANIM", "MOVE", &[CnvValue::Integer(1)], 2)]
+#[test_case("TESTANIM", "ISNEAR", &[CnvValue::String("TESTIMG".into())], 2)]
+#[test_case("TESTIMG", "SETPOSITION", &[CnvValue::Integer(1)], 2)]
+fn methods_called_with_missing_arguments_should_fail_gracefully(
+    object_name: &str,
+    method: &str,
+    arguments: &[CnvValue],
+    expected_min: usize,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTINT
+        TESTINT:TYPE=INTEGER
+
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+
+        OBJECT=TESTDBL
+        TESTDBL:TYPE=DOUBLE
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+
+        OBJECT=TESTIMG
+        TESTIMG:TYPE=IMAGE
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+
+    let error = runner
+        .get_object(object_name)
+        .unwrap()
+        .call_method(CallableIdentifier::Method(method), arguments, None)
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<RunnerError>(),
+        Some(RunnerError::TooFewArguments { expected_min: min, actual })
+            if *min == expected_min && *actual == arguments.len()
+    ));
+}
+
+#[test]
+fn deep_clone_should_copy_current_state_independently() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+        TESTSTR:VALUE=INITIAL
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let original = runner.get_object("TESTSTR").unwrap();
+    original
+        .call_method(
+            CallableIdentifier::Method("SET"),
+            &[CnvValue::String("CHANGED".into())],
+            None,
+        )
+        .unwrap();
+
+    let clone = original.deep_clone("TESTSTR_1", 1);
+    let get = |object: &Arc<CnvObject>| {
+        object
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    assert_eq!(clone.name, "TESTSTR_1");
+    assert_eq!(clone.index, 1);
+    assert_eq!(get(&clone), CnvValue::String("CHANGED".into()));
+
+    clone
+        .call_method(
+            CallableIdentifier::Method("SET"),
+            &[CnvValue::String("CLONED".into())],
+            None,
+        )
+        .unwrap();
+    assert_eq!(get(&clone), CnvValue::String("CLONED".into()));
+    assert_eq!(get(&original), CnvValue::String("CHANGED".into()));
+}
@@ -0,0 +1,441 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test]
+fn scene_change_should_emit_transition_events_in_order() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("SCENE1.CNV", b"OBJECT=VAR1\nVAR1:TYPE=INTEGER\n".to_vec())
+        .with_file("SCENE2.CNV", b"OBJECT=VAR2\nVAR2:TYPE=INTEGER\n".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=CANVAS
+        CANVAS:TYPE=CANVAS_OBSERVER
+
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+
+        OBJECT=SCENE2
+        SCENE2:TYPE=SCENE
+        SCENE2:PATH=SCENES
+        SCENE2:TRANSITION=FADE
+        SCENE2:TRANSITIONTIME=500
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.change_scene("SCENE2").unwrap();
+
+    let graphics_events: Vec<_> = runner
+        .events_out
+        .graphics
+        .borrow()
+        .iter()
+        .cloned()
+        .collect();
+    let transition = Some(SceneTransition {
+        kind: "FADE".into(),
+        duration_ms: 500,
+    });
+    assert_eq!(
+        graphics_events,
+        vec![
+            GraphicsEvent::SceneLoaded {
+                name: "SCENE1".into(),
+                transition: None,
+            },
+            GraphicsEvent::SceneUnloading {
+                name: "SCENE1".into(),
+                transition: transition.clone(),
+            },
+            GraphicsEvent::SceneLoaded {
+                name: "SCENE2".into(),
+                transition,
+            },
+        ]
+    );
+}
+
+#[test]
+fn run_should_call_object_method_and_return_its_result() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTSCENE
+        TESTSCENE:TYPE=SCENE
+        TESTSCENE:PATH=SCENES
+
+        OBJECT=HELPER
+        HELPER:TYPE=BEHAVIOUR
+        HELPER:CODE={@RETURN($1);}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let scene_object = runner.get_object("TESTSCENE").unwrap();
+    let run = |object_name: &str, method_name: &str| {
+        scene_object.call_method(
+            CallableIdentifier::Method("RUN"),
+            &[
+                CnvValue::String(object_name.into()),
+                CnvValue::String(method_name.into()),
+                CnvValue::Integer(11),
+            ],
+            None,
+        )
+    };
+
+    assert_eq!(run("HELPER", "RUN").unwrap(), CnvValue::Integer(11));
+    assert!(matches!(
+        run("MISSING", "RUN").unwrap_err().downcast_ref::<RunnerError>(),
+        Some(RunnerError::ObjectNotFound { name }) if name == "MISSING"
+    ));
+    assert!(matches!(
+        run("HELPER", "NOSUCHMETHOD").unwrap_err().downcast_ref::<RunnerError>(),
+        Some(RunnerError::InvalidCallable { object_name, .. }) if object_name == "HELPER"
+    ));
+}
+
+#[derive(Debug)]
+struct CountingFileSystem {
+    inner: MemoryFileSystem,
+    read_count: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl FileSystem for CountingFileSystem {
+    fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
+        self.read_count
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.inner.read_file(filename)
+    }
+
+    fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()> {
+        self.inner.write_file(filename, data)
+    }
+}
+
+#[test]
+fn goto_should_not_read_cached_scene_script_again() {
+    let read_count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let filesystem = CountingFileSystem {
+        inner: MemoryFileSystem::default()
+            .with_file("SCENE2.CNV", b"OBJECT=VAR2\nVAR2:TYPE=INTEGER\n".to_vec()),
+        read_count: Arc::clone(&read_count),
+    };
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTEPISODE
+        TESTEPISODE:TYPE=EPISODE
+        TESTEPISODE:SCENES=SCENE2
+
+        OBJECT=SCENE2
+        SCENE2:TYPE=SCENE
+        SCENE2:PATH=SCENES
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let episode_object = runner.get_object("TESTEPISODE").unwrap();
+    episode_object
+        .call_method(
+            CallableIdentifier::Method("PRELOADSCENE"),
+            &[CnvValue::String("SCENE2".into())],
+            None,
+        )
+        .unwrap();
+    let reads_after_preload = read_count.load(std::sync::atomic::Ordering::Relaxed);
+    assert!(reads_after_preload > 0);
+
+    episode_object
+        .call_method(
+            CallableIdentifier::Method("GOTO"),
+            &[CnvValue::String("SCENE2".into())],
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(
+        read_count.load(std::sync::atomic::Ordering::Relaxed),
+        reads_after_preload
+    );
+    assert_eq!(runner.get_current_scene().unwrap().name, "SCENE2");
+    assert!(runner.get_object("VAR2").is_some());
+}
+
+#[test]
+fn revisited_scene_should_not_be_scanned_again() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("SCENE1.CNV", b"OBJECT=VAR1\nVAR1:TYPE=INTEGER\n".to_vec())
+        .with_file("SCENE2.CNV", b"OBJECT=VAR2\nVAR2:TYPE=INTEGER\n".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+
+        OBJECT=SCENE2
+        SCENE2:TYPE=SCENE
+        SCENE2:PATH=SCENES
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.change_scene("SCENE2").unwrap();
+    runner.change_scene("SCENE1").unwrap();
+
+    assert_eq!(runner.parsed_scripts.borrow().parse_count, 2);
+    assert!(runner.get_object("VAR1").is_some());
+
+    runner.reload_resource("SCENE2.CNV");
+    runner.change_scene("SCENE2").unwrap();
+
+    assert_eq!(runner.parsed_scripts.borrow().parse_count, 3);
+}
+
+#[test]
+fn scene_initialization_should_report_resource_loading_progress() {
+    let scene_script = r#"
+        OBJECT=FIRSTIMG
+        FIRSTIMG:TYPE=IMAGE
+        FIRSTIMG:FILENAME=TEST.IMG
+
+        OBJECT=COUNTER
+        COUNTER:TYPE=INTEGER
+
+        OBJECT=SECONDIMG
+        SECONDIMG:TYPE=IMAGE
+        SECONDIMG:FILENAME=TEST.IMG
+
+        OBJECT=THIRDIMG
+        THIRDIMG:TYPE=IMAGE
+        THIRDIMG:FILENAME=TEST.IMG
+        "#;
+    let filesystem = MemoryFileSystem::default()
+        .with_file("SCENE1.CNV", scene_script.as_bytes().to_vec())
+        .with_file("TEST.IMG", build_img((1, 1), (0, 0), 0));
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.events_out.script.borrow_mut().clear();
+    runner.step().unwrap();
+
+    let progress: Vec<_> = runner
+        .events_out
+        .script
+        .borrow()
+        .iter()
+        .filter_map(|e| match e {
+            ScriptEvent::ScriptResourcesLoading {
+                path,
+                loaded,
+                total,
+            } => Some((path.clone(), *loaded, *total)),
+            _ => None,
+        })
+        .collect();
+    let scene_path = ScenePath::new("SCENES", "SCENE1");
+    assert_eq!(
+        progress,
+        vec![
+            (scene_path.clone(), 1, 3),
+            (scene_path.clone(), 2, 3),
+            (scene_path, 3, 3),
+        ]
+    );
+}
+
+#[test_case(true, vec![SoundEvent::SoundStarted(SoundSource::BackgroundMusic)] ; "looping")]
+#[test_case(false, vec![SoundEvent::SoundStopped(SoundSource::BackgroundMusic)] ; "one shot")]
+fn finished_music_should_restart_only_when_looping(looping: bool, expected: Vec<SoundEvent>) {
+    let filesystem = MemoryFileSystem::default()
+        .with_file("SCENE1.CNV", b"OBJECT=VAR1\nVAR1:TYPE=INTEGER\n".to_vec())
+        .with_file("MUSIC.WAV", b"RIFF".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+        SCENE1:MUSIC=MUSIC.WAV
+        SCENE1:MUSICLOOP={}
+        "#,
+        if looping { "TRUE" } else { "FALSE" }
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.step().unwrap();
+    runner.events_out.sound.borrow_mut().clear();
+
+    for _ in 0..2 {
+        runner
+            .events_in
+            .multimedia
+            .borrow_mut()
+            .push_back(MultimediaEvents::SoundFinishedPlaying(
+                SoundSource::BackgroundMusic,
+            ));
+        runner.step().unwrap();
+    }
+
+    let sound_events: Vec<_> = runner.events_out.sound.borrow().iter().cloned().collect();
+    let expected: Vec<_> = if looping {
+        expected.iter().chain(expected.iter()).cloned().collect()
+    } else {
+        expected
+    };
+    assert_eq!(sound_events, expected);
+}
+
+#[test]
+fn scene_change_should_release_previous_scene_resources() {
+    let filesystem = MemoryFileSystem::default()
+        .with_file(
+            "SCENE1.CNV",
+            b"OBJECT=SCENESND\nSCENESND:TYPE=SOUND\nSCENESND:FILENAME=SFX.WAV\n".to_vec(),
+        )
+        .with_file("SCENE2.CNV", b"OBJECT=VAR2\nVAR2:TYPE=INTEGER\n".to_vec());
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=SCENE1
+        SCENE1:TYPE=SCENE
+        SCENE1:PATH=SCENES
+
+        OBJECT=SCENE2
+        SCENE2:TYPE=SCENE
+        SCENE2:PATH=SCENES
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("SCENE1").unwrap();
+    runner.events_out.sound.borrow_mut().clear();
+
+    runner.change_scene("SCENE2").unwrap();
+
+    let sound_events: Vec<_> = runner.events_out.sound.borrow().iter().cloned().collect();
+    assert_eq!(
+        sound_events,
+        vec![SoundEvent::SoundStopped(SoundSource::Sound {
+            script_path: ScenePath::new("SCENES", "SCENE1"),
+            object_name: "SCENESND".into(),
+            instance: 0,
+        })]
+    );
+    assert!(runner.get_object("SCENESND").is_none());
+}
+
+#[test]
+fn scene_should_be_loaded_from_directory_mapped_from_its_path() {
+    let filesystem = crate::filesystems::BundleFileSystem::new([(
+        "DANE/SCENES/INTRO_V2/INTRO.CNV".to_owned(),
+        b"OBJECT=INTROVAR\nINTROVAR:TYPE=INTEGER\n".to_vec(),
+    )])
+    .with_resolve_strategy(
+        ResolveStrategy::default().with_directory_mapping("SCENES/INTRO", "SCENES/INTRO_V2"),
+    );
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(filesystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=INTRO
+        INTRO:TYPE=SCENE
+        INTRO:PATH=SCENES\INTRO
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.change_scene("INTRO").unwrap();
+
+    assert_eq!(runner.get_current_scene().unwrap().name, "INTRO");
+    assert!(runner.get_object("INTROVAR").is_some());
+}
@@ -0,0 +1,192 @@
+use super::*;
+
+fn create_runner_with_script(filesystem: MemoryFileSystem, script: &str) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(
+            filesystem.with_file("SFX.WAV", b"RIFF".to_vec()),
+        )),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+#[test]
+fn sound_play_should_reach_backend() {
+    let script = r#"
+        OBJECT=TESTSND
+        TESTSND:TYPE=SOUND
+        TESTSND:FILENAME=SFX.WAV
+        "#;
+    let runner = create_runner_with_script(MemoryFileSystem::default(), script);
+    runner.drain_sound_events(&mut RecordingSoundBackend::default());
+    let test_snd_object = runner.get_object("TESTSND").unwrap();
+    test_snd_object
+        .call_method(CallableIdentifier::Method("PLAY"), &Vec::new(), None)
+        .unwrap();
+
+    let mut backend = RecordingSoundBackend::default();
+    runner.drain_sound_events(&mut backend);
+
+    let expected_source = SoundSource::Sound {
+        script_path: ScenePath::new(".", "SCRIPT.CNV"),
+        object_name: "TESTSND".into(),
+        instance: 1,
+    };
+    assert_eq!(
+        backend.0.last(),
+        Some(&SoundBackendCall::Play(expected_source))
+    );
+    assert!(runner.events_out.sound.borrow().is_empty());
+}
+
+#[test]
+fn animation_sfx_parameters_should_reach_backend() {
+    let filesystem = MemoryFileSystem::default().with_file(
+        "TEST.ANN",
+        build_ann(&[TestSequence {
+            name: "MAIN",
+            looping_after: 0,
+            frames: &[TestFrame {
+                name: "FIRST",
+                sprite_idx: 0,
+                offset_px: (0, 0),
+                sfx: Some("SFX.WAV"),
+            }],
+        }]),
+    );
+    let script = r#"
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:FILENAME=TEST.ANN
+
+        OBJECT=TESTBEH
+        TESTBEH:TYPE=BEHAVIOUR
+        TESTBEH:CODE={TESTANIM^SETVOLUME(500);TESTANIM^SETPAN(-1000);TESTANIM^PLAY("MAIN");}
+        "#;
+    let runner = create_runner_with_script(filesystem, script);
+    runner
+        .get_object("TESTBEH")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("RUN"), &Vec::new(), None)
+        .unwrap();
+
+    let mut backend = RecordingSoundBackend::default();
+    runner.drain_sound_events(&mut backend);
+
+    let expected_source = SoundSource::AnimationSfx {
+        script_path: ScenePath::new(".", "SCRIPT.CNV"),
+        object_name: "TESTANIM".into(),
+    };
+    assert!(backend
+        .0
+        .contains(&SoundBackendCall::SetVolume(expected_source.clone(), 0.5)));
+    assert!(backend
+        .0
+        .contains(&SoundBackendCall::SetPan(expected_source.clone(), -1.0)));
+    assert_eq!(
+        backend.0.last(),
+        Some(&SoundBackendCall::Play(expected_source))
+    );
+}
+
+#[test]
+fn unloading_script_should_stop_its_sounds_and_drop_its_events() {
+    let script = r#"
+        OBJECT=TESTSND
+        TESTSND:TYPE=SOUND
+        TESTSND:FILENAME=SFX.WAV
+        TESTSND:ONSTARTED={TESTSND^STOP();}
+        "#;
+    let runner = create_runner_with_script(MemoryFileSystem::default(), script);
+    runner
+        .get_object("TESTSND")
+        .unwrap()
+        .call_method(CallableIdentifier::Method("PLAY"), &Vec::new(), None)
+        .unwrap();
+    assert!(!runner.internal_events.borrow().is_empty());
+
+    runner
+        .unload_script(&ScenePath::new(".", "SCRIPT.CNV"))
+        .unwrap();
+
+    let expected_source = SoundSource::Sound {
+        script_path: ScenePath::new(".", "SCRIPT.CNV"),
+        object_name: "TESTSND".into(),
+        instance: 0,
+    };
+    assert_eq!(
+        runner.events_out.sound.borrow().back(),
+        Some(&SoundEvent::SoundStopped(expected_source))
+    );
+    assert!(runner.internal_events.borrow().is_empty());
+    assert!(runner.get_object("TESTSND").is_none());
+}
+
+#[test]
+fn finished_events_should_only_count_for_the_latest_playback() {
+    let script = r#"
+        OBJECT=TESTSND
+        TESTSND:TYPE=SOUND
+        TESTSND:FILENAME=SFX.WAV
+        TESTSND:ONFINISHED={FINISHEDCOUNT^INC();}
+
+        OBJECT=FINISHEDCOUNT
+        FINISHEDCOUNT:TYPE=INTEGER
+        FINISHEDCOUNT:VALUE=0
+        "#;
+    let runner = create_runner_with_script(MemoryFileSystem::default(), script);
+    let test_snd_object = runner.get_object("TESTSND").unwrap();
+    for _ in 0..2 {
+        test_snd_object
+            .call_method(CallableIdentifier::Method("PLAY"), &Vec::new(), None)
+            .unwrap();
+    }
+    let started_instances: Vec<_> = runner
+        .events_out
+        .sound
+        .borrow()
+        .iter()
+        .filter_map(|e| match e {
+            SoundEvent::SoundStarted(SoundSource::Sound { instance, .. }) => Some(*instance),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(started_instances, vec![1, 2]);
+    runner.step().unwrap();
+    let finish =
+        |instance: usize| {
+            runner.events_in.multimedia.borrow_mut().push_back(
+                MultimediaEvents::SoundFinishedPlaying(SoundSource::Sound {
+                    script_path: ScenePath::new(".", "SCRIPT.CNV"),
+                    object_name: "TESTSND".into(),
+                    instance,
+                }),
+            );
+            runner.step().unwrap();
+            (
+                test_snd_object
+                    .call_method(CallableIdentifier::Method("ISPLAYING"), &Vec::new(), None)
+                    .unwrap(),
+                runner
+                    .get_object("FINISHEDCOUNT")
+                    .unwrap()
+                    .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+                    .unwrap(),
+            )
+        };
+
+    // the second playback replaced the first one, which is never reported as finished
+    assert_eq!(finish(1), (CnvValue::Bool(true), CnvValue::Integer(0)));
+    assert_eq!(finish(2), (CnvValue::Bool(false), CnvValue::Integer(1)));
+    assert_eq!(finish(2), (CnvValue::Bool(false), CnvValue::Integer(1)));
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case(CnvValue::String("DEF".into()), "ABCDEF" ; "string")]
+#[test_case(CnvValue::Integer(42), "ABC42" ; "integer")]
+#[test_case(CnvValue::Double(2.5), "ABC2.5" ; "double")]
+fn add_should_append_coerced_value(argument: CnvValue, expected: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTSTR
+        TESTSTR:TYPE=STRING
+        TESTSTR:VALUE=ABC
+        TESTSTR:ONCHANGED={CHANGECOUNT^INC();}
+
+        OBJECT=CHANGECOUNT
+        CHANGECOUNT:TYPE=INTEGER
+        CHANGECOUNT:VALUE=0
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_str_object = runner.get_object("TESTSTR").unwrap();
+
+    let result = test_str_object
+        .call_method(CallableIdentifier::Method("ADD"), &[argument], None)
+        .unwrap();
+    runner.step().unwrap();
+
+    assert_eq!(result, CnvValue::String(expected.into()));
+    assert_eq!(
+        test_str_object
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::String(expected.into())
+    );
+    assert_eq!(
+        runner
+            .get_object("CHANGECOUNT")
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::Integer(1)
+    );
+}
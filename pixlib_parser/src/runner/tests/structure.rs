@@ -0,0 +1,134 @@
+use super::*;
+
+use test_case::test_case;
+
+fn create_runner_with_structs(source_fields: &str, target_fields: &str) -> Arc<CnvRunner> {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=SOURCE
+        SOURCE:TYPE=STRUCT
+        SOURCE:FIELDS={}
+
+        OBJECT=TARGET
+        TARGET:TYPE=STRUCT
+        TARGET:FIELDS={}
+        "#,
+        source_fields, target_fields
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner
+}
+
+fn set_field(object: &Arc<CnvObject>, name: &str, value: CnvValue) -> anyhow::Result<CnvValue> {
+    object.call_method(
+        CallableIdentifier::Method("SETFIELD"),
+        &[CnvValue::String(name.into()), value],
+        None,
+    )
+}
+
+fn get_field(object: &Arc<CnvObject>, name: &str) -> CnvValue {
+    object
+        .call_method(
+            CallableIdentifier::Method("GETFIELD"),
+            &[CnvValue::String(name.into())],
+            None,
+        )
+        .unwrap()
+}
+
+#[test]
+fn set_should_copy_fields_from_struct_with_identical_layout() {
+    let fields = "X<INTEGER>,LABEL<STRING>,ACTIVE<BOOL>";
+    let runner = create_runner_with_structs(fields, fields);
+    let source = runner.get_object("SOURCE").unwrap();
+    set_field(&source, "X", CnvValue::Integer(42)).unwrap();
+    set_field(&source, "LABEL", CnvValue::String("HERO".into())).unwrap();
+    set_field(&source, "ACTIVE", CnvValue::Bool(true)).unwrap();
+
+    let target = runner.get_object("TARGET").unwrap();
+    target
+        .call_method(
+            CallableIdentifier::Method("SET"),
+            &[CnvValue::String("SOURCE".into())],
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(get_field(&target, "X"), CnvValue::Integer(42));
+    assert_eq!(get_field(&target, "LABEL"), CnvValue::String("HERO".into()));
+    assert_eq!(get_field(&target, "ACTIVE"), CnvValue::Bool(true));
+}
+
+#[test_case(CnvValue::String("12".into()), Some(CnvValue::Double(12.0)))]
+#[test_case(CnvValue::Integer(3), Some(CnvValue::Double(3.0)))]
+#[test_case(CnvValue::String("ABC".into()), None)]
+fn set_should_coerce_mismatched_field_types(value: CnvValue, expected: Option<CnvValue>) {
+    let runner = create_runner_with_structs("X<STRING>", "X<DOUBLE>,Y<INTEGER>");
+    let source = runner.get_object("SOURCE").unwrap();
+    set_field(&source, "X", value).unwrap();
+
+    let target = runner.get_object("TARGET").unwrap();
+    let result = target.call_method(
+        CallableIdentifier::Method("SET"),
+        &[CnvValue::String("SOURCE".into())],
+        None,
+    );
+
+    assert_eq!(result.is_ok(), expected.is_some());
+    if let Some(expected) = expected {
+        assert_eq!(get_field(&target, "X"), expected);
+        assert_eq!(get_field(&target, "Y"), CnvValue::Integer(0));
+    }
+}
+
+#[test]
+fn set_field_should_reject_unknown_fields() {
+    let runner = create_runner_with_structs("X<INTEGER>", "X<INTEGER>");
+    let source = runner.get_object("SOURCE").unwrap();
+
+    assert!(set_field(&source, "Z", CnvValue::Integer(1)).is_err());
+}
+
+#[test]
+fn fields_should_be_listed_in_declaration_order_with_current_values() {
+    let runner = create_runner_with_structs(
+        "X<INTEGER>,LABEL<STRING>,SPEED<DOUBLE>,ACTIVE<BOOL>",
+        "X<INTEGER>",
+    );
+    let source = runner.get_object("SOURCE").unwrap();
+    set_field(&source, "ACTIVE", CnvValue::Bool(true)).unwrap();
+    set_field(&source, "X", CnvValue::Integer(42)).unwrap();
+    let CnvContent::Struct(ref source_struct) = &source.content else {
+        unreachable!();
+    };
+
+    assert_eq!(
+        source_struct.fields(),
+        vec![
+            ("X".to_owned(), CnvValue::Integer(42)),
+            ("LABEL".to_owned(), CnvValue::String(String::new())),
+            ("SPEED".to_owned(), CnvValue::Double(0.0)),
+            ("ACTIVE".to_owned(), CnvValue::Bool(true)),
+        ]
+    );
+    let dump = runner.dump_state();
+    assert!(
+        dump.contains("SOURCE: STRUCT X=42 LABEL=\"\" SPEED=0.0 ACTIVE=TRUE\n"),
+        "{}",
+        dump
+    );
+}
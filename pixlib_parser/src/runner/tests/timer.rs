@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn long_delta_should_fire_tick_for_each_elapsed_interval() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTTIMER
+        TESTTIMER:TYPE=TIMER
+        TESTTIMER:ELAPSE=100
+        TESTTIMER:ONTICK={TICKCOUNT^INC();}
+        TESTTIMER:ONTICK^3={THIRDTICK^SET(TRUE);}
+
+        OBJECT=TICKCOUNT
+        TICKCOUNT:TYPE=INTEGER
+        TICKCOUNT:VALUE=0
+
+        OBJECT=THIRDTICK
+        THIRDTICK:TYPE=BOOL
+        THIRDTICK:VALUE=FALSE
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+
+    runner
+        .events_in
+        .timer
+        .borrow_mut()
+        .push_back(TimerEvent::Elapsed { seconds: 0.3 });
+    runner.step().unwrap();
+
+    let get = |name: &str| {
+        runner
+            .get_object(name)
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+            .unwrap()
+    };
+    // the third tick is handled by ONTICK^3 instead of the generic handler
+    assert_eq!(get("TICKCOUNT"), CnvValue::Integer(2));
+    assert_eq!(get("THIRDTICK"), CnvValue::Bool(true));
+    assert_eq!(
+        runner
+            .get_object("TESTTIMER")
+            .unwrap()
+            .call_method(CallableIdentifier::Method("GETTICKS"), &Vec::new(), None)
+            .unwrap(),
+        CnvValue::Integer(3)
+    );
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+#[test]
+fn dispatched_events_should_be_recorded_when_trace_is_enabled() {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    runner.event_trace.borrow_mut().enable(16);
+    let script = r#"
+        OBJECT=CLICKCOUNT
+        CLICKCOUNT:TYPE=INTEGER
+        CLICKCOUNT:VALUE=0
+
+        OBJECT=TESTANIM
+        TESTANIM:TYPE=ANIMO
+        TESTANIM:ONINIT={CLICKCOUNT^SET(0);}
+        TESTANIM:ONCLICK={CLICKCOUNT^INC();}
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.step().unwrap();
+    let test_anim_object = runner.get_object("TESTANIM").unwrap();
+    runner
+        .internal_events
+        .borrow_mut()
+        .push_back(InternalEvent {
+            context: RunnerContext::new_minimal(&runner, &test_anim_object),
+            callable: CallableIdentifier::Event("ONCLICK").to_owned(),
+        });
+    runner.step().unwrap();
+
+    let trace = runner.event_trace.borrow_mut().drain();
+    let button_events = trace
+        .iter()
+        .filter(|e| {
+            e.object_name == "TESTANIM"
+                && matches!(&e.callable, CallableIdentifierOwned::Event(name) if name == "ONINIT" || name == "ONCLICK")
+        })
+        .map(|e| e.callable.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        button_events,
+        vec![
+            CallableIdentifierOwned::Event("ONINIT".into()),
+            CallableIdentifierOwned::Event("ONCLICK".into()),
+        ]
+    );
+    assert!(trace.iter().any(|e| e.object_name == "CLICKCOUNT"
+        && e.callable == CallableIdentifierOwned::Method("INC".into())
+        && e.result == Ok(CnvValue::Null)));
+
+    runner.event_trace.borrow_mut().disable();
+    test_anim_object
+        .call_method(CallableIdentifier::Event("ONCLICK"), &Vec::new(), None)
+        .unwrap();
+    assert_eq!(runner.event_trace.borrow().entries().count(), 0);
+}
@@ -0,0 +1,186 @@
+use crate::runner::parsers::ConditionOperator;
+
+use super::*;
+
+use test_case::test_case;
+
+#[test_case(CnvValue::String("10".into()), CnvValue::Integer(9), ConditionOperator::Greater, true)]
+#[test_case(CnvValue::String("10".into()), CnvValue::Integer(9), ConditionOperator::Less, false)]
+#[test_case(CnvValue::Integer(9), CnvValue::String("10".into()), ConditionOperator::Less, true)]
+#[test_case(CnvValue::String("10".into()), CnvValue::String("9".into()), ConditionOperator::Greater, true)]
+#[test_case(CnvValue::String("10.0".into()), CnvValue::Integer(10), ConditionOperator::Equal, true)]
+#[test_case(CnvValue::Double(2.5), CnvValue::String("2".into()), ConditionOperator::GreaterEqual, true)]
+#[test_case(CnvValue::String("\"5\"".into()), CnvValue::Integer(5), ConditionOperator::Equal, true)]
+#[test_case(CnvValue::String("\"10\"".into()), CnvValue::Integer(11), ConditionOperator::Less, true)]
+#[test_case(
+    CnvValue::Integer(3),
+    CnvValue::Double(3.0),
+    ConditionOperator::NotEqual,
+    false
+)]
+#[test_case(
+    CnvValue::Integer(3),
+    CnvValue::Integer(3),
+    ConditionOperator::LessEqual,
+    true
+)]
+fn numeric_values_should_be_compared_numerically(
+    left: CnvValue,
+    right: CnvValue,
+    operator: ConditionOperator,
+    expected: bool,
+) {
+    assert_eq!(left.compare(&right, &operator), expected);
+}
+
+#[test_case(CnvValue::String("10".into()), CnvValue::String("9A".into()), ConditionOperator::Less, true)]
+#[test_case(CnvValue::String("ABC".into()), CnvValue::String("ABD".into()), ConditionOperator::Less, true)]
+#[test_case(CnvValue::String("ABC".into()), CnvValue::String("AB".into()), ConditionOperator::Greater, true)]
+#[test_case(CnvValue::String("abc".into()), CnvValue::String("ABC".into()), ConditionOperator::Equal, false)]
+#[test_case(CnvValue::String("ABC".into()), CnvValue::String("ABC".into()), ConditionOperator::GreaterEqual, true)]
+#[test_case(CnvValue::Integer(5), CnvValue::String("X".into()), ConditionOperator::NotEqual, true)]
+#[test_case(CnvValue::String("\"".into()), CnvValue::String("A".into()), ConditionOperator::Less, true)]
+fn non_numeric_values_should_be_compared_lexically(
+    left: CnvValue,
+    right: CnvValue,
+    operator: ConditionOperator,
+    expected: bool,
+) {
+    assert_eq!(left.compare(&right, &operator), expected);
+}
+
+#[test_case("LESS", "FAILED")]
+#[test_case("GREATER", "SUCCESS")]
+fn condition_should_compare_numeric_strings_numerically(operator: &str, expected: &str) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    let script = format!(
+        r#"
+        OBJECT=RESULT
+        RESULT:TYPE=STRING
+
+        OBJECT=LEFT
+        LEFT:TYPE=STRING
+        LEFT:VALUE=10
+
+        OBJECT=TESTCOND
+        TESTCOND:TYPE=CONDITION
+        TESTCOND:OPERAND1=LEFT
+        TESTCOND:OPERAND2=9
+        TESTCOND:OPERATOR={}
+        TESTCOND:ONRUNTIMESUCCESS={{RESULT^SET("SUCCESS");}}
+        TESTCOND:ONRUNTIMEFAILED={{RESULT^SET("FAILED");}}
+        "#,
+        operator
+    );
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(&script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    let test_cond_object = runner.get_object("TESTCOND").unwrap();
+    test_cond_object
+        .call_method(CallableIdentifier::Method("CHECK"), &Vec::new(), None)
+        .unwrap();
+    runner.step().unwrap();
+    let result_object = runner.get_object("RESULT").unwrap();
+    let result = result_object
+        .call_method(CallableIdentifier::Method("GET"), &Vec::new(), None)
+        .unwrap();
+
+    assert_eq!(result, CnvValue::String(expected.into()));
+}
+
+fn assert_identical(actual: &CnvValue, expected: &CnvValue) {
+    match (actual, expected) {
+        (CnvValue::Integer(a), CnvValue::Integer(e)) => assert_eq!(a, e),
+        (CnvValue::Double(a), CnvValue::Double(e)) => assert_eq!(a.to_bits(), e.to_bits()),
+        (CnvValue::Bool(a), CnvValue::Bool(e)) => assert_eq!(a, e),
+        (CnvValue::String(a), CnvValue::String(e)) => assert_eq!(a, e),
+        (CnvValue::Null, CnvValue::Null) => {}
+        _ => panic!("Expected {:?}, got {:?}", expected, actual),
+    }
+}
+
+#[test_case(CnvValue::Integer(i32::MIN))]
+#[test_case(CnvValue::Integer(i32::MAX))]
+#[test_case(CnvValue::Double(1.0))]
+#[test_case(CnvValue::Double(-0.0))]
+#[test_case(CnvValue::Double(1e300))]
+#[test_case(CnvValue::Double(f64::MIN_POSITIVE))]
+#[test_case(CnvValue::Double(f64::INFINITY))]
+#[test_case(CnvValue::Bool(true))]
+#[test_case(CnvValue::Bool(false))]
+#[test_case(CnvValue::String("".into()))]
+#[test_case(CnvValue::String("TRUE".into()))]
+#[test_case(CnvValue::String("NULL".into()))]
+#[test_case(CnvValue::String("12".into()))]
+#[test_case(CnvValue::String("\"QUOTED\"".into()))]
+#[test_case(CnvValue::String("ZAŻÓŁĆ GĘŚLĄ JAŹŃ".into()) ; "cp1250 string")]
+#[test_case(CnvValue::Null)]
+fn values_should_round_trip_through_cnv_strings(value: CnvValue) {
+    let actual = CnvValue::from_cnv_string(&value.to_cnv_string());
+
+    assert_identical(&actual, &value);
+}
+
+#[test_case("TEXT", CnvValue::String("TEXT".into()))]
+#[test_case("true", CnvValue::String("true".into()))]
+#[test_case("2.50", CnvValue::Double(2.5))]
+#[test_case("-7", CnvValue::Integer(-7))]
+fn declarative_literals_should_be_parsed_from_cnv_strings(string: &str, expected: CnvValue) {
+    let actual = CnvValue::from_cnv_string(string);
+
+    assert_identical(&actual, &expected);
+}
+
+mod round_trip {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn integers_should_round_trip(i in any::<i32>()) {
+            let value = CnvValue::Integer(i);
+            assert_identical(&CnvValue::from_cnv_string(&value.to_cnv_string()), &value);
+        }
+
+        #[test]
+        fn doubles_should_round_trip(d in any::<f64>().prop_filter("NaN", |d| !d.is_nan())) {
+            let value = CnvValue::Double(d);
+            assert_identical(&CnvValue::from_cnv_string(&value.to_cnv_string()), &value);
+        }
+
+        #[test]
+        fn strings_should_round_trip(s in "\\PC*") {
+            let value = CnvValue::String(s);
+            assert_identical(&CnvValue::from_cnv_string(&value.to_cnv_string()), &value);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn values_and_rects_should_serialize_to_json() {
+    assert_eq!(
+        serde_json::to_value(CnvValue::Double(2.5)).unwrap(),
+        serde_json::json!({ "Double": 2.5 })
+    );
+    assert_eq!(
+        serde_json::to_value(Rect::from((-1, 2), (3, 4))).unwrap(),
+        serde_json::json!({
+            "top_left_x": -1,
+            "top_left_y": 2,
+            "bottom_right_x": 2,
+            "bottom_right_y": 6,
+        })
+    );
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+use test_case::test_case;
+
+#[test_case((800, 600), None)]
+#[test_case((1024, 768), Some(0))]
+fn buttons_should_be_hit_tested_against_resized_window(
+    window_size: (usize, usize),
+    expected: Option<usize>,
+) {
+    let runner = CnvRunner::try_new(
+        Arc::new(RwLock::new(DummyFileSystem)),
+        Default::default(),
+        (800, 600),
+    )
+    .unwrap();
+    let script = r#"
+        OBJECT=TESTBUTTON
+        TESTBUTTON:TYPE=BUTTON
+        "#;
+    runner
+        .load_script(
+            ScenePath::new(".", "SCRIPT.CNV"),
+            as_parser_input(script),
+            None,
+            ScriptSource::CnvLoader,
+        )
+        .unwrap();
+    runner.set_window_size(window_size.0, window_size.1);
+
+    let buttons = [ButtonDescriptor {
+        priority: 0,
+        object_index: ObjectIndex::default(),
+        object: runner.get_object("TESTBUTTON").unwrap(),
+        rect: Rect::from((1000, 750), (20, 10)),
+    }];
+    assert_eq!(
+        runner.find_relevant_button(&buttons, (1010, 755)).unwrap(),
+        expected
+    );
+    assert_eq!(
+        *runner.window_rect.borrow(),
+        Rect::from((0, 0), window_size)
+    );
+}
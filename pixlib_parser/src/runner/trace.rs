@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+use super::{CallableIdentifierOwned, CnvValue, ScenePath};
+
+/// A single method call or event dispatch recorded by [`EventTrace`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+    pub script_path: ScenePath,
+    pub object_name: String,
+    pub callable: CallableIdentifierOwned,
+    pub arguments: Vec<CnvValue>,
+    pub result: Result<CnvValue, String>,
+}
+
+/// Bounded record of calls made on objects, meant for debugging script flow.
+///
+/// Entries are recorded in order of completion, so nested calls precede the calls that made them.
+/// When the capacity is reached, the oldest entries are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct EventTrace {
+    capacity: Option<usize>,
+    entries: VecDeque<TraceEntry>,
+}
+
+impl EventTrace {
+    pub fn enable(&mut self, capacity: usize) {
+        self.capacity = Some(capacity);
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn disable(&mut self) {
+        self.capacity = None;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity.is_some()
+    }
+
+    pub fn record(&mut self, entry: TraceEntry) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        if capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.entries.iter()
+    }
+
+    pub fn drain(&mut self) -> Vec<TraceEntry> {
+        self.entries.drain(..).collect()
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     common::LoggableToOption,
     parser::ast::{Expression, IgnorableExpression, Invocation, Operation},
-    runner::{CallableIdentifier, RunnerError},
+    runner::{
+        classes::GeneralCondition, parsers::ConditionOperator, CallableIdentifier, CnvContent,
+        RunnerError,
+    },
 };
 
 use super::super::{CnvStatement, CnvValue, RunnerContext};
@@ -73,6 +76,24 @@ impl CnvExpression for Expression {
                         Operation::Subtraction => &result - &argument,
                         Operation::Division => &result / &argument,
                         Operation::Remainder => &result % &argument,
+                        Operation::Less => {
+                            CnvValue::Bool(result.compare(&argument, &ConditionOperator::Less))
+                        }
+                        Operation::LessEqual => {
+                            CnvValue::Bool(result.compare(&argument, &ConditionOperator::LessEqual))
+                        }
+                        Operation::Greater => {
+                            CnvValue::Bool(result.compare(&argument, &ConditionOperator::Greater))
+                        }
+                        Operation::GreaterEqual => CnvValue::Bool(
+                            result.compare(&argument, &ConditionOperator::GreaterEqual),
+                        ),
+                        Operation::Equal => {
+                            CnvValue::Bool(result.compare(&argument, &ConditionOperator::Equal))
+                        }
+                        Operation::NotEqual => {
+                            CnvValue::Bool(result.compare(&argument, &ConditionOperator::NotEqual))
+                        }
                     }
                 }
                 Ok(result)
@@ -95,7 +116,14 @@ impl CnvExpression for Invocation {
     fn calculate(&self, context: RunnerContext) -> anyhow::Result<CnvValue> {
         // log::trace!("Invocation::calculate: {:?} with context {}", self, context);
         if self.parent.is_none() {
-            Ok(CnvValue::Null) // TODO: match &self.name
+            match self.name.to_ascii_uppercase().as_ref() {
+                "IF" => run_if(&self.arguments, context),
+                "WHILE" => run_while(&self.arguments, context),
+                "BREAK" => Err(RunnerError::ExecutionInterrupted { one: false }.into()),
+                "ONEBREAK" => Err(RunnerError::ExecutionInterrupted { one: true }.into()),
+                "RETURN" => run_return(&self.arguments, context),
+                _ => Ok(CnvValue::Null), // TODO: handle remaining functions
+            }
         } else {
             let parent = self
                 .parent
@@ -137,3 +165,140 @@ impl CnvExpression for Invocation {
         }
     }
 }
+
+// @IF(CONDITION, THEN, ELSE) or @IF(LEFT, OPERATOR, RIGHT, THEN, ELSE)
+fn run_if(arguments: &[Expression], context: RunnerContext) -> anyhow::Result<CnvValue> {
+    let (condition, branches) = match arguments.len() {
+        3 => (&arguments[..1], &arguments[1..]),
+        5 => (&arguments[..3], &arguments[3..]),
+        actual if actual > 5 => {
+            return Err(RunnerError::TooManyArguments {
+                expected_max: 5,
+                actual,
+            }
+            .into())
+        }
+        actual => {
+            return Err(RunnerError::TooFewArguments {
+                expected_min: if actual < 3 { 3 } else { 5 },
+                actual,
+            }
+            .into())
+        }
+    };
+    if evaluate_condition(condition, context.clone())? {
+        run_branch(&branches[0], context)
+    } else {
+        run_branch(&branches[1], context)
+    }
+}
+
+// @RETURN([VALUE])
+fn run_return(arguments: &[Expression], context: RunnerContext) -> anyhow::Result<CnvValue> {
+    if arguments.len() > 1 {
+        return Err(RunnerError::TooManyArguments {
+            expected_max: 1,
+            actual: arguments.len(),
+        }
+        .into());
+    }
+    let value = match arguments.first() {
+        Some(argument) => calculate_operand(argument, context)?,
+        None => CnvValue::Null,
+    };
+    Err(RunnerError::ExecutionReturned { value }.into())
+}
+
+/// Iterations after which a `@WHILE` loop is considered stuck.
+const MAX_WHILE_ITERATIONS: usize = 100_000;
+
+// @WHILE(LEFT, OPERATOR, RIGHT, BODY)
+fn run_while(arguments: &[Expression], context: RunnerContext) -> anyhow::Result<CnvValue> {
+    match arguments.len() {
+        4 => {}
+        actual if actual > 4 => {
+            return Err(RunnerError::TooManyArguments {
+                expected_max: 4,
+                actual,
+            }
+            .into())
+        }
+        actual => {
+            return Err(RunnerError::TooFewArguments {
+                expected_min: 4,
+                actual,
+            }
+            .into())
+        }
+    }
+    let mut iteration_count = 0;
+    while evaluate_condition(&arguments[..3], context.clone())? {
+        iteration_count += 1;
+        if iteration_count > MAX_WHILE_ITERATIONS {
+            return Err(RunnerError::LoopLimitExceeded {
+                limit: MAX_WHILE_ITERATIONS,
+            }
+            .into());
+        }
+        if let Err(e) = run_branch(&arguments[3], context.clone()) {
+            match e.downcast_ref::<RunnerError>() {
+                Some(RunnerError::ExecutionInterrupted { one: false }) => break,
+                Some(RunnerError::ExecutionInterrupted { one: true }) => continue,
+                _ => return Err(e),
+            }
+        }
+    }
+    Ok(CnvValue::Null)
+}
+
+fn evaluate_condition(arguments: &[Expression], context: RunnerContext) -> anyhow::Result<bool> {
+    if let [left, operator, right] = arguments {
+        let operator = operator.calculate(context.clone())?.to_str();
+        let operator = ConditionOperator::parse_inline(&operator)
+            .ok_or(RunnerError::InvalidOperator { operator })?;
+        let left = calculate_operand(left, context.clone())?;
+        let right = calculate_operand(right, context)?;
+        return Ok(left.compare(&right, &operator));
+    }
+    let name = arguments[0].calculate(context.clone())?.to_str();
+    let name = name.trim_matches('"');
+    let object = context
+        .runner
+        .get_object(name)
+        .ok_or(RunnerError::ObjectNotFound {
+            name: name.to_owned(),
+        })?;
+    let condition: &dyn GeneralCondition = match &object.content {
+        CnvContent::Condition(c) => c,
+        CnvContent::ComplexCondition(c) => c,
+        _ => return Err(RunnerError::ExpectedConditionObject.into()),
+    };
+    condition.check(Some(context))
+}
+
+fn calculate_operand(expression: &Expression, context: RunnerContext) -> anyhow::Result<CnvValue> {
+    let value = expression.calculate(context.clone())?;
+    Ok(if let Expression::Identifier(_) = expression {
+        value.resolve(context)?
+    } else {
+        value
+    })
+}
+
+fn run_branch(expression: &Expression, context: RunnerContext) -> anyhow::Result<CnvValue> {
+    if let Expression::Block(_) = expression {
+        return expression.calculate(context);
+    }
+    let name = expression.calculate(context.clone())?.to_str();
+    let name = name.trim_matches('"');
+    if name.is_empty() || name.eq_ignore_ascii_case("NULL") {
+        return Ok(CnvValue::Null);
+    }
+    let object = context
+        .runner
+        .get_object(name)
+        .ok_or(RunnerError::ObjectNotFound {
+            name: name.to_owned(),
+        })?;
+    object.call_method(CallableIdentifier::Method("RUN"), &[], Some(context))
+}
@@ -2,4 +2,4 @@ mod expression;
 mod statement;
 
 pub use expression::CnvExpression;
-pub use statement::CnvStatement;
+pub use statement::{calculate_script, CnvStatement};
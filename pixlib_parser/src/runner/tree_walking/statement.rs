@@ -1,6 +1,6 @@
 use crate::parser::ast::{ParsedScript, Statement};
 
-use super::super::{CnvExpression, RunnerContext};
+use super::super::{CnvExpression, CnvValue, RunnerContext, RunnerError};
 
 pub trait CnvStatement {
     fn run(&self, context: RunnerContext) -> anyhow::Result<()>;
@@ -21,7 +21,19 @@ impl CnvStatement for Statement {
 impl CnvStatement for ParsedScript {
     fn run(&self, context: RunnerContext) -> anyhow::Result<()> {
         // log::trace!("ParsedScript::run: {:?}", self);
-        self.calculate(context)?;
+        calculate_script(self, context)?;
         Ok(())
     }
 }
+
+/// Calculates a whole script, ending it early with the value passed to `@RETURN`.
+pub fn calculate_script(script: &ParsedScript, context: RunnerContext) -> anyhow::Result<CnvValue> {
+    match script.calculate(context) {
+        Err(e) => match e.downcast::<RunnerError>() {
+            Ok(RunnerError::ExecutionReturned { value }) => Ok(value),
+            Ok(e) => Err(e.into()),
+            Err(e) => Err(e),
+        },
+        result => result,
+    }
+}
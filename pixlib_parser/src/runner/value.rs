@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     fmt::Display,
     ops::{Add, Div, Mul, Rem, Sub},
     sync::Arc,
@@ -8,9 +9,12 @@ use log::error;
 
 use crate::runner::{content::CnvContent, CnvObject};
 
-use super::RunnerContext;
+use super::{parsers::ConditionOperator, RunnerContext, RunnerError};
+
+const MAX_REFERENCE_DEPTH: usize = 64;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CnvValue {
     Integer(i32),
     Double(f64),
@@ -40,6 +44,9 @@ impl CnvValue {
         self
     }
 
+    // string values may still carry the quotes they were declared with,
+    // so one level of them is stripped before coercing to a non-string type
+
     pub fn to_int(&self) -> i32 {
         match self {
             CnvValue::Integer(i) => *i,
@@ -51,7 +58,7 @@ impl CnvValue {
                     0
                 }
             }
-            CnvValue::String(s) => s.parse().unwrap(),
+            CnvValue::String(s) => trim_one_quotes_level(s).parse().unwrap(),
             CnvValue::Null => 0,
         }
     }
@@ -67,7 +74,7 @@ impl CnvValue {
                     0.0
                 }
             }
-            CnvValue::String(s) => s
+            CnvValue::String(s) => trim_one_quotes_level(s)
                 .parse()
                 .inspect_err(|e| error!("{} for string->double {}", e, s))
                 .unwrap(),
@@ -80,7 +87,7 @@ impl CnvValue {
             CnvValue::Integer(i) => *i == 1,  // TODO: check
             CnvValue::Double(d) => *d == 1.0, // TODO: check
             CnvValue::Bool(b) => *b,
-            CnvValue::String(s) => !s.is_empty(), // TODO: check
+            CnvValue::String(s) => !trim_one_quotes_level(s).is_empty(), // TODO: check
             CnvValue::Null => false,
         }
     }
@@ -95,20 +102,134 @@ impl CnvValue {
         }
     }
 
-    pub fn resolve(self, context: RunnerContext) -> CnvValue {
-        match &self {
-            CnvValue::String(s) => context
-                .runner
-                .get_object(s)
-                // .inspect(|v| log::trace!("Resolving {:?} through {}", &self, v.name))
-                .as_ref()
-                .map(get_reference_value)
-                .transpose()
-                .unwrap()
-                .flatten()
-                // .inspect(|v| log::trace!("Resolved into {}", v))
-                .unwrap_or(CnvValue::String(trim_one_quotes_level(s).to_owned())), // TODO: modify with caution, the logic is very subtle
-            _ => self,
+    /// Formats the value the way it would be written in a declarative script.
+    ///
+    /// Strings are always quoted so that they are not confused with other literals,
+    /// doubles always keep their decimal point or exponent.
+    pub fn to_cnv_string(&self) -> String {
+        match self {
+            CnvValue::Integer(i) => i.to_string(),
+            CnvValue::Double(d) => format!("{:?}", d),
+            CnvValue::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_owned(),
+            CnvValue::String(s) => format!("\"{}\"", s),
+            CnvValue::Null => "NULL".to_owned(),
+        }
+    }
+
+    /// Parses a value formatted by [`CnvValue::to_cnv_string`].
+    ///
+    /// Unquoted text which is not a valid literal is treated as a bare string.
+    pub fn from_cnv_string(string: &str) -> CnvValue {
+        if string.len() >= 2 && string.starts_with('"') && string.ends_with('"') {
+            return CnvValue::String(trim_one_quotes_level(string).to_owned());
+        }
+        match string {
+            "TRUE" => return CnvValue::Bool(true),
+            "FALSE" => return CnvValue::Bool(false),
+            "NULL" => return CnvValue::Null,
+            _ => {}
+        }
+        if let Ok(i) = string.parse::<i32>() {
+            CnvValue::Integer(i)
+        } else if let Ok(d) = string.parse::<f64>() {
+            CnvValue::Double(d)
+        } else {
+            CnvValue::String(string.to_owned())
+        }
+    }
+
+    /// Compares two values the way CONDITION objects do.
+    ///
+    /// If both values are numeric (integers, doubles or strings parseable as numbers),
+    /// they are compared as numbers: as integers if both are integral, as doubles otherwise.
+    /// Else, if a boolean or NULL is involved, EQUAL and NOTEQUAL fall back to the loose
+    /// equality of `PartialEq`. All remaining cases compare the string representations
+    /// lexically (case-sensitive).
+    pub fn compare(&self, other: &CnvValue, operator: &ConditionOperator) -> bool {
+        let is_equality = matches!(
+            operator,
+            ConditionOperator::Equal | ConditionOperator::NotEqual
+        );
+        let is_loose = [self, other]
+            .iter()
+            .any(|v| matches!(v, CnvValue::Bool(_) | CnvValue::Null));
+        let ordering = match (self.as_number(), other.as_number()) {
+            (Some(Number::Integer(l)), Some(Number::Integer(r))) => l.partial_cmp(&r),
+            (Some(l), Some(r)) => l.to_dbl().partial_cmp(&r.to_dbl()),
+            _ if is_equality && is_loose => {
+                return (self == other) == matches!(operator, ConditionOperator::Equal);
+            }
+            _ => Some(self.to_str().cmp(&other.to_str())),
+        };
+        let Some(ordering) = ordering else {
+            return matches!(operator, ConditionOperator::NotEqual);
+        };
+        match operator {
+            ConditionOperator::Equal => ordering == Ordering::Equal,
+            ConditionOperator::NotEqual => ordering != Ordering::Equal,
+            ConditionOperator::Less => ordering == Ordering::Less,
+            ConditionOperator::LessEqual => ordering != Ordering::Greater,
+            ConditionOperator::Greater => ordering == Ordering::Greater,
+            ConditionOperator::GreaterEqual => ordering != Ordering::Less,
+        }
+    }
+
+    fn as_number(&self) -> Option<Number> {
+        match self {
+            CnvValue::Integer(i) => Some(Number::Integer(*i)),
+            CnvValue::Double(d) => Some(Number::Double(*d)),
+            CnvValue::String(s) => {
+                let s = trim_one_quotes_level(s);
+                if let Ok(i) = s.parse::<i32>() {
+                    Some(Number::Integer(i))
+                } else {
+                    s.parse::<f64>()
+                        .ok()
+                        .filter(|d| d.is_finite())
+                        .map(Number::Double)
+                }
+            }
+            CnvValue::Bool(_) | CnvValue::Null => None,
+        }
+    }
+
+    /// Replaces a string naming a value object with that object's value.
+    ///
+    /// Resolution may recurse (e.g. through expression operands), so it fails with
+    /// [`RunnerError::ReferenceDepthExceeded`] instead of overflowing the stack on cycles.
+    pub fn resolve(self, context: RunnerContext) -> anyhow::Result<CnvValue> {
+        let CnvValue::String(s) = &self else {
+            return Ok(self);
+        };
+        let Some(object) = context.runner.get_object(s) else {
+            return Ok(CnvValue::String(trim_one_quotes_level(s).to_owned()));
+        };
+        {
+            let mut depth = context.runner.reference_depth.borrow_mut();
+            if *depth >= MAX_REFERENCE_DEPTH {
+                return Err(RunnerError::ReferenceDepthExceeded { name: s.clone() }.into());
+            }
+            *depth += 1;
+        }
+        // log::trace!("Resolving {:?} through {}", &self, object.name);
+        let value = get_reference_value(&object);
+        *context.runner.reference_depth.borrow_mut() -= 1;
+        // TODO: modify with caution, the logic is very subtle
+        Ok(value?.unwrap_or(CnvValue::String(trim_one_quotes_level(s).to_owned())))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Integer(i32),
+    Double(f64),
+}
+
+impl Number {
+    fn to_dbl(self) -> f64 {
+        match self {
+            Number::Integer(i) => i.into(),
+            Number::Double(d) => d,
         }
     }
 }
@@ -126,9 +247,8 @@ fn get_reference_value(r: &Arc<CnvObject>) -> anyhow::Result<Option<CnvValue>> {
 }
 
 fn trim_one_quotes_level(string: &str) -> &str {
-    let start: usize = if string.starts_with('"') { 1 } else { 0 };
-    let end: usize = string.len() - if string.ends_with('"') { 1 } else { 0 };
-    &string[start..end]
+    let string = string.strip_prefix('"').unwrap_or(string);
+    string.strip_suffix('"').unwrap_or(string)
 }
 
 impl Add for &CnvValue {
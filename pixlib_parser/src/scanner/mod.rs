@@ -42,6 +42,10 @@ impl CnvFile {
 }
 
 pub fn parse_cnv(input: &[u8]) -> CnvFile {
+    parse_cnv_with_settings(input, Default::default())
+}
+
+pub fn parse_cnv_with_settings(input: &[u8], settings: ScanningSettings) -> CnvFile {
     let mut input = input.iter().map(|b| Ok(*b)).peekable();
     let mut first_line = Vec::<u8>::new();
     while let Some(res) =
@@ -72,7 +76,7 @@ pub fn parse_cnv(input: &[u8]) -> CnvFile {
         Err(err) => panic!("{}", err),
     };
     let decoder = CodepageDecoder::new(&CP1250_LUT, input);
-    let scanner = CnvScanner::new(decoder);
+    let scanner = CnvScanner::with_settings(decoder, settings);
     CnvFile(scanner.map(|r| r.unwrap().1).collect())
 }
 
@@ -558,33 +562,76 @@ impl<'lut, I: Iterator<Item = IoReadResult>> Iterator for CodepageDecoder<'lut,
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ScanningSettings {
+    /// Replace every kind of line break (`\n`, `\r\n`, `\n\r`, lone `\r`, `\x1e`) with `\n`.
+    pub normalize_newlines: bool,
+    /// Drop spaces and tabs directly preceding a line break or the end of input.
+    pub trim_trailing_whitespace: bool,
+}
+
+impl Default for ScanningSettings {
+    fn default() -> Self {
+        Self {
+            normalize_newlines: true,
+            trim_trailing_whitespace: false,
+        }
+    }
+}
+
 pub struct CnvScanner<I: Iterator<Item = ScannerInput>> {
     input: I,
+    settings: ScanningSettings,
     buffer: Vec<char>,
     next_position: Position,
+    pending_newline: Option<NewlineDetails>,
 }
 
 impl<I: Iterator<Item = ScannerInput>> CnvScanner<I> {
     const BUFFER_SIZE: usize = 2;
 
     pub fn new(input: I) -> Self {
+        Self::with_settings(input, Default::default())
+    }
+
+    pub fn with_settings(input: I, settings: ScanningSettings) -> Self {
         Self {
             input,
+            settings,
             buffer: Vec::with_capacity(Self::BUFFER_SIZE),
             next_position: Position::default(),
+            pending_newline: None,
         }
     }
 
     fn refill_buffer(&mut self) -> std::io::Result<()> {
-        while self.buffer.len() < Self::BUFFER_SIZE {
+        self.fill_buffer_to(Self::BUFFER_SIZE).map(|_| ())
+    }
+
+    fn fill_buffer_to(&mut self, size: usize) -> std::io::Result<bool> {
+        while self.buffer.len() < size {
             match self.input.next() {
                 Some(Ok(character)) => self.buffer.push(character),
                 Some(Err(err)) if err.kind() == std::io::ErrorKind::Interrupted => continue,
                 Some(Err(err)) => return Err(err),
-                None => return Ok(()),
+                None => return Ok(false),
             };
         }
-        Ok(())
+        Ok(true)
+    }
+
+    /// Returns the length of the run of spaces and tabs at the start of the buffer
+    /// if it is followed by a line break or the end of input.
+    fn match_trailing_whitespace(&mut self) -> std::io::Result<Option<usize>> {
+        let mut length = 0;
+        while self.fill_buffer_to(length + 1)? {
+            match self.buffer[length] {
+                ' ' | '\t' => length += 1,
+                '\n' | '\r' | '\u{1e}' => break,
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(length).filter(|l| *l > 0))
     }
 
     fn match_newline(&mut self) -> Option<NewlineDetails> {
@@ -606,14 +653,39 @@ impl<I: Iterator<Item = ScannerInput>> Iterator for CnvScanner<I> {
         if let Err(err) = self.refill_buffer() {
             return Some(Err(err));
         }
+        if self.pending_newline.is_none() && self.settings.trim_trailing_whitespace {
+            match self.match_trailing_whitespace() {
+                Ok(Some(length)) => {
+                    for _ in 0..length {
+                        self.next_position = self.next_position.with_incremented_column();
+                    }
+                    self.buffer.drain(..length);
+                    if let Err(err) = self.refill_buffer() {
+                        return Some(Err(err));
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+        }
         if self.buffer.is_empty() {
             None
         } else {
             let start = self.next_position;
-            let character = if let Some(NewlineDetails { length }) = self.match_newline() {
-                self.next_position = self.next_position.with_incremented_line(length);
-                self.buffer.drain(..length);
-                '\n'
+            let newline = self.pending_newline.take().or_else(|| self.match_newline());
+            let character = if let Some(NewlineDetails { length }) = newline {
+                if self.settings.normalize_newlines {
+                    self.next_position = self.next_position.with_incremented_line(length);
+                    self.buffer.drain(..length);
+                    '\n'
+                } else if length > 1 {
+                    self.pending_newline = Some(NewlineDetails { length: length - 1 });
+                    self.next_position = self.next_position.with_incremented_column();
+                    self.buffer.remove(0)
+                } else {
+                    self.next_position = self.next_position.with_incremented_line(1);
+                    self.buffer.remove(0)
+                }
             } else {
                 self.next_position = self.next_position.with_incremented_column();
                 self.buffer.remove(0)
@@ -623,6 +695,7 @@ impl<I: Iterator<Item = ScannerInput>> Iterator for CnvScanner<I> {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
 struct NewlineDetails {
     pub length: usize,
 }
@@ -743,6 +816,64 @@ mod tests {
         }
     }
 
+    fn parse_declarations(input: &[u8], settings: ScanningSettings) -> Vec<String> {
+        let file = parse_cnv_with_settings(input, settings);
+        declarative_parser::DeclarativeParser::new(
+            file.as_parser_input(),
+            Default::default(),
+            Default::default(),
+        )
+        .map(|result| format!("{:?}", result.unwrap().1))
+        .collect()
+    }
+
+    #[test_case(ScanningSettings::default() ; "normalized newlines")]
+    #[test_case(ScanningSettings { trim_trailing_whitespace: true, ..Default::default() } ; "trimmed whitespace")]
+    fn mixed_line_endings_should_parse_like_line_feeds(settings: ScanningSettings) {
+        let lines = ["OBJECT=TEST", "TEST:TYPE=INTEGER", "TEST:VALUE=5", ""];
+        let canonical = lines.join("\n");
+        let mixed = ["OBJECT=TEST\r\n", "TEST:TYPE=INTEGER\r", "TEST:VALUE=5\n\r"].concat();
+        assert_eq!(
+            parse_cnv_with_settings(mixed.as_bytes(), settings.clone()).0,
+            canonical.chars().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            parse_declarations(mixed.as_bytes(), settings),
+            parse_declarations(canonical.as_bytes(), Default::default())
+        );
+    }
+
+    #[test_case("VALUE=5  \r\nNEXT \t", "VALUE=5\nNEXT" ; "before newline and end of input")]
+    #[test_case("A B\t C\n", "A B\t C\n" ; "inside line")]
+    fn trailing_whitespace_should_be_trimmed_when_enabled(input: &str, expected: &str) {
+        let settings = ScanningSettings {
+            trim_trailing_whitespace: true,
+            ..Default::default()
+        };
+        let scanner = CnvScanner::with_settings(iter_from(input), settings);
+        assert_eq!(scanner.map(|r| r.unwrap().1).collect::<String>(), expected);
+    }
+
+    #[test]
+    fn newlines_should_be_kept_when_normalization_disabled() {
+        let input = "A\r\nB\rC";
+        let settings = ScanningSettings {
+            normalize_newlines: false,
+            ..Default::default()
+        };
+        let scanner = CnvScanner::with_settings(iter_from(input), settings);
+        let output = scanner.map(|r| r.unwrap()).collect::<Vec<_>>();
+        assert_eq!(output.iter().map(|(_, c, _)| c).collect::<String>(), input);
+        assert_eq!(
+            output.last().unwrap().2,
+            Position {
+                character: input.len(),
+                line: 3,
+                column: 2,
+            }
+        );
+    }
+
     #[test]
     fn io_error_should_be_passed_through_properly() {
         let expected_err_kind = std::io::ErrorKind::TimedOut;
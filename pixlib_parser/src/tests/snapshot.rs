@@ -181,11 +181,10 @@ struct VirtualFilesystem(pub HashMap<Path, RwFileBuffer>);
 
 impl FileSystem for VirtualFilesystem {
     fn read_file(&mut self, filename: &str) -> std::io::Result<Arc<Vec<u8>>> {
-        if let Some(file) = self
-            .0
-            .iter_mut()
-            .find(|(k, _)| k.as_ref().eq_ignore_ascii_case(filename))
-            .map(|(_, v)| v)
+        if let Some(file) = ResolveStrategy::default()
+            .resolve(filename, self.0.keys().map(|k| k.as_ref()))
+            .map(Path::from)
+            .and_then(|k| self.0.get_mut(&k))
         {
             let mut wrapped_vec = Arc::new(Vec::new());
             let vec = Arc::get_mut(&mut wrapped_vec).unwrap();
@@ -198,11 +197,10 @@ impl FileSystem for VirtualFilesystem {
     }
 
     fn write_file(&mut self, filename: &str, data: &[u8]) -> std::io::Result<()> {
-        if let Some(file) = self
-            .0
-            .iter_mut()
-            .find(|(k, _)| k.as_ref().eq_ignore_ascii_case(filename))
-            .map(|(_, v)| v)
+        if let Some(file) = ResolveStrategy::default()
+            .resolve(filename, self.0.keys().map(|k| k.as_ref()))
+            .map(Path::from)
+            .and_then(|k| self.0.get_mut(&k))
         {
             file.rewind()?;
             file.set_len(0)?;
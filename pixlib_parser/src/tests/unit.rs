@@ -7,6 +7,7 @@ use crate::common::LoggableToOption;
 use crate::filesystems::{DummyFileSystem, GameDirectory};
 use crate::runner::*;
 use object::CnvObjectBuilder;
+use pixlib_formats::{detect_format, FileFormat};
 #[allow(unused)]
 use test_case::test_case;
 
@@ -182,7 +183,7 @@ fn ensure_object_type_can_be_created(object_type: &str, object_properties: &[(&s
 #[cfg_attr(any(feature = "test_ric", feature = "test_riwc"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETOPACITY", &[], CnvValue::Integer(0)))]
 #[cfg_attr(any(feature = "test_risp8", feature = "test_riu8", feature = "test_ric", feature = "test_riwc", feature = "test_rikn", feature = "test_rikwa"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETPOSITIONX", &[], CnvValue::Integer(10)))]
 #[cfg_attr(any(feature = "test_risp8", feature = "test_riu8", feature = "test_ric", feature = "test_riwc", feature = "test_rikn", feature = "test_rikwa"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETPOSITIONY", &[], CnvValue::Integer(10)))]
-#[cfg_attr(any(feature = "test_ric", feature = "test_riwc", feature = "test_rikn"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETPRIORITY", &[], CnvValue::Null))]
+#[cfg_attr(any(feature = "test_ric", feature = "test_riwc", feature = "test_rikn"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETPRIORITY", &[], CnvValue::Integer(0)))]
 #[cfg_attr(any(feature = "test_ric", feature = "test_riwc", feature = "test_rikn", feature = "test_rikwa"), test_case("ANIMO", ANIMATION_PROPERTIES, "GETWIDTH", &[], CnvValue::Integer(0)))]
 #[cfg_attr(any(feature = "test_risp8", feature = "test_riu8", feature = "test_ric", feature = "test_riwc", feature = "test_rikn", feature = "test_rikwa"), test_case("ANIMO", ANIMATION_PROPERTIES, "HIDE", &[], CnvValue::Null))]
 #[cfg_attr(any(feature = "test_rikn"), test_case("ANIMO", ANIMATION_PROPERTIES, "INVALIDATE", &[], CnvValue::Null))]
@@ -722,6 +723,22 @@ fn ensure_method_is_implemented(
     assert_eq!(result, expected);
 }
 
+#[test_case("test.ann", FileFormat::Ann)]
+#[test_case("test.img", FileFormat::Img)]
+#[test_case("helper.img", FileFormat::Img)]
+#[test_case("test.seq", FileFormat::Seq)]
+#[test_case("test.wav", FileFormat::Wav)]
+fn unit_asset_format_should_be_detected(filename: &str, expected: FileFormat) {
+    let path = PathBuf::from_iter([
+        env!("CARGO_MANIFEST_DIR"),
+        "src/tests/unit_assets",
+        filename,
+    ]);
+    let data = std::fs::read(path).unwrap();
+
+    assert_eq!(detect_format(&data), Some(expected));
+}
+
 #[allow(unused)]
 fn create_object(
     parent: &Arc<CnvScript>,